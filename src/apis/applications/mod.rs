@@ -2,6 +2,8 @@ pub mod models;
 pub mod params;
 
 use crate::apis::client::Client;
+use crate::apis::generated::applications as operations;
+use crate::apis::ids::AppName;
 use crate::errors::Result;
 
 pub struct Applications<'c> {
@@ -17,16 +19,16 @@ impl<'c> Applications<'c> {
 impl Applications<'_> {
     /// List all applications
     pub async fn list(&self) -> Result<Vec<models::Application>> {
-        self.client.get("/applications").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// Get details of an application.
     pub async fn get(
         &self,
-        application_name: impl Into<String> + Send,
+        application_name: impl Into<AppName> + Send,
     ) -> Result<models::Application> {
         self.client
-            .get(format!("/applications/{}", application_name.into()).as_str())
+            .call(&operations::GET, &[&application_name.into()], &(), ())
             .await
     }
 
@@ -38,11 +40,7 @@ impl Applications<'_> {
         request: params::SubscribeRequest,
     ) -> Result<models::Application> {
         self.client
-            .post_with_query(
-                format!("/applications/{}/subscription", request.name).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::SUBSCRIBE, &[&request.name], &request, ())
             .await
     }
 
@@ -54,10 +52,7 @@ impl Applications<'_> {
         request: params::UnSubscribeRequest,
     ) -> Result<models::Application> {
         self.client
-            .delete_with_query(
-                format!("/applications/{}/subscription", request.name).as_str(),
-                &request,
-            )
+            .call(&operations::UNSUBSCRIBE, &[&request.name], &request, ())
             .await
     }
 
@@ -69,10 +64,7 @@ impl Applications<'_> {
         request: params::FilterEventsRequest,
     ) -> Result<models::Application> {
         self.client
-            .put(
-                format!("/applications/{}/eventFilter", request.name).as_str(),
-                request,
-            )
+            .call(&operations::FILTER, &[&request.name], &(), &request)
             .await
     }
 }
@@ -1,22 +1,25 @@
-use serde::Deserialize;
+use crate::apis::ids::{AppName, BridgeId, ChannelId, EndpointRef};
+use crate::ws::models::EventKind;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 
 /// Application : Details of a Stasis application
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Application {
     /// Name of this application
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: AppName,
     /// Id's for channels subscribed to.,
     #[serde(rename = "channel_ids")]
-    pub channel_ids: Vec<String>,
+    pub channel_ids: Vec<ChannelId>,
 
     /// Id's for bridges subscribed to.,
     #[serde(rename = "bridge_ids")]
-    pub bridge_ids: Vec<String>,
+    pub bridge_ids: Vec<BridgeId>,
 
     /// {tech}/{resource} for endpoints subscribed to.,
     #[serde(rename = "endpoint_ids")]
-    pub endpoint_ids: Vec<String>,
+    pub endpoint_ids: Vec<EndpointRef>,
 
     /// Names of the devices subscribed to.,
     #[serde(rename = "device_names")]
@@ -24,9 +27,93 @@ pub struct Application {
 
     /// Event types sent to the application.,
     #[serde(rename = "events_allowed")]
-    pub events_allowed: Vec<serde_json::Value>,
+    pub events_allowed: Vec<FilteredEvent>,
 
     /// Event types not sent to the application.
     #[serde(rename = "events_disallowed")]
-    pub events_disallowed: Vec<serde_json::Value>,
+    pub events_disallowed: Vec<FilteredEvent>,
+}
+
+impl Application {
+    /// Whether the application is subscribed to an event source.
+    pub fn is_subscribed(&self, source: &EventSource) -> bool {
+        match source {
+            EventSource::Channel(id) => self.channel_ids.contains(id),
+            EventSource::Bridge(id) => self.bridge_ids.contains(id),
+            EventSource::Endpoint(endpoint) => self.endpoint_ids.contains(endpoint),
+            EventSource::DeviceState(name) => self.device_names.contains(name),
+        }
+    }
+}
+
+/// EventSource : A resource whose events an application subscribes to.
+///
+/// Displays as the URI `Applications::subscribe` takes, e.g. `endpoint:PJSIP/1000`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum EventSource {
+    /// A channel.
+    Channel(ChannelId),
+    /// A bridge.
+    Bridge(BridgeId),
+    /// An endpoint, or every endpoint of a technology when the resource is empty.
+    Endpoint(EndpointRef),
+    /// A device state, by device name (e.g. `Custom:agent-1000`).
+    DeviceState(String),
+}
+
+impl EventSource {
+    /// A channel.
+    pub fn channel(id: impl Into<ChannelId>) -> Self {
+        EventSource::Channel(id.into())
+    }
+
+    /// A bridge.
+    pub fn bridge(id: impl Into<BridgeId>) -> Self {
+        EventSource::Bridge(id.into())
+    }
+
+    /// An endpoint, e.g. `PJSIP/1000`.
+    pub fn endpoint(endpoint: impl Into<EndpointRef>) -> Self {
+        EventSource::Endpoint(endpoint.into())
+    }
+
+    /// A device state.
+    pub fn device_state(name: impl Into<String>) -> Self {
+        EventSource::DeviceState(name.into())
+    }
+}
+
+impl Display for EventSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EventSource::Channel(id) => write!(f, "channel:{id}"),
+            EventSource::Bridge(id) => write!(f, "bridge:{id}"),
+            EventSource::Endpoint(endpoint) if endpoint.resource.is_empty() => {
+                write!(f, "endpoint:{}", endpoint.technology)
+            }
+            EventSource::Endpoint(endpoint) => write!(f, "endpoint:{endpoint}"),
+            EventSource::DeviceState(name) => write!(f, "deviceState:{name}"),
+        }
+    }
+}
+
+impl From<EventSource> for String {
+    fn from(source: EventSource) -> Self {
+        source.to_string()
+    }
+}
+
+/// FilteredEvent : An event type in the filter of an application.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FilteredEvent {
+    /// The event type, as named by Asterisk.
+    #[serde(rename = "type")]
+    pub name: String,
+}
+
+impl FilteredEvent {
+    /// The kind of event, or `None` for an event type unknown to this library.
+    pub fn kind(&self) -> Option<EventKind> {
+        EventKind::from_name(&self.name)
+    }
 }
@@ -1,3 +1,6 @@
+use crate::apis::concat_str;
+use crate::apis::ids::AppName;
+use crate::ws::models::EventKind;
 use derive_new::new;
 use derive_setters::Setters;
 use serde::Serialize;
@@ -10,10 +13,11 @@ pub struct UnSubscribeRequest {
     /// Application's name
     #[setters(skip)]
     #[serde(skip_serializing)]
-    pub(crate) name: String,
+    pub(crate) name: AppName,
 
-    /// The application to subscribe to.
+    /// URI for event source (see [`EventSource`](super::models::EventSource)).
     #[setters(skip)]
+    #[serde(serialize_with = "concat_str")]
     #[new(into_iter = "String")]
     pub(crate) event_source: Vec<String>,
 }
@@ -26,10 +30,11 @@ pub struct SubscribeRequest {
     /// Application's name
     #[setters(skip)]
     #[serde(skip_serializing)]
-    pub(crate) name: String,
+    pub(crate) name: AppName,
 
     /// URI for event source (channel:{channelId}, bridge:{bridgeId}, endpoint:{tech}[/{resource}], deviceState:{deviceName}
     #[setters(skip)]
+    #[serde(serialize_with = "concat_str")]
     #[new(into_iter = "String")]
     pub(crate) event_source: Vec<String>,
 }
@@ -42,7 +47,7 @@ pub struct FilterEventsRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) name: String,
+    pub(crate) name: AppName,
 
     /// Specify which event types to allow/disallow
     ///
@@ -72,11 +77,20 @@ pub struct FilterEventsRequest {
     /// * An empty \"allowed\" list means all events are allowed.
     /// * An empty \"disallowed\" list means no events are disallowed.
     /// * Disallowed events take precedence over allowed events if the event type is specified in both lists.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(flatten)]
     #[new(default)]
     pub(crate) filter: Option<Filter>,
 }
 
+/// The event types an application receives.
+///
+/// ```
+/// use asterisk_ari::apis::applications::params::Filter;
+/// use asterisk_ari::ws::models::EventKind;
+///
+/// let filter = Filter::allow([EventKind::StasisStart, EventKind::ChannelDtmfReceived])
+///     .and_disallow([EventKind::ChannelDtmfReceived]);
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, new, Setters)]
 #[setters(prefix = "with_")]
 #[setters(into, strip_option)]
@@ -90,9 +104,39 @@ pub struct Filter {
     disallowed: Option<Vec<FilterType>>,
 }
 
+impl Filter {
+    /// Allows only the given kinds of events; an empty list allows every event.
+    pub fn allow(kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        Filter::new().and_allow(kinds)
+    }
+
+    /// Allows every event but the given kinds.
+    pub fn disallow(kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        Filter::new().and_disallow(kinds)
+    }
+
+    /// Sets the allowed kinds of events.
+    pub fn and_allow(mut self, kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        self.allowed = Some(kinds.into_iter().map(FilterType::from).collect());
+        self
+    }
+
+    /// Sets the disallowed kinds of events, which take precedence over the allowed ones.
+    pub fn and_disallow(mut self, kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        self.disallowed = Some(kinds.into_iter().map(FilterType::from).collect());
+        self
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, new)]
 pub struct FilterType {
     #[serde(rename = "type")]
     #[new(into)]
     name: String,
 }
+
+impl From<EventKind> for FilterType {
+    fn from(kind: EventKind) -> Self {
+        FilterType::new(kind.as_str())
+    }
+}
@@ -2,6 +2,7 @@ pub mod models;
 pub mod params;
 
 use crate::apis::client::Client;
+use crate::apis::generated::asterisk as operations;
 use std::fmt::Display;
 
 pub struct DynamicConfiguration<'c> {
@@ -19,30 +20,28 @@ impl<'c> DynamicConfiguration<'c> {
         config_class: impl Into<String> + Send,
         object_type: impl Into<String> + Send,
         id: impl Into<String> + Send,
-    ) -> crate::errors::Result<models::ConfigTuple> {
+    ) -> crate::errors::Result<Vec<models::ConfigTuple>> {
         self.client
-            .get(
-                format!(
-                    "/asterisk/config/dynamic/{}/{}/{}",
-                    config_class.into(),
-                    object_type.into(),
-                    id.into()
-                )
-                .as_str(),
+            .call(
+                &operations::GET_OBJECT,
+                &[&config_class.into(), &object_type.into(), &id.into()],
+                &(),
+                (),
             )
             .await
     }
 
     /// Create or update a dynamic configuration object.
-    pub async fn put(&self, request: params::CreateUpdateRequest) -> crate::errors::Result<()> {
+    pub async fn put(
+        &self,
+        request: params::CreateUpdateRequest,
+    ) -> crate::errors::Result<Vec<models::ConfigTuple>> {
         self.client
-            .put(
-                format!(
-                    "/asterisk/config/dynamic/{}/{}/{}",
-                    request.config_class, request.object_type, request.id
-                )
-                .as_str(),
-                request,
+            .call(
+                &operations::UPDATE_OBJECT,
+                &[&request.config_class, &request.object_type, &request.id],
+                &(),
+                &request,
             )
             .await
     }
@@ -55,7 +54,12 @@ impl<'c> DynamicConfiguration<'c> {
         id: impl Into<String> + Display + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/asterisk/config/dynamic/{config_class}/{object_type}/{id}",).as_str())
+            .call(
+                &operations::DELETE_OBJECT,
+                &[&config_class.into(), &object_type.into(), &id.into()],
+                &(),
+                (),
+            )
             .await
     }
 }
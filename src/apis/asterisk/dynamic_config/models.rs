@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// ConfigTuple : A key/value pair that makes up part of a configuration object.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ConfigTuple {
     /// A configuration object attribute.
     pub attribute: String,
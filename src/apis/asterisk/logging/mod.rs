@@ -2,6 +2,7 @@ pub mod models;
 pub mod params;
 
 use crate::apis::client::Client;
+use crate::apis::generated::asterisk as operations;
 use std::fmt::Display;
 
 pub struct Logging<'c> {
@@ -17,7 +18,9 @@ impl<'c> Logging<'c> {
 impl Logging<'_> {
     /// Gets Asterisk log channel information.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::LogChannel>> {
-        self.client.get("/asterisk/logging").await
+        self.client
+            .call(&operations::LIST_LOG_CHANNELS, &[], &(), ())
+            .await
     }
 
     /// Add a log channel
@@ -25,11 +28,7 @@ impl Logging<'_> {
     /// Example: NOTICE WARNING ERROR VERBOSE
     pub async fn add(&self, request: params::AddRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/asterisk/logging/{}", request.name).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::ADD_LOG, &[&request.name], &request, ())
             .await
     }
 
@@ -39,7 +38,7 @@ impl Logging<'_> {
         name: impl Into<String> + Display + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/asterisk/logging/{name}").as_str())
+            .call(&operations::DELETE_LOG, &[&name.into()], &(), ())
             .await
     }
 
@@ -49,10 +48,7 @@ impl Logging<'_> {
         name: impl Into<String> + Display + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .put(
-                format!("/asterisk/logging/{name}/rotate",).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::ROTATE_LOG, &[&name.into()], &(), ())
             .await
     }
 }
@@ -6,6 +6,7 @@ pub mod modules;
 pub mod variables;
 
 use crate::apis::client::Client;
+use crate::apis::generated::asterisk as operations;
 use crate::errors::Result;
 
 pub struct Asterisk<'c> {
@@ -20,26 +21,26 @@ impl<'c> Asterisk<'c> {
 
 impl Asterisk<'_> {
     pub async fn info(&self) -> Result<models::AsteriskInfo> {
-        self.client.get("/asterisk/info").await
+        self.client.call(&operations::GET_INFO, &[], &(), ()).await
     }
 
     pub async fn ping(&self) -> Result<models::AsteriskPing> {
-        self.client.get("/asterisk/ping").await
+        self.client.call(&operations::PING, &[], &(), ()).await
     }
 
-    pub fn config(&self) -> dynamic_config::DynamicConfiguration {
+    pub fn config(&self) -> dynamic_config::DynamicConfiguration<'_> {
         dynamic_config::DynamicConfiguration::new(self.client)
     }
 
-    pub fn modules(&self) -> modules::Modules {
+    pub fn modules(&self) -> modules::Modules<'_> {
         modules::Modules::new(self.client)
     }
 
-    pub fn logging(&self) -> logging::Logging {
+    pub fn logging(&self) -> logging::Logging<'_> {
         logging::Logging::new(self.client)
     }
 
-    pub fn variables(&self) -> variables::Variables {
+    pub fn variables(&self) -> variables::Variables<'_> {
         variables::Variables::new(self.client)
     }
 }
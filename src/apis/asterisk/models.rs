@@ -1,32 +1,37 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// AsteriskInfo : Asterisk system information
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AsteriskInfo {
     /// Info about how Asterisk was built,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub build: Option<BuildInfo>,
     /// Info about the system running Asterisk,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub system: Option<SystemInfo>,
     /// Info about Asterisk configuration,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub config: Option<ConfigInfo>,
     /// Info about Asterisk status
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub status: Option<StatusInfo>,
 }
 
 /// AsteriskPing : Asterisk ping information
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AsteriskPing {
     /// Asterisk id info
     pub asterisk_id: String,
     /// Always string value is pong
     pub ping: String,
     /// The timestamp string of request received time
+    #[serde(serialize_with = "crate::apis::serialize_timestamp")]
     pub timestamp: DateTime<Utc>,
 }
 
 /// BuildInfo : Info about how Asterisk was built
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BuildInfo {
     /// OS Asterisk was built on.
     pub os: String,
@@ -43,24 +48,28 @@ pub struct BuildInfo {
 }
 
 /// ConfigInfo : Info about Asterisk configuration
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ConfigInfo {
     /// Asterisk system name.
     pub name: String,
     /// Default language for media playback.
     pub default_language: String,
     /// Maximum number of simultaneous channels.,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_channels: Option<u32>,
     /// Maximum number of open file handles (files, sockets).,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_open_files: Option<u32>,
     /// Maximum load avg on system.,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_load: Option<f32>,
     /// Effective user/group id for running Asterisk.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub set_id: Option<SetId>,
 }
 
 /// SetId : Effective user/group id
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SetId {
     /// Effective user id.
     pub user: String,
@@ -69,16 +78,18 @@ pub struct SetId {
 }
 
 /// StatusInfo : Info about Asterisk status
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StatusInfo {
     /// Time when Asterisk was started.
+    #[serde(serialize_with = "crate::apis::serialize_timestamp")]
     pub startup_time: DateTime<Utc>,
     /// Time when Asterisk was last reloaded.
+    #[serde(serialize_with = "crate::apis::serialize_timestamp")]
     pub last_reload_time: DateTime<Utc>,
 }
 
 /// SystemInfo : Info about Asterisk
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SystemInfo {
     /// Asterisk version.
     pub version: String,
@@ -1,6 +1,7 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::asterisk as operations;
 
 pub struct Modules<'c> {
     client: &'c Client,
@@ -15,7 +16,9 @@ impl<'c> Modules<'c> {
 impl Modules<'_> {
     /// List Asterisk modules.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Module>> {
-        self.client.get("/asterisk/modules").await
+        self.client
+            .call(&operations::LIST_MODULES, &[], &(), ())
+            .await
     }
 
     /// Get Asterisk module information.
@@ -24,34 +27,28 @@ impl Modules<'_> {
         name: impl Into<String> + Send,
     ) -> crate::errors::Result<models::Module> {
         self.client
-            .get(format!("/asterisk/modules/{}", name.into(),).as_str())
+            .call(&operations::GET_MODULE, &[&name.into()], &(), ())
             .await
     }
 
     /// Load an Asterisk module.
     pub async fn load(&self, name: impl Into<String> + Send) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/asterisk/modules/{}", name.into(),).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::LOAD_MODULE, &[&name.into()], &(), ())
             .await
     }
 
     /// Unload an Asterisk module.
     pub async fn unload(&self, name: impl Into<String> + Send) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/asterisk/modules/{}", name.into()).as_str())
+            .call(&operations::UNLOAD_MODULE, &[&name.into()], &(), ())
             .await
     }
 
     /// Reload an Asterisk module
     pub async fn reload(&self, name: impl Into<String> + Send) -> crate::errors::Result<()> {
         self.client
-            .put(
-                format!("/asterisk/modules/{}", name.into()).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::RELOAD_MODULE, &[&name.into()], &(), ())
             .await
     }
 }
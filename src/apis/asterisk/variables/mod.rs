@@ -1,6 +1,7 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::asterisk as operations;
 
 pub struct Variables<'c> {
     client: &'c Client,
@@ -19,7 +20,12 @@ impl Variables<'_> {
         variable: impl Into<String> + Send,
     ) -> crate::errors::Result<models::Variable> {
         self.client
-            .get_with_query("/asterisk/variable", &[("variable", variable.into())])
+            .call(
+                &operations::GET_GLOBAL_VAR,
+                &[],
+                &[("variable", variable.into())],
+                (),
+            )
             .await
     }
 
@@ -30,13 +36,14 @@ impl Variables<'_> {
         value: Option<impl Into<String> + Send>,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                "/asterisk/variable",
-                vec![] as Vec<String>,
+            .call(
+                &operations::SET_GLOBAL_VAR,
+                &[],
                 &[
                     ("variable", variable.into()),
                     ("value", value.map(|v| v.into()).unwrap_or_default()),
                 ],
+                (),
             )
             .await
     }
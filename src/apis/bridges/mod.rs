@@ -1,5 +1,7 @@
+use crate::apis::capabilities::Capability;
 use crate::apis::client::Client;
-use std::fmt::Display;
+use crate::apis::generated::bridges as operations;
+use crate::apis::ids::{BridgeId, ChannelId};
 
 pub mod models;
 pub mod params;
@@ -15,16 +17,28 @@ impl<'c> Bridges<'c> {
 }
 
 impl Bridges<'_> {
+    /// Fails when the server does not support the type of bridge to create.
+    async fn require_type(
+        &self,
+        bridge_type: &Option<models::BridgeType>,
+    ) -> crate::errors::Result<()> {
+        if *bridge_type == Some(models::BridgeType::VideoSFU) {
+            self.client.require(Capability::VideoSfuBridges).await?;
+        }
+        Ok(())
+    }
+
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Bridge>> {
-        self.client.get("/bridges").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     pub async fn create(
         &self,
         request: params::CreateRequest,
     ) -> crate::errors::Result<models::Bridge> {
+        self.require_type(&request.r#type).await?;
         self.client
-            .post_with_query("/bridges", vec![] as Vec<String>, &request)
+            .call(&operations::CREATE, &[], &request, ())
             .await
     }
 
@@ -32,30 +46,29 @@ impl Bridges<'_> {
         &self,
         request: params::CreateWithIdRequest,
     ) -> crate::errors::Result<models::Bridge> {
+        self.require_type(&request.r#type).await?;
         self.client
-            .post_with_query(
-                format!("/bridges/{}", request.bridge_id).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::CREATE_WITH_ID,
+                &[&request.bridge_id],
                 &request,
+                (),
             )
             .await
     }
 
     pub async fn get(
         &self,
-        bridge_id: impl Into<String> + Display + Send,
+        bridge_id: impl Into<BridgeId> + Send,
     ) -> crate::errors::Result<models::Bridge> {
         self.client
-            .get(format!("/bridges/{bridge_id}").as_str())
+            .call(&operations::GET, &[&bridge_id.into()], &(), ())
             .await
     }
 
-    pub async fn delete(
-        &self,
-        bridge_id: impl Into<String> + Display + Send,
-    ) -> crate::errors::Result<()> {
+    pub async fn delete(&self, bridge_id: impl Into<BridgeId> + Send) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/bridges/{bridge_id}").as_str())
+            .call(&operations::DESTROY, &[&bridge_id.into()], &(), ())
             .await
     }
 
@@ -64,24 +77,26 @@ impl Bridges<'_> {
         request: params::AddChannelRequest,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/bridges/{}/addChannel", request.bridge_id).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::ADD_CHANNEL,
+                &[&request.bridge_id],
                 &request,
+                (),
             )
             .await
     }
 
     pub async fn remove_channel(
         &self,
-        bridge_id: impl Into<String> + Display + Send,
-        channel: impl Into<String> + Send,
+        bridge_id: impl Into<BridgeId> + Send,
+        channel: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/bridges/{bridge_id}/removeChannel").as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::REMOVE_CHANNEL,
+                &[&bridge_id.into()],
                 &[("channel", channel.into())],
+                (),
             )
             .await
     }
@@ -89,21 +104,14 @@ impl Bridges<'_> {
     /// Play music on hold to a bridge or change the MOH class that is playing.
     pub async fn moh(&self, request: params::MohRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/bridges/{}/moh", request.bridge_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::START_MOH, &[&request.bridge_id], &request, ())
             .await
     }
 
     /// Stop playing music on hold to a bridge.
-    pub async fn unmoh(
-        &self,
-        bridge_id: impl Into<String> + Display + Send,
-    ) -> crate::errors::Result<()> {
+    pub async fn unmoh(&self, bridge_id: impl Into<BridgeId> + Send) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/bridges/{bridge_id}/moh").as_str())
+            .call(&operations::STOP_MOH, &[&bridge_id.into()], &(), ())
             .await
     }
 
@@ -116,11 +124,7 @@ impl Bridges<'_> {
         request: params::PlayRequest,
     ) -> crate::errors::Result<crate::apis::playbacks::models::Playback> {
         self.client
-            .post_with_query(
-                format!("/bridges/{}/play", request.bridge_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::PLAY, &[&request.bridge_id], &request, ())
             .await
     }
 
@@ -133,14 +137,11 @@ impl Bridges<'_> {
         request: params::PlayWithIdRequest,
     ) -> crate::errors::Result<crate::apis::playbacks::models::Playback> {
         self.client
-            .post_with_query(
-                format!(
-                    "/bridges/{}/play/{}",
-                    request.bridge_id, request.playback_id
-                )
-                .as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::PLAY_WITH_ID,
+                &[&request.bridge_id, &request.playback_id],
                 &request,
+                (),
             )
             .await
     }
@@ -154,11 +155,7 @@ impl Bridges<'_> {
         request: params::RecordRequest,
     ) -> crate::errors::Result<crate::apis::recordings::models::LiveRecording> {
         self.client
-            .post_with_query(
-                format!("/bridges/{}/record", request.bridge_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::RECORD, &[&request.bridge_id], &request, ())
             .await
     }
 }
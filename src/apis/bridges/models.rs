@@ -1,3 +1,4 @@
+use crate::apis::ids::{BridgeId, ChannelId};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -5,11 +6,11 @@ use serde::{Deserialize, Serialize};
 ///
 /// The merging of media from one or more channels.
 /// Everyone on the bridge receives the same audio.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Bridge {
     /// Unique identifier for this bridge
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: BridgeId,
     /// Name of the current bridging technology
     #[serde(rename = "technology")]
     pub technology: String,
@@ -26,14 +27,17 @@ pub struct Bridge {
     #[serde(rename = "name")]
     pub name: String,
     #[serde(rename = "channels")]
-    pub channels: Vec<String>,
+    pub channels: Vec<ChannelId>,
     /// The video mode the bridge is using. One of 'none', 'talker', 'sfu', or 'single'.
-    #[serde(rename = "video_mode")]
+    #[serde(rename = "video_mode", skip_serializing_if = "Option::is_none")]
     pub video_mode: Option<VideoMode>,
     /// The ID of the channel that is the source of video in this bridge, if one exists.
-    #[serde(rename = "video_source_id")]
-    pub video_source_id: Option<String>,
-    #[serde(rename = "creationtime")]
+    #[serde(rename = "video_source_id", skip_serializing_if = "Option::is_none")]
+    pub video_source_id: Option<ChannelId>,
+    #[serde(
+        rename = "creationtime",
+        serialize_with = "crate::apis::serialize_timestamp"
+    )]
     pub creation_time: DateTime<Utc>,
 }
 
@@ -56,7 +60,7 @@ pub enum BridgeType {
     SDPLabel,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum VideoMode {
     #[serde(rename = "none")]
     #[default]
@@ -1,4 +1,6 @@
 use crate::apis::bridges::models;
+use crate::apis::ids::{BridgeId, ChannelId, PlaybackId, RecordingName};
+use crate::apis::media::MediaList;
 use crate::apis::params::{IfExists, TerminateOn};
 use derive_new::new;
 use derive_setters::Setters;
@@ -12,15 +14,15 @@ pub struct CreateRequest {
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     #[setters(rename = "with_type")]
     #[new(default)]
-    r#type: Option<models::BridgeType>,
+    pub(crate) r#type: Option<models::BridgeType>,
 
     /// Unique ID to give to the bridge being created.
     #[serde(rename = "bridgeId", skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    bridge_id: Option<String>,
+    bridge_id: Option<BridgeId>,
 
     /// Name to give to the bridge being created.
-    #[serde(rename = "bridgeId", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     #[new(default)]
     name: Option<String>,
 }
@@ -33,16 +35,16 @@ pub struct CreateWithIdRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
     /// Comma separated list of bridge type attributes (mixing, holding, dtmf_events, proxy_media, video_sfu, video_single, sdp_label).
     #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
     #[setters(rename = "with_type")]
     #[new(default)]
-    r#type: Option<models::BridgeType>,
+    pub(crate) r#type: Option<models::BridgeType>,
 
     /// Name to give to the bridge being created.
-    #[serde(rename = "bridgeId", skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "name", skip_serializing_if = "Option::is_none")]
     #[new(default)]
     name: Option<String>,
 }
@@ -55,12 +57,12 @@ pub struct AddChannelRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
     /// Ids of channels to add to bridge.
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel: String,
+    pub(crate) channel: ChannelId,
 
     /// Channel's role in the bridge
     #[serde(rename = "role", skip_serializing_if = "Option::is_none")]
@@ -94,7 +96,7 @@ pub struct MohRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
     /// Music on hold class to use.
     #[serde(rename = "mohClass", skip_serializing_if = "Option::is_none")]
@@ -110,12 +112,12 @@ pub struct PlayRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
-    /// Media URIs to play.
+    /// Media URIs to play, one after the other.
     #[setters(skip)]
     #[new(into)]
-    media: String,
+    media: MediaList,
 
     /// For sounds, selects language for sound.
     #[serde(rename = "lang", skip_serializing_if = "Option::is_none")]
@@ -141,18 +143,18 @@ pub struct PlayWithIdRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
     /// Playback ID.
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) playback_id: String,
+    pub(crate) playback_id: PlaybackId,
 
-    /// Media URIs to play.
+    /// Media URIs to play, one after the other.
     #[setters(skip)]
     #[new(into)]
-    pub(crate) media: String,
+    pub(crate) media: MediaList,
 
     /// For sounds, selects language for sound.
     #[serde(rename = "lang", skip_serializing_if = "Option::is_none")]
@@ -178,12 +180,12 @@ pub struct RecordRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) bridge_id: String,
+    pub(crate) bridge_id: BridgeId,
 
     /// Recording's filename
     #[setters(skip)]
     #[new(into)]
-    pub(crate) name: String,
+    pub(crate) name: RecordingName,
 
     /// Format to encode audio in
     #[setters(skip)]
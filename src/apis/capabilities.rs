@@ -0,0 +1,209 @@
+//! Features depending on the version of Asterisk.
+//!
+//! ```no_run
+//! use asterisk_ari::apis::capabilities::Capability;
+//! use asterisk_ari::{AriClient, Config};
+//!
+//! # async fn example() -> asterisk_ari::Result<()> {
+//! let client = AriClient::with_config(Config::new("http://localhost:8088", "asterisk", "asterisk"));
+//! let capabilities = client.capabilities().await;
+//! if capabilities.supports(Capability::ChannelTenantId) {
+//!     // Route the calls by tenant.
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::errors::{AriError, Result};
+use std::fmt::{Display, Formatter};
+
+/// A release of Asterisk, e.g. `20.5.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AsteriskVersion {
+    /// Branch, e.g. `20`.
+    pub major: u32,
+    /// Minor release within the branch.
+    pub minor: u32,
+    /// Patch release.
+    pub patch: u32,
+}
+
+impl AsteriskVersion {
+    /// A release.
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        AsteriskVersion {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Parses the version reported by Asterisk, e.g. `20.5.0`, `18.20.2-rc1` or
+    /// `certified/18.9-cert5`.
+    ///
+    /// Development builds, e.g. `GIT-master-3fc7d9e`, have no version.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.strip_prefix("certified/").unwrap_or(version);
+        let release = version.split('-').next()?;
+        let mut numbers = release.split('.').map(str::parse::<u32>);
+        let major = numbers.next()?.ok()?;
+        let minor = numbers.next().unwrap_or(Ok(0)).ok()?;
+        let patch = numbers.next().unwrap_or(Ok(0)).ok()?;
+        Some(AsteriskVersion::new(major, minor, patch))
+    }
+}
+
+impl Display for AsteriskVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A feature missing from older releases of Asterisk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `POST /channels/externalMedia`.
+    ExternalMedia,
+    /// The `audiosocket` encapsulation of external media.
+    ExternalMediaAudioSocket,
+    /// Bridges of type `video_sfu`.
+    VideoSfuBridges,
+    /// `Channel::protocol_id`.
+    ChannelProtocolId,
+    /// `Channel::tenant_id`.
+    ChannelTenantId,
+}
+
+impl Capability {
+    /// The first release of each maintained branch with the feature.
+    pub fn since(&self) -> &'static [AsteriskVersion] {
+        const fn v(major: u32, minor: u32, patch: u32) -> AsteriskVersion {
+            AsteriskVersion::new(major, minor, patch)
+        }
+        const EXTERNAL_MEDIA: &[AsteriskVersion] = &[v(16, 6, 0)];
+        const EXTERNAL_MEDIA_AUDIO_SOCKET: &[AsteriskVersion] = &[v(18, 0, 0)];
+        const VIDEO_SFU_BRIDGES: &[AsteriskVersion] = &[v(15, 0, 0)];
+        const CHANNEL_PROTOCOL_ID: &[AsteriskVersion] = &[v(18, 20, 0), v(20, 5, 0), v(21, 0, 0)];
+        const CHANNEL_TENANT_ID: &[AsteriskVersion] = &[v(18, 24, 0), v(20, 9, 0), v(21, 4, 0)];
+        match self {
+            Capability::ExternalMedia => EXTERNAL_MEDIA,
+            Capability::ExternalMediaAudioSocket => EXTERNAL_MEDIA_AUDIO_SOCKET,
+            Capability::VideoSfuBridges => VIDEO_SFU_BRIDGES,
+            Capability::ChannelProtocolId => CHANNEL_PROTOCOL_ID,
+            Capability::ChannelTenantId => CHANNEL_TENANT_ID,
+        }
+    }
+
+    /// Whether a release has the feature: it is at least the first release of its branch
+    /// with it, or of a later branch.
+    pub fn is_supported_by(&self, version: &AsteriskVersion) -> bool {
+        let since = self.since();
+        match since.iter().find(|first| first.major == version.major) {
+            Some(first) => version >= first,
+            None => since.iter().all(|first| first.major < version.major),
+        }
+    }
+
+    /// The releases with the feature, e.g. `18.24.0, 20.9.0 or 21.4.0`.
+    fn required(&self) -> String {
+        let since: Vec<String> = self.since().iter().map(ToString::to_string).collect();
+        match since.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, others)) => format!("{} or {last}", others.join(", ")),
+            None => String::new(),
+        }
+    }
+}
+
+/// The features of the Asterisk server, from the version it reports.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    reported: String,
+    version: Option<AsteriskVersion>,
+}
+
+impl Capabilities {
+    /// The features of the server reporting a version.
+    pub fn new(reported: impl Into<String>) -> Self {
+        let reported = reported.into();
+        Capabilities {
+            version: AsteriskVersion::parse(&reported),
+            reported,
+        }
+    }
+
+    /// The features of a server whose version could not be read: all are assumed.
+    pub fn unknown() -> Self {
+        Capabilities {
+            reported: "unknown".to_string(),
+            version: None,
+        }
+    }
+
+    /// The version, as reported by Asterisk.
+    pub fn reported_version(&self) -> &str {
+        &self.reported
+    }
+
+    /// The version, unless Asterisk is a development build.
+    pub fn version(&self) -> Option<AsteriskVersion> {
+        self.version
+    }
+
+    /// Whether the server has a feature. Development builds, and servers of unknown version,
+    /// are assumed to have them all.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.version
+            .map_or(true, |version| capability.is_supported_by(&version))
+    }
+
+    /// Fails with [`AriError::Unsupported`] when the server lacks a feature.
+    pub fn require(&self, capability: Capability) -> Result<()> {
+        if self.supports(capability) {
+            return Ok(());
+        }
+        Err(AriError::Unsupported {
+            required: capability.required(),
+            actual: self.reported.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        let versions = [
+            ("20.5.0", Some(AsteriskVersion::new(20, 5, 0))),
+            ("18.20.2-rc1", Some(AsteriskVersion::new(18, 20, 2))),
+            ("certified/18.9-cert5", Some(AsteriskVersion::new(18, 9, 0))),
+            ("GIT-master-3fc7d9e", None),
+        ];
+        for (reported, version) in versions {
+            assert_eq!(AsteriskVersion::parse(reported), version);
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let tenant_id = Capability::ChannelTenantId;
+        assert!(Capabilities::new("20.9.0").supports(tenant_id));
+        assert!(Capabilities::new("22.0.0").supports(tenant_id));
+        assert!(!Capabilities::new("20.8.1").supports(tenant_id));
+        assert!(!Capabilities::new("19.8.0").supports(tenant_id));
+        assert!(Capabilities::new("GIT-master-3fc7d9e").supports(tenant_id));
+
+        match Capabilities::new("16.2.0").require(tenant_id) {
+            Err(AriError::Unsupported { required, actual }) => {
+                assert_eq!(required, "18.24.0, 20.9.0 or 21.4.0");
+                assert_eq!(actual, "16.2.0");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(Capabilities::new("16.6.0")
+            .require(Capability::ExternalMedia)
+            .is_ok());
+    }
+}
@@ -0,0 +1,327 @@
+//! Record and replay of the REST interactions with Asterisk.
+//!
+//! A [`Cassette`] is an [`AriTransport`] which, in record mode, forwards the requests to
+//! another transport and writes each request and its response to a JSON Lines file. In replay
+//! mode, it answers the requests from such a file without any Asterisk, and fails on the
+//! requests that were not recorded.
+//!
+//! ```no_run
+//! use asterisk_ari::apis::cassette::Cassette;
+//! use asterisk_ari::apis::client::Client;
+//! use asterisk_ari::apis::transport::ReqwestTransport;
+//! use asterisk_ari::Config;
+//!
+//! # async fn example() -> asterisk_ari::Result<()> {
+//! let config = Config::new("http://localhost:8088", "asterisk", "asterisk");
+//!
+//! // Once, against a real Asterisk.
+//! let cassette = Cassette::record("flow.jsonl", ReqwestTransport::default())?;
+//! let client = Client::with_config(config.clone()).with_transport(cassette);
+//! client.channels().list().await?;
+//!
+//! // Then, offline.
+//! let cassette = Cassette::replay("flow.jsonl")?;
+//! let client = Client::with_config(config).with_transport(cassette.clone());
+//! client.channels().list().await?;
+//! assert!(cassette.unplayed().is_empty());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::apis::transport::{AriTransport, TransportFuture, TransportRequest, TransportResponse};
+use crate::errors::{AriError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// A recorded REST request, without the address of Asterisk and the `/ari` prefix.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CassetteRequest {
+    /// HTTP method.
+    pub method: String,
+    /// Path, e.g. `/channels/1736284349.1/answer`.
+    pub path: String,
+    /// Query parameters, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<(String, String)>,
+    /// JSON body, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+}
+
+impl CassetteRequest {
+    /// Extracts what is recorded of a request.
+    fn from_request(request: &TransportRequest) -> Result<Self> {
+        let url = url::Url::parse(&request.url)?;
+        let path = match url.path().find("/ari") {
+            Some(start) => &url.path()[start + "/ari".len()..],
+            None => url.path(),
+        };
+        let body = request
+            .body
+            .as_ref()
+            .map(|body| serde_json::from_str(body).unwrap_or_else(|_| Value::String(body.clone())));
+
+        Ok(CassetteRequest {
+            method: request.method.to_string(),
+            path: path.to_string(),
+            query: url.query_pairs().into_owned().collect(),
+            body,
+        })
+    }
+
+    /// Whether two requests are the same, regardless of the order of the query parameters.
+    fn matches(&self, other: &CassetteRequest) -> bool {
+        let mut query = self.query.clone();
+        let mut other_query = other.query.clone();
+        query.sort();
+        other_query.sort();
+
+        self.method.eq_ignore_ascii_case(&other.method)
+            && self.path == other.path
+            && query == other_query
+            && self.body == other.body
+    }
+}
+
+/// A recorded response.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CassetteResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Body, as sent by Asterisk.
+    #[serde(default)]
+    pub body: String,
+}
+
+/// A request and its response, one line of a cassette.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// The request.
+    pub request: CassetteRequest,
+    /// The response to the request.
+    pub response: CassetteResponse,
+}
+
+enum Mode {
+    Record {
+        inner: Box<dyn AriTransport>,
+        writer: Mutex<Box<dyn Write + Send>>,
+    },
+    Replay {
+        /// The interactions, along with whether they were played.
+        interactions: Mutex<Vec<(Interaction, bool)>>,
+    },
+}
+
+/// A transport recording the REST interactions into a cassette, or replaying them from one.
+///
+/// Clones share the same cassette.
+#[derive(Clone)]
+pub struct Cassette {
+    mode: Arc<Mode>,
+}
+
+impl Cassette {
+    /// Records the interactions made through another transport into a file, overwriting it.
+    pub fn record(path: impl AsRef<Path>, inner: impl AriTransport + 'static) -> Result<Self> {
+        Ok(Self::record_with_writer(File::create(path)?, inner))
+    }
+
+    /// Records the interactions made through another transport into any destination.
+    pub fn record_with_writer(
+        writer: impl Write + Send + 'static,
+        inner: impl AriTransport + 'static,
+    ) -> Self {
+        Self {
+            mode: Arc::new(Mode::Record {
+                inner: Box::new(inner),
+                writer: Mutex::new(Box::new(writer)),
+            }),
+        }
+    }
+
+    /// Replays the interactions of a cassette file.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self> {
+        Self::replay_from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Replays the interactions of a cassette read from any source. Empty lines are skipped.
+    pub fn replay_from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut interactions = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                interactions.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(Self::replay_interactions(interactions))
+    }
+
+    /// Replays a list of interactions.
+    pub fn replay_interactions(interactions: Vec<Interaction>) -> Self {
+        Self {
+            mode: Arc::new(Mode::Replay {
+                interactions: Mutex::new(
+                    interactions
+                        .into_iter()
+                        .map(|interaction| (interaction, false))
+                        .collect(),
+                ),
+            }),
+        }
+    }
+
+    /// The interactions not replayed yet; always empty when recording.
+    pub fn unplayed(&self) -> Vec<Interaction> {
+        match &*self.mode {
+            Mode::Record { .. } => vec![],
+            Mode::Replay { interactions } => interactions
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|(_, played)| !played)
+                .map(|(interaction, _)| interaction.clone())
+                .collect(),
+        }
+    }
+
+    /// Answers a request with the first matching interaction not played yet.
+    fn play(
+        interactions: &Mutex<Vec<(Interaction, bool)>>,
+        request: &TransportRequest,
+    ) -> Result<TransportResponse> {
+        let request = CassetteRequest::from_request(request)?;
+        let mut interactions = interactions.lock().unwrap();
+        let Some((interaction, played)) = interactions
+            .iter_mut()
+            .find(|(interaction, played)| !played && interaction.request.matches(&request))
+        else {
+            return Err(AriError::UnmatchedInteraction(format!(
+                "{} {}",
+                request.method, request.path
+            )));
+        };
+
+        *played = true;
+        Ok(TransportResponse::new(
+            interaction.response.status,
+            interaction.response.body.as_str(),
+        ))
+    }
+
+    /// Appends an interaction to the cassette.
+    fn write(writer: &Mutex<Box<dyn Write + Send>>, interaction: &Interaction) {
+        let mut writer = writer.lock().unwrap();
+        let written = serde_json::to_writer(&mut *writer, interaction)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"))
+            .and_then(|_| writer.flush());
+        if let Err(e) = written {
+            warn!("unable to write to the cassette: {}", e);
+        }
+    }
+}
+
+impl AriTransport for Cassette {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            match &*self.mode {
+                Mode::Replay { interactions } => Self::play(interactions, &request),
+                Mode::Record { inner, writer } => {
+                    let recorded = CassetteRequest::from_request(&request)?;
+                    let response = inner.send(request).await?;
+                    let interaction = Interaction {
+                        request: recorded,
+                        response: CassetteResponse {
+                            status: response.status,
+                            body: response.body.clone(),
+                        },
+                    };
+                    Self::write(writer, &interaction);
+                    Ok(response)
+                }
+            }
+        })
+    }
+}
+
+impl std::fmt::Debug for Cassette {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match &*self.mode {
+            Mode::Record { .. } => "record",
+            Mode::Replay { .. } => "replay",
+        };
+        f.debug_struct("Cassette")
+            .field("mode", &mode)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::channels::params::PlayRequest;
+    use crate::apis::client::Client;
+    use crate::apis::transport::ReqwestTransport;
+    use crate::testing::MockAriServer;
+
+    /// A writer whose output can be read after it was moved into a cassette.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let server = MockAriServer::start().await.unwrap();
+        server.add_channel("1", "PJSIP/1000", "test").unwrap();
+
+        let output = Shared::default();
+        let cassette = Cassette::record_with_writer(output.clone(), ReqwestTransport::default());
+        let client = Client::with_config(server.config()).with_transport(cassette);
+        client.channels().answer("1").await.unwrap();
+        let playback = client
+            .channels()
+            .play(PlayRequest::new("1", "sound:hello-world"))
+            .await
+            .unwrap();
+        assert!(client.channels().get("missing").await.is_err());
+        drop(server);
+
+        let written = output.0.lock().unwrap().clone();
+        let cassette = Cassette::replay_from_reader(written.as_slice()).unwrap();
+        assert_eq!(cassette.unplayed().len(), 3);
+        assert_eq!(cassette.unplayed()[0].request.path, "/channels/1/answer");
+
+        let config = crate::Config::new("http://offline:8088", "asterisk", "asterisk");
+        let client = Client::with_config(config).with_transport(cassette.clone());
+        assert!(client.channels().get("missing").await.is_err());
+        client.channels().answer("1").await.unwrap();
+        let replayed = client
+            .channels()
+            .play(PlayRequest::new("1", "sound:hello-world"))
+            .await
+            .unwrap();
+        assert_eq!(replayed.id, playback.id);
+        assert!(cassette.unplayed().is_empty());
+
+        assert!(matches!(
+            client.channels().answer("1").await,
+            Err(AriError::UnmatchedInteraction(_))
+        ));
+    }
+}
@@ -1,5 +1,8 @@
+use crate::apis::capabilities::Capability;
 use crate::apis::client::Client;
-use std::fmt::Display;
+use crate::apis::generated::channels as operations;
+use crate::apis::ids::ChannelId;
+use crate::apis::operation::Body;
 
 pub mod models;
 pub mod params;
@@ -17,16 +20,16 @@ impl<'c> Channels<'c> {
 impl Channels<'_> {
     /// List all active channels in Asterisk.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Channel>> {
-        self.client.get("/channels").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// Channel details
     pub async fn get(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .get(format!("/channels/{channel_id}").as_str())
+            .call(&operations::GET, &[&channel_id.into()], &(), ())
             .await
     }
 
@@ -40,7 +43,12 @@ impl Channels<'_> {
         request: params::OriginateRequest,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .post_with_query("/channels", &request.variables, &request)
+            .call(
+                &operations::ORIGINATE,
+                &[],
+                &request,
+                Body::new().field("variables", &request.variables)?,
+            )
             .await
     }
 
@@ -54,7 +62,12 @@ impl Channels<'_> {
         request: params::CreateRequest,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .post_with_query("/channels", &request.variables, &request)
+            .call(
+                &operations::CREATE,
+                &[],
+                &request,
+                Body::new().field("variables", &request.variables)?,
+            )
             .await
     }
 
@@ -68,10 +81,11 @@ impl Channels<'_> {
         request: params::OriginateWithIdRequest,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .post_with_query(
-                format!("/channels/{}", request.channel_id).as_str(),
-                &request.variables,
+            .call(
+                &operations::ORIGINATE_WITH_ID,
+                &[&request.channel_id],
                 &request,
+                Body::new().field("variables", &request.variables)?,
             )
             .await
     }
@@ -79,20 +93,18 @@ impl Channels<'_> {
     /// Delete (i.e. hangup) a channel.
     pub async fn delete(&self, request: params::DeleteRequest) -> crate::errors::Result<()> {
         self.client
-            .delete_with_query(
-                format!("/channels/{}", request.channel_id).as_str(),
-                &request,
-            )
+            .call(&operations::HANGUP, &[&request.channel_id], &request, ())
             .await
     }
 
     /// Exit application; continue execution in the dialplan.
     pub async fn r#continue(&self, request: params::ContinueRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/continue", request.channel_id).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::CONTINUE_IN_DIALPLAN,
+                &[&request.channel_id],
                 &request,
+                (),
             )
             .await
     }
@@ -100,25 +112,22 @@ impl Channels<'_> {
     /// Move the channel from one Stasis application to another
     pub async fn r#move(&self, request: params::MoveRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/move", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::MOVE, &[&request.channel_id], &request, ())
             .await
     }
 
     /// Redirect the channel to a different location.
     pub async fn redirect(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
         endpoint: impl Into<String> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{channel_id}/redirect").as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::REDIRECT,
+                &[&channel_id.into()],
                 &[("endpoint", endpoint.into())],
+                (),
             )
             .await
     }
@@ -126,61 +135,49 @@ impl Channels<'_> {
     /// Answer a channel
     pub async fn answer(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/channels/{channel_id}/answer",).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::ANSWER, &[&channel_id.into()], &(), ())
             .await
     }
 
     /// Indicate ringing to a channel.
-    pub async fn ring(
-        &self,
-        channel_id: impl Into<String> + Display + Send,
-    ) -> crate::errors::Result<()> {
+    pub async fn ring(&self, channel_id: impl Into<ChannelId> + Send) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/channels/{channel_id}/ring").as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::RING, &[&channel_id.into()], &(), ())
             .await
     }
 
     /// Stop ringing indication on a channel if locally generated.
     pub async fn stop_ring(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/channels/{channel_id}/ring").as_str())
+            .call(&operations::RING_STOP, &[&channel_id.into()], &(), ())
             .await
     }
 
     /// Send provided DTMF to a given channel.
     pub async fn dtmf(&self, request: params::DtmfRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/dtmf", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::SEND_DTMF, &[&request.channel_id], &request, ())
             .await
     }
 
     /// Mute a channel.
     pub async fn mute(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
         direction: crate::apis::params::Direction,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{channel_id}/mute",).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::MUTE,
+                &[&channel_id.into()],
                 &[("direction", direction)],
+                (),
             )
             .await
     }
@@ -188,37 +185,33 @@ impl Channels<'_> {
     /// Unmute a channel.
     pub async fn unmute(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
         direction: crate::apis::params::Direction,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete_with_query(
-                format!("/channels/{channel_id}/mute").as_str(),
+            .call(
+                &operations::UNMUTE,
+                &[&channel_id.into()],
                 &[("direction", direction)],
+                (),
             )
             .await
     }
 
     /// Hold a channel.
-    pub async fn hold(
-        &self,
-        channel_id: impl Into<String> + Display + Send,
-    ) -> crate::errors::Result<()> {
+    pub async fn hold(&self, channel_id: impl Into<ChannelId> + Send) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/channels/{channel_id}/hold").as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::HOLD, &[&channel_id.into()], &(), ())
             .await
     }
 
     /// Remove a channel from hold.
     pub async fn unhold(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/channels/{channel_id}/hold").as_str())
+            .call(&operations::UNHOLD, &[&channel_id.into()], &(), ())
             .await
     }
 
@@ -229,21 +222,17 @@ impl Channels<'_> {
     /// If continuing music on hold is desired, the stasis application must reinitiate music on hold.
     pub async fn moh(&self, request: params::MohRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/moh", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::START_MOH, &[&request.channel_id], &request, ())
             .await
     }
 
     /// Stop playing music on hold to a channel.
     pub async fn unmoh(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/channels/{channel_id}/moh",).as_str())
+            .call(&operations::STOP_MOH, &[&channel_id.into()], &(), ())
             .await
     }
 
@@ -253,23 +242,20 @@ impl Channels<'_> {
     /// Using media operations such as /play on a channel playing silence in this manner will suspend silence without resuming automatically.
     pub async fn silence(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/channels/{channel_id}/silence").as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::START_SILENCE, &[&channel_id.into()], &(), ())
             .await
     }
 
     /// Stop playing silence to a channel.
     pub async fn stop_silence(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/channels/{channel_id}/silence").as_str())
+            .call(&operations::STOP_SILENCE, &[&channel_id.into()], &(), ())
             .await
     }
 
@@ -282,11 +268,7 @@ impl Channels<'_> {
         request: params::PlayRequest,
     ) -> crate::errors::Result<crate::apis::playbacks::models::Playback> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/play", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::PLAY, &[&request.channel_id], &request, ())
             .await
     }
 
@@ -299,14 +281,11 @@ impl Channels<'_> {
         request: params::PlayWithPlaybackIdRequest,
     ) -> crate::errors::Result<crate::apis::playbacks::models::Playback> {
         self.client
-            .post_with_query(
-                format!(
-                    "/channels/{}/play/{}",
-                    request.channel_id, request.playback_id
-                )
-                .as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::PLAY_WITH_ID,
+                &[&request.channel_id, &request.playback_id],
                 &request,
+                (),
             )
             .await
     }
@@ -322,25 +301,22 @@ impl Channels<'_> {
         request: params::RecordRequest,
     ) -> crate::errors::Result<crate::apis::recordings::models::LiveRecording> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/record", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::RECORD, &[&request.channel_id], &request, ())
             .await
     }
 
     /// Get the value of a channel variable or function
     pub async fn get_variable(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
         variable: impl Into<String> + Send,
     ) -> crate::errors::Result<crate::apis::asterisk::variables::models::Variable> {
         self.client
-            .post_with_query(
-                format!("/channels/{channel_id}/variable").as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::GET_CHANNEL_VAR,
+                &[&channel_id.into()],
                 &[("variable", variable.into())],
+                (),
             )
             .await
     }
@@ -348,15 +324,16 @@ impl Channels<'_> {
     /// Set the value of a channel variable or function.
     pub async fn set_variable(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
         variable: impl Into<String> + Send,
         value: impl Into<String> + Send,
-    ) -> crate::errors::Result<crate::apis::asterisk::variables::models::Variable> {
+    ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{channel_id}/variable").as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::SET_CHANNEL_VAR,
+                &[&channel_id.into()],
                 &[("variable", variable.into()), ("value", value.into())],
+                (),
             )
             .await
     }
@@ -370,10 +347,11 @@ impl Channels<'_> {
         request: params::SnoopRequest,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/snoop", request.channel_id).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::SNOOP_CHANNEL,
+                &[&request.channel_id],
                 &request,
+                (),
             )
             .await
     }
@@ -387,14 +365,11 @@ impl Channels<'_> {
         request: params::SnoopWithIdRequest,
     ) -> crate::errors::Result<models::Channel> {
         self.client
-            .post_with_query(
-                format!(
-                    "/channels/{}/snoop/{}",
-                    request.channel_id, request.snoop_id
-                )
-                .as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::SNOOP_CHANNEL_WITH_ID,
+                &[&request.channel_id, &request.snoop_id],
                 &request,
+                (),
             )
             .await
     }
@@ -402,21 +377,17 @@ impl Channels<'_> {
     /// Dial a created channel.
     pub async fn dial(&self, request: params::DialRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/channels/{}/dial", request.channel_id).as_str(),
-                vec![] as Vec<String>,
-                &request,
-            )
+            .call(&operations::DIAL, &[&request.channel_id], &request, ())
             .await
     }
 
     /// RTP stats on a channel.
     pub async fn rtp_statistics(
         &self,
-        channel_id: impl Into<String> + Display + Send,
+        channel_id: impl Into<ChannelId> + Send,
     ) -> crate::errors::Result<models::RTPStatistics> {
         self.client
-            .get(format!("/channels/{channel_id}/rtp_statistics").as_str())
+            .call(&operations::RTPSTATISTICS, &[&channel_id.into()], &(), ())
             .await
     }
 
@@ -425,8 +396,19 @@ impl Channels<'_> {
         &self,
         request: params::ExternalMediaRequest,
     ) -> crate::errors::Result<models::Channel> {
+        self.client.require(Capability::ExternalMedia).await?;
+        if request.encapsulation == params::Encapsulation::AudioSocket {
+            self.client
+                .require(Capability::ExternalMediaAudioSocket)
+                .await?;
+        }
         self.client
-            .post_with_query("/channels/externalMedia", &request.variables, &request)
+            .call(
+                &operations::EXTERNAL_MEDIA,
+                &[],
+                &request,
+                Body::new().field("variables", &request.variables)?,
+            )
             .await
     }
 }
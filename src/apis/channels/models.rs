@@ -1,15 +1,18 @@
-use serde::Deserialize;
+use crate::apis::ids::ChannelId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
 
 use chrono::{DateTime, Utc};
 
 /// Channel : A specific communication connection between Asterisk and an Endpoint.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Channel {
     /// Unique identifier of the channel.  This is the same as the Uniqueid field in AMI.
     #[serde(rename = "id")]
-    pub id: String,
+    pub id: ChannelId,
     /// Protocol id from underlying channel driver (i.e. Call-ID for chan_pjsip; will be empty if not applicable or not implemented by driver).
-    #[serde(rename = "protocol_id")]
+    #[serde(rename = "protocol_id", skip_serializing_if = "Option::is_none")]
     pub protocol_id: Option<String>,
     /// Name of the channel (i.e. SIP/foo-0000a7e3)
     #[serde(rename = "name")]
@@ -24,31 +27,55 @@ pub struct Channel {
     pub account_code: String,
     #[serde(rename = "dialplan")]
     pub dialplan: DialplanCep,
-    #[serde(rename = "creationtime")]
+    #[serde(
+        rename = "creationtime",
+        serialize_with = "crate::apis::serialize_timestamp"
+    )]
     pub creation_time: DateTime<Utc>,
     /// The default spoken language
     #[serde(rename = "language")]
     pub language: String,
-    /// Channel variables
-    #[serde(rename = "channelvars")]
-    pub channel_vars: Option<serde_json::Value>,
+    /// Channel variables, the ones listed in the `channelvars` option of the `[general]`
+    /// section of `ari.conf`.
+    ///
+    /// Empty unless `channelvars` is set there: ARI has no call to request them at runtime.
+    #[serde(
+        rename = "channelvars",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub channel_vars: HashMap<String, String>,
     /// The Caller ID RDNIS
-    #[serde(rename = "caller_rdnis")]
+    #[serde(rename = "caller_rdnis", skip_serializing_if = "Option::is_none")]
     pub caller_rdnis: Option<String>,
     /// The Tenant ID for the channel
-    #[serde(rename = "tenantid")]
+    #[serde(rename = "tenantid", skip_serializing_if = "Option::is_none")]
     pub tenant_id: Option<String>,
 }
 
+impl Channel {
+    /// The value of a channel variable sent along with the channel.
+    ///
+    /// Only the variables listed in the `channelvars` option of `ari.conf` are sent.
+    pub fn var(&self, name: &str) -> Option<&str> {
+        self.channel_vars.get(name).map(String::as_str)
+    }
+
+    /// The value of a channel variable, parsed; `None` when missing or not parsing.
+    pub fn var_as<T: FromStr>(&self, name: &str) -> Option<T> {
+        self.var(name)?.parse().ok()
+    }
+}
+
 /// CallerId : Caller identification
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CallerId {
     pub name: String,
     pub number: String,
 }
 
 /// DialplanCep : Dialplan location (context/extension/priority)
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DialplanCep {
     /// Context in the dialplan
     pub context: String,
@@ -57,58 +84,70 @@ pub struct DialplanCep {
     #[serde(rename = "exten")]
     pub extension: String,
 
-    /// Priority in the dialplan,
-    pub priority: f32,
+    /// Priority in the dialplan
+    pub priority: i64,
 
+    /// Dialplan application the channel is in
     #[serde(flatten)]
     pub app_name: App,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+/// App : Dialplan application a channel is executing
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct App {
     /// Name of current dialplan application
     #[serde(rename = "app_name")]
-    name: String,
+    pub name: String,
     /// Parameter of current dialplan application
-    #[serde(rename = "app_data")]
-    data: Option<String>,
+    #[serde(rename = "app_data", skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
 }
 
 /// RTPStatistics : A statistics of RTP.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RTPStatistics {
     /// Number of packets transmitted.,
     pub txcount: u64,
     /// Number of packets received.
     pub rxcount: u64,
     /// Jitter on transmitted packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub txjitter: Option<f64>,
 
     /// Jitter on received packets.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rxjitter: Option<f64>,
 
     /// Maximum jitter on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_maxjitter: Option<f64>,
 
     /// Minimum jitter on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_minjitter: Option<f64>,
 
     /// Average jitter on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_normdevjitter: Option<f64>,
 
     /// Standard deviation jitter on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_stdevjitter: Option<f64>,
 
     /// Maximum jitter on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_maxjitter: Option<f64>,
 
     /// Minimum jitter on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_minjitter: Option<f64>,
 
     /// Average jitter on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_normdevjitter: Option<f64>,
 
     /// Standard deviation jitter on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_stdevjitter: Option<f64>,
 
     /// Number of transmitted packets lost.
@@ -118,42 +157,55 @@ pub struct RTPStatistics {
     pub rxploss: u64,
 
     /// Maximum number of packets lost on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_maxrxploss: Option<f64>,
 
     /// Minimum number of packets lost on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_minrxploss: Option<f64>,
 
     /// Average number of packets lost on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_normdevrxploss: Option<f64>,
 
     /// Standard deviation packets lost on remote side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub remote_stdevrxploss: Option<f64>,
 
     /// Maximum number of packets lost on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_maxrxploss: Option<f64>,
 
     /// Minimum number of packets lost on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_minrxploss: Option<f64>,
 
     /// Average number of packets lost on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_normdevrxploss: Option<f64>,
 
     /// Standard deviation packets lost on local side.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub local_stdevrxploss: Option<f64>,
 
     /// Total round trip time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rtt: Option<f64>,
 
     /// Maximum round trip time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub maxrtt: Option<f64>,
 
     /// Minimum round trip time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minrtt: Option<f64>,
 
     /// Average round trip time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub normdevrtt: Option<f64>,
 
     /// Standard deviation round trip time.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub stdevrtt: Option<f64>,
 
     /// Our SSRC.
@@ -171,7 +223,7 @@ pub struct RTPStatistics {
     pub channel_uniqueid: String,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub enum ChannelState {
     Down,
     Rsrved,
@@ -189,3 +241,266 @@ pub enum ChannelState {
     #[default]
     Unknown,
 }
+
+/// Declares the hangup causes, with their Q.850 code and the text Asterisk gives them.
+macro_rules! hangup_causes {
+    ($($(#[$meta:meta])* $name:ident = $code:literal => $text:literal,)*) => {
+        /// HangupCause : Q.850 cause of a hangup, as reported by Asterisk.
+        ///
+        /// Serialized as its code. Codes without a variant are kept in [`HangupCause::Other`],
+        /// which only [`HangupCause::from`] builds, so that a code has a single representation.
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(from = "u32", into = "u32")]
+        pub enum HangupCause {
+            $($(#[$meta])* $name,)*
+            /// A cause not known to this library.
+            Other(OtherCause),
+        }
+
+        impl HangupCause {
+            /// The Q.850 code of the cause.
+            pub fn code(&self) -> u32 {
+                match self {
+                    $(HangupCause::$name => $code,)*
+                    HangupCause::Other(other) => other.0,
+                }
+            }
+
+            /// The text Asterisk gives the cause, as in the `cause_txt` of the events.
+            pub fn text(&self) -> &'static str {
+                match self {
+                    $(HangupCause::$name => $text,)*
+                    HangupCause::Other(_) => "Unknown",
+                }
+            }
+        }
+
+        impl From<u32> for HangupCause {
+            fn from(code: u32) -> Self {
+                match code {
+                    $($code => HangupCause::$name,)*
+                    code => HangupCause::Other(OtherCause(code)),
+                }
+            }
+        }
+    };
+}
+
+hangup_causes! {
+    /// No cause was given.
+    NotDefined = 0 => "Not Defined",
+    Unallocated = 1 => "Unallocated (unassigned) number",
+    NoRouteTransitNet = 2 => "No route to specified transmit network",
+    NoRouteDestination = 3 => "No route to destination",
+    MisdialledTrunkPrefix = 5 => "Misdialed trunk prefix",
+    ChannelUnacceptable = 6 => "Channel unacceptable",
+    CallAwardedDelivered = 7 => "Call awarded and being delivered in an established channel",
+    PreEmpted = 8 => "Pre-empted",
+    NumberPortedNotHere = 14 => "Number ported elsewhere",
+    /// The call ended normally.
+    #[default]
+    NormalClearing = 16 => "Normal Clearing",
+    UserBusy = 17 => "User busy",
+    /// The endpoint did not respond.
+    NoUserResponse = 18 => "No user responding",
+    /// The endpoint rang but was not answered.
+    NoAnswer = 19 => "User alerting, no answer",
+    SubscriberAbsent = 20 => "Subscriber absent",
+    CallRejected = 21 => "Call Rejected",
+    NumberChanged = 22 => "Number changed",
+    RedirectedToNewDestination = 23 => "Redirected to new destination",
+    AnsweredElsewhere = 26 => "Answered elsewhere",
+    DestinationOutOfOrder = 27 => "Destination out of order",
+    InvalidNumberFormat = 28 => "Invalid number format (addr incomplete)",
+    FacilityRejected = 29 => "Facility rejected",
+    ResponseToStatusEnquiry = 30 => "Response to STATus ENQuiry",
+    NormalUnspecified = 31 => "Normal, unspecified",
+    NormalCircuitCongestion = 34 => "Circuit/channel congestion",
+    NetworkOutOfOrder = 38 => "Network out of order",
+    NormalTemporaryFailure = 41 => "Temporary failure",
+    SwitchCongestion = 42 => "Switching equipment congestion",
+    AccessInfoDiscarded = 43 => "Access information discarded",
+    RequestedChanUnavail = 44 => "Requested channel not available",
+    FacilityNotSubscribed = 50 => "Facility not subscribed",
+    OutgoingCallBarred = 52 => "Outgoing call barred",
+    IncomingCallBarred = 54 => "Incoming call barred",
+    BearerCapabilityNotAuth = 57 => "Bearer capability not authorized",
+    BearerCapabilityNotAvail = 58 => "Bearer capability not available",
+    BearerCapabilityNotImpl = 65 => "Bearer capability not implemented",
+    ChanNotImplemented = 66 => "Channel not implemented",
+    FacilityNotImplemented = 69 => "Facility not implemented",
+    InvalidCallReference = 81 => "Invalid call reference value",
+    IncompatibleDestination = 88 => "Incompatible destination",
+    InvalidMsgUnspecified = 95 => "Invalid message unspecified",
+    MandatoryIeMissing = 96 => "Mandatory information element is missing",
+    MessageTypeNonexist = 97 => "Message type nonexist.",
+    WrongMessage = 98 => "Wrong message",
+    IeNonexist = 99 => "Info. element nonexist or not implemented",
+    InvalidIeContents = 100 => "Invalid information element contents",
+    WrongCallState = 101 => "Message not compatible with call state",
+    RecoveryOnTimerExpire = 102 => "Recover on timer expiry",
+    MandatoryIeLengthError = 103 => "Mandatory IE length error",
+    ProtocolError = 111 => "Protocol error, unspecified",
+    Interworking = 127 => "Interworking, unspecified",
+}
+
+/// The code of a [`HangupCause::Other`], never one of the known causes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OtherCause(u32);
+
+impl OtherCause {
+    /// The Q.850 code of the cause.
+    pub fn code(&self) -> u32 {
+        self.0
+    }
+}
+
+impl From<HangupCause> for u32 {
+    fn from(cause: HangupCause) -> Self {
+        cause.code()
+    }
+}
+
+impl std::fmt::Display for HangupCause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.text(), self.code())
+    }
+}
+
+/// DialStatus : Status of a dialing attempt, as in the `DIALSTATUS` of the dialplan.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum DialStatus {
+    /// Dialing has started, without any status yet (empty in the events).
+    #[default]
+    Pending,
+    /// The peer is ringing.
+    Ringing,
+    /// The peer reported progress.
+    Progress,
+    /// The peer answered.
+    Answer,
+    /// The peer is busy.
+    Busy,
+    /// The peer did not answer in time.
+    NoAnswer,
+    /// The dialing was cancelled by the caller.
+    Cancel,
+    /// The network or the peer is congested.
+    Congestion,
+    /// The peer is unavailable or does not exist.
+    ChanUnavail,
+    /// The privacy manager refused the call.
+    DontCall,
+    /// The privacy manager sent the call to the torture script.
+    Torture,
+    /// The dial arguments were invalid.
+    InvalidArgs,
+    /// A status not known to this library.
+    Other(String),
+}
+
+impl DialStatus {
+    /// The status, as sent by Asterisk.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DialStatus::Pending => "",
+            DialStatus::Ringing => "RINGING",
+            DialStatus::Progress => "PROGRESS",
+            DialStatus::Answer => "ANSWER",
+            DialStatus::Busy => "BUSY",
+            DialStatus::NoAnswer => "NOANSWER",
+            DialStatus::Cancel => "CANCEL",
+            DialStatus::Congestion => "CONGESTION",
+            DialStatus::ChanUnavail => "CHANUNAVAIL",
+            DialStatus::DontCall => "DONTCALL",
+            DialStatus::Torture => "TORTURE",
+            DialStatus::InvalidArgs => "INVALIDARGS",
+            DialStatus::Other(status) => status,
+        }
+    }
+
+    /// Whether the dialing attempt is over.
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            DialStatus::Pending | DialStatus::Ringing | DialStatus::Progress
+        )
+    }
+}
+
+impl From<&str> for DialStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "" => DialStatus::Pending,
+            "RINGING" => DialStatus::Ringing,
+            "PROGRESS" => DialStatus::Progress,
+            "ANSWER" => DialStatus::Answer,
+            "BUSY" => DialStatus::Busy,
+            "NOANSWER" => DialStatus::NoAnswer,
+            "CANCEL" => DialStatus::Cancel,
+            "CONGESTION" => DialStatus::Congestion,
+            "CHANUNAVAIL" => DialStatus::ChanUnavail,
+            "DONTCALL" => DialStatus::DontCall,
+            "TORTURE" => DialStatus::Torture,
+            "INVALIDARGS" => DialStatus::InvalidArgs,
+            status => DialStatus::Other(status.to_string()),
+        }
+    }
+}
+
+impl From<String> for DialStatus {
+    fn from(status: String) -> Self {
+        status.as_str().into()
+    }
+}
+
+impl From<DialStatus> for String {
+    fn from(status: DialStatus) -> Self {
+        status.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for DialStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::channels::params::DeleteRequest;
+
+    #[test]
+    fn test_hangup_cause_codes() {
+        for code in 0..=255 {
+            let cause = HangupCause::from(code);
+            assert_eq!(cause.code(), code);
+            assert_eq!(HangupCause::from(cause.code()), cause);
+        }
+        assert_eq!(HangupCause::from(17), HangupCause::UserBusy);
+        assert_eq!(HangupCause::UserBusy.text(), "User busy");
+
+        let HangupCause::Other(other) = HangupCause::from(4) else {
+            panic!("4 is not a known cause");
+        };
+        assert_eq!(other.code(), 4);
+        assert_eq!(HangupCause::from(4).text(), "Unknown");
+        assert_eq!(HangupCause::from(4).to_string(), "Unknown (4)");
+    }
+
+    #[test]
+    fn test_hangup_cause_serde() {
+        let cause: HangupCause = serde_json::from_str("17").unwrap();
+        assert_eq!(cause, HangupCause::UserBusy);
+        let cause: HangupCause = serde_json::from_str("4").unwrap();
+        assert_eq!(serde_json::to_string(&cause).unwrap(), "4");
+
+        let request = DeleteRequest::new("1").with_reason_code(HangupCause::UserBusy);
+        assert_eq!(
+            serde_urlencoded::to_string(&request).unwrap(),
+            "reason_code=17"
+        );
+    }
+}
@@ -1,4 +1,8 @@
+use crate::apis::channels::models::HangupCause;
 use crate::apis::concat_option_str;
+use crate::apis::dtmf::DtmfSequence;
+use crate::apis::ids::{AppName, ChannelId, PlaybackId, RecordingName};
+use crate::apis::media::MediaList;
 use crate::apis::params::{Direction, IfExists, TerminateOn};
 use derive_new::new;
 use derive_setters::Setters;
@@ -40,17 +44,17 @@ pub struct OriginateRequest {
     /// The unique id to assign the channel on creation.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    channel_id: Option<String>,
+    channel_id: Option<ChannelId>,
 
     /// The unique id to assign the second channel when using local channels.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    other_channel_id: Option<String>,
+    other_channel_id: Option<ChannelId>,
 
     /// The unique id of the channel which is originating this one.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    originator: Option<String>,
+    originator: Option<ChannelId>,
 
     /// The format name capability list to use if originator is not specified.
     /// Ex. "ulaw,slin16".
@@ -105,7 +109,7 @@ pub struct App {
     #[serde(rename = "app")]
     #[setters(skip)]
     #[new(into)]
-    name: String,
+    name: AppName,
     /// The application arguments to pass to the Stasis application provided by 'app'.
     #[serde(rename = "appArgs", skip_serializing_if = "Option::is_none")]
     #[new(default)]
@@ -130,17 +134,17 @@ pub struct CreateRequest {
     /// The unique id to assign the channel on creation.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    channel_id: Option<String>,
+    channel_id: Option<ChannelId>,
 
     /// The unique id to assign the second channel when using local channels.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    other_channel_id: Option<String>,
+    other_channel_id: Option<ChannelId>,
 
     /// Unique ID of the calling channel
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    originator: Option<String>,
+    originator: Option<ChannelId>,
 
     /// The format name capability list to use if originator is not specified.
     /// Ex. "ulaw,slin16".
@@ -169,7 +173,7 @@ pub struct OriginateWithIdRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Endpoint to call.
     #[setters(skip)]
@@ -179,7 +183,7 @@ pub struct OriginateWithIdRequest {
     /// The extension to dial after the endpoint answers. Mutually exclusive with 'app'.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    extension: Option<Extension>,
+    pub(crate) extension: Option<Extension>,
 
     /// The application that is subscribed to the originated channel.
     ///
@@ -187,7 +191,7 @@ pub struct OriginateWithIdRequest {
     /// Mutually exclusive with  'extension'.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    app: Option<App>,
+    pub(crate) app: Option<App>,
 
     /// CallerID to use when dialing the endpoint or extension.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -202,12 +206,12 @@ pub struct OriginateWithIdRequest {
     /// The unique id to assign the second channel when using local channels.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    other_channel_id: Option<String>,
+    other_channel_id: Option<ChannelId>,
 
     /// The unique id of the channel which is originating this one.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    originator: Option<String>,
+    originator: Option<ChannelId>,
 
     /// The format name capability list to use if originator is not specified.
     /// Ex. "ulaw,slin16".
@@ -266,7 +270,7 @@ pub struct DeleteRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// The reason code for hanging up the channel for detail use.
     ///
@@ -274,7 +278,7 @@ pub struct DeleteRequest {
     /// See detail hangup codes at here. <https://docs.asterisk.org/Configuration/Miscellaneous/Hangup-Cause-Mappings/>
     #[serde(rename = "reason_code", skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    reason_code: Option<String>,
+    reason_code: Option<HangupCause>,
 
     /// Reason for hanging up the channel for simple use.
     ///
@@ -292,7 +296,7 @@ pub struct ContinueRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// The extension to continue to.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
@@ -308,7 +312,7 @@ pub struct MoveRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// The channel will be passed to this Stasis application.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
@@ -324,12 +328,12 @@ pub struct DtmfRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// DTMF To send.
     #[setters(skip)]
     #[new(into)]
-    dtmf: String,
+    dtmf: DtmfSequence,
 
     /// Amount of time to wait before DTMF digits (specified in milliseconds) start.
     #[serde(rename = "before", skip_serializing_if = "Option::is_none")]
@@ -360,7 +364,7 @@ pub struct MohRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Music on hold class to use.
     #[serde(rename = "mohClass", skip_serializing_if = "Option::is_none")]
@@ -376,12 +380,12 @@ pub struct PlayRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
-    /// Media URIs to play.
+    /// Media URIs to play, one after the other.
     #[setters(skip)]
     #[new(into)]
-    pub(crate) media: String,
+    pub(crate) media: MediaList,
 
     /// For sounds, selects language for sound.
     #[serde(rename = "lang", skip_serializing_if = "Option::is_none")]
@@ -407,18 +411,18 @@ pub struct PlayWithPlaybackIdRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Playback ID.
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) playback_id: String,
+    pub(crate) playback_id: PlaybackId,
 
-    /// Media URIs to play.
+    /// Media URIs to play, one after the other.
     #[setters(skip)]
     #[new(into)]
-    pub(crate) media: String,
+    pub(crate) media: MediaList,
 
     /// For sounds, selects language for sound.
     #[serde(rename = "lang", skip_serializing_if = "Option::is_none")]
@@ -444,12 +448,12 @@ pub struct RecordRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Recording's filename
     #[setters(skip)]
     #[new(into)]
-    pub(crate) name: String,
+    pub(crate) name: RecordingName,
 
     /// Format to encode audio in
     #[setters(skip)]
@@ -490,7 +494,7 @@ pub struct SnoopRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// The channel will be passed to this Stasis application.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
@@ -516,13 +520,13 @@ pub struct SnoopWithIdRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Channel's id
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) snoop_id: String,
+    pub(crate) snoop_id: ChannelId,
 
     /// The channel will be passed to this Stasis application.
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
@@ -548,12 +552,12 @@ pub struct DialRequest {
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) channel_id: String,
+    pub(crate) channel_id: ChannelId,
 
     /// Channel ID of caller.
     #[serde(rename = "caller", skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    caller: Option<String>,
+    caller: Option<ChannelId>,
 
     /// Dial timeout.
     #[serde(rename = "timeout", skip_serializing_if = "Option::is_none")]
@@ -568,7 +572,7 @@ pub struct ExternalMediaRequest {
     /// Stasis Application to place channel into.
     #[setters(skip)]
     #[new(into)]
-    app: String,
+    app: AppName,
 
     /// Hostname/ip:port of external host.
     #[setters(skip)]
@@ -581,9 +585,9 @@ pub struct ExternalMediaRequest {
     format: String,
 
     /// The unique id to assign the channel on creation.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "channelId", skip_serializing_if = "Option::is_none")]
     #[new(default)]
-    channel_id: Option<String>,
+    channel_id: Option<ChannelId>,
 
     /// The "variables" key in the body object holds variable key/value pairs to set on the channel on creation.
     ///
@@ -595,7 +599,7 @@ pub struct ExternalMediaRequest {
 
     /// Payload encapsulation protocol.
     #[new(default)]
-    encapsulation: Encapsulation,
+    pub(crate) encapsulation: Encapsulation,
 
     /// Transport protocol.
     #[new(default)]
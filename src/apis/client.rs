@@ -1,3 +1,8 @@
+use crate::apis::capabilities::{Capabilities, Capability};
+use crate::apis::operation::Operation;
+use crate::apis::transport::{
+    AriTransport, Method, ReqwestTransport, TransportRequest, TransportResponse,
+};
 use crate::config::Config;
 use crate::errors::AriError;
 use base64::prelude::BASE64_STANDARD;
@@ -5,16 +10,19 @@ use base64::Engine;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::fmt::Display;
+use std::sync::{Arc, RwLock};
 
 /// Represents the ARI client.
 ///
-/// This struct holds the configuration and HTTP client for making requests to the ARI API.
+/// This struct holds the configuration and the transport for making requests to the ARI API.
 #[derive(Debug)]
 pub struct Client {
     /// Configuration for the ARI client.
     pub(crate) config: Config,
-    /// HTTP client for making requests.
-    pub(crate) client: reqwest::Client,
+    /// Transport sending the requests.
+    pub(crate) transport: Arc<dyn AriTransport>,
+    /// Features of the server, read once per connection.
+    capabilities: RwLock<Option<Capabilities>>,
 }
 
 impl Client {
@@ -29,11 +37,14 @@ impl Client {
     ///
     /// A new instance of `Client`.
     pub fn build(config: Config, client: reqwest::Client) -> Self {
-        Client { config, client }
+        Client::with_config(config).with_client(client)
     }
 
     /// Creates a new client with the given configuration.
     ///
+    /// The TLS settings and timeouts were validated by [`crate::ConfigBuilder::build`], the
+    /// only way to set them.
+    ///
     /// # Arguments
     ///
     /// * `config` - The configuration for the ARI client.
@@ -42,14 +53,12 @@ impl Client {
     ///
     /// A new instance of `Client`.
     pub fn with_config(config: Config) -> Self {
+        let transport = ReqwestTransport::with_config(&config)
+            .expect("the HTTP client settings are validated by ConfigBuilder::build");
         Client {
             config,
-            client: reqwest::Client::builder()
-                .connect_timeout(std::time::Duration::from_secs(5))
-                .http2_keep_alive_interval(Some(std::time::Duration::from_secs(5)))
-                .http2_keep_alive_while_idle(true)
-                .build()
-                .unwrap(),
+            transport: Arc::new(transport),
+            capabilities: RwLock::new(None),
         }
     }
 
@@ -62,372 +71,343 @@ impl Client {
     /// # Returns
     ///
     /// The updated `Client` instance.
-    pub fn with_client(mut self, client: reqwest::Client) -> Self {
-        self.client = client;
+    pub fn with_client(self, client: reqwest::Client) -> Self {
+        self.with_transport(ReqwestTransport::new(client))
+    }
+
+    /// Sets the transport sending the requests, instead of `reqwest`.
+    ///
+    /// # Arguments
+    ///
+    /// * `transport` - The transport to use.
+    ///
+    /// # Returns
+    ///
+    /// The updated `Client` instance.
+    pub fn with_transport(mut self, transport: impl AriTransport + 'static) -> Self {
+        self.transport = Arc::new(transport);
         self
     }
 
+    /// Returns the transport sending the requests.
+    pub fn transport(&self) -> Arc<dyn AriTransport> {
+        self.transport.clone()
+    }
+
+    /// Returns the features of the Asterisk server, from the version it reports.
+    ///
+    /// The version is read on the first call, then cached until the WebSocket reconnects, as
+    /// Asterisk may have been upgraded in the meantime. When it cannot be read, the version is
+    /// unknown and every feature is assumed, until the WebSocket reconnects as well.
+    pub async fn capabilities(&self) -> Capabilities {
+        if let Some(capabilities) = self.capabilities.read().unwrap().clone() {
+            return capabilities;
+        }
+
+        let capabilities = match self.asterisk().info().await {
+            Ok(info) => {
+                let version = info.system.map(|system| system.version);
+                Capabilities::new(version.unwrap_or_default())
+            }
+            Err(e) => {
+                tracing::warn!("unable to read the version of Asterisk: {e}");
+                Capabilities::unknown()
+            }
+        };
+        *self.capabilities.write().unwrap() = Some(capabilities.clone());
+        capabilities
+    }
+
+    /// Forgets the features of the server, to read them again on the next call.
+    pub(crate) fn forget_capabilities(&self) {
+        self.capabilities.write().unwrap().take();
+    }
+
+    /// Fails with `AriError::Unsupported` when the server lacks a feature.
+    pub(crate) async fn require(&self, capability: Capability) -> Result<(), AriError> {
+        self.capabilities().await.require(capability)
+    }
+
     /// Returns an instance of the `Applications` API.
-    pub fn applications(&self) -> crate::apis::applications::Applications {
+    pub fn applications(&self) -> crate::apis::applications::Applications<'_> {
         crate::apis::applications::Applications::new(self)
     }
 
     /// Returns an instance of the `Asterisk` API.
-    pub fn asterisk(&self) -> crate::apis::asterisk::Asterisk {
+    pub fn asterisk(&self) -> crate::apis::asterisk::Asterisk<'_> {
         crate::apis::asterisk::Asterisk::new(self)
     }
 
     /// Returns an instance of the `Endpoints` API.
-    pub fn endpoints(&self) -> crate::apis::endpoints::Endpoints {
+    pub fn endpoints(&self) -> crate::apis::endpoints::Endpoints<'_> {
         crate::apis::endpoints::Endpoints::new(self)
     }
 
     /// Returns an instance of the `Channels` API.
-    pub fn channels(&self) -> crate::apis::channels::Channels {
+    pub fn channels(&self) -> crate::apis::channels::Channels<'_> {
         crate::apis::channels::Channels::new(self)
     }
 
     /// Returns an instance of the `Bridges` API.
-    pub fn bridges(&self) -> crate::apis::bridges::Bridges {
+    pub fn bridges(&self) -> crate::apis::bridges::Bridges<'_> {
         crate::apis::bridges::Bridges::new(self)
     }
 
     /// Returns an instance of the `Recordings` API.
-    pub fn recordings(&self) -> crate::apis::recordings::Recordings {
+    pub fn recordings(&self) -> crate::apis::recordings::Recordings<'_> {
         crate::apis::recordings::Recordings::new(self)
     }
 
     /// Returns an instance of the `Sounds` API.
-    pub fn sounds(&self) -> crate::apis::sounds::Sounds {
+    pub fn sounds(&self) -> crate::apis::sounds::Sounds<'_> {
         crate::apis::sounds::Sounds::new(self)
     }
 
     /// Returns an instance of the `Playbacks` API.
-    pub fn playbacks(&self) -> crate::apis::playbacks::Playbacks {
+    pub fn playbacks(&self) -> crate::apis::playbacks::Playbacks<'_> {
         crate::apis::playbacks::Playbacks::new(self)
     }
 
     /// Returns an instance of the `DeviceStats` API.
-    pub fn device_stats(&self) -> crate::apis::device_stats::DeviceStats {
+    pub fn device_stats(&self) -> crate::apis::device_stats::DeviceStats<'_> {
         crate::apis::device_stats::DeviceStats::new(self)
     }
 
     /// Returns an instance of the `Mailboxes` API.
-    pub fn mailboxes(&self) -> crate::apis::mailboxes::Mailboxes {
+    pub fn mailboxes(&self) -> crate::apis::mailboxes::Mailboxes<'_> {
         crate::apis::mailboxes::Mailboxes::new(self)
     }
 
     /// Returns an instance of the `Events` API.
-    pub fn events(&self) -> crate::apis::events::Events {
+    pub fn events(&self) -> crate::apis::events::Events<'_> {
         crate::apis::events::Events::new(self)
     }
 
-    /// Makes a GET request to the specified path and deserializes the response body.
+    /// Sends a request of an operation and deserializes the response body.
     ///
     /// # Arguments
     ///
-    /// * `path` - The path for the GET request.
+    /// * `operation` - The operation, as declared by the definitions of ARI.
+    /// * `args` - The path parameters of the operation, in order.
+    /// * `query` - The query parameters.
+    /// * `body` - The JSON body, omitted when it serializes to `null`.
     ///
     /// # Returns
     ///
     /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn get<O>(&self, path: &str) -> Result<O, AriError>
+    pub(crate) async fn call<Q, I, O>(
+        &self,
+        operation: &Operation,
+        args: &[&(dyn Display + Sync)],
+        query: &Q,
+        body: I,
+    ) -> Result<O, AriError>
     where
+        Q: Serialize + ?Sized,
+        I: Serialize,
         O: DeserializeOwned,
     {
-        let request_maker = || async {
-            self.client
-                .get(self.url(path))
-                .headers(self.headers())
-                .build()
-                .map_err(|e| AriError::Internal(e.to_string()))
-        };
-
-        self.execute(request_maker).await
+        let (query, body) = (query_string(query)?, json_body(&body)?);
+        #[cfg(test)]
+        operation.check(args.len(), &query, body.as_deref());
+        let path = operation.path_with(args);
+        self.execute(self.request(operation.method.clone(), &path, Some(query), body))
+            .await
     }
 
-    /// Makes a GET request to the specified path with the given query and deserializes the response body.
+    /// Constructs the full URL for the given path.
     ///
     /// # Arguments
     ///
-    /// * `path` - The path for the GET request.
-    /// * `query` - The query parameters for the GET request.
+    /// * `path` - The path to append to the base URL.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn get_with_query<Q, O>(&self, path: &str, query: &Q) -> Result<O, AriError>
-    where
-        O: DeserializeOwned,
-        Q: Serialize + ?Sized,
-    {
-        let request_maker = || async {
-            self.client
-                .get(self.url(path))
-                .query(query)
-                .headers(self.headers())
-                .build()
-                .map_err(|e| AriError::Internal(e.to_string()))
-        };
-
-        self.execute(request_maker).await
+    /// The full URL as a string.
+    pub(crate) fn url(&self, path: impl Into<String> + Display) -> String {
+        format!("{}/ari{}", self.config.api_base, path)
     }
 
-    /// Makes a DELETE request to the specified path and deserializes the response body.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - The path for the DELETE request.
+    /// Constructs the headers for the HTTP requests.
     ///
     /// # Returns
     ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn delete<O>(&self, path: &str) -> Result<O, AriError>
-    where
-        O: DeserializeOwned,
-    {
-        let request_maker = || async {
-            self.client
-                .delete(self.url(path))
-                .headers(self.headers())
-                .build()
-                .map_err(|e| AriError::Internal(e.to_string()))
-        };
-
-        self.execute(request_maker).await
+    /// The content type and authorization headers.
+    pub(crate) fn headers(&self) -> Vec<(String, String)> {
+        vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            (
+                "Authorization".to_string(),
+                format!(
+                    "Basic {}",
+                    BASE64_STANDARD
+                        .encode(format!("{}:{}", self.config.username, self.config.password))
+                ),
+            ),
+        ]
     }
 
-    /// Makes a DELETE request to the specified path with the given query and deserializes the response body.
+    /// Constructs a request to send through the transport.
     ///
     /// # Arguments
     ///
-    /// * `path` - The path for the DELETE request.
-    /// * `query` - The query parameters for the DELETE request.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn delete_with_query<O, Q>(&self, path: &str, query: &Q) -> Result<O, AriError>
-    where
-        O: DeserializeOwned,
-        Q: Serialize + ?Sized,
-    {
-        let request_maker = || async {
-            self.client
-                .delete(self.url(path))
-                .query(query)
-                .headers(self.headers())
-                .build()
-                .map_err(|e| AriError::Internal(e.to_string()))
-        };
+    /// * `method` - The HTTP method.
+    /// * `path` - The path of the request.
+    /// * `query` - The encoded query string, if any.
+    /// * `body` - The JSON body, if any.
+    fn request(
+        &self,
+        method: Method,
+        path: &str,
+        query: Option<String>,
+        body: Option<String>,
+    ) -> TransportRequest {
+        let mut url = self.url(path);
+        if let Some(query) = query.filter(|query| !query.is_empty()) {
+            url.push('?');
+            url.push_str(&query);
+        }
 
-        self.execute(request_maker).await
+        TransportRequest {
+            method,
+            url,
+            headers: self.headers(),
+            body,
+        }
     }
 
-    /// Makes a POST request to the specified path with the given request body and deserializes the response body.
+    /// Sends a request through the transport.
     ///
     /// # Arguments
     ///
-    /// * `path` - The path for the POST request.
-    /// * `request` - The request body for the POST request.
+    /// * `request` - The request to send.
     ///
     /// # Returns
     ///
     /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn post<I, O>(&self, path: &str, request: I) -> Result<O, AriError>
+    async fn execute<O>(&self, request: TransportRequest) -> Result<O, AriError>
     where
-        I: Serialize,
         O: DeserializeOwned,
     {
-        let request_maker = || async {
-            let mut req = self.client.post(self.url(path)).headers(self.headers());
-            if !serde_json::to_value(&request)?.is_null() {
-                req = req.json(&request);
-            }
-            req.build().map_err(|e| AriError::Internal(e.to_string()))
-        };
-
-        self.execute(request_maker).await
+        let response = self.transport.send(request).await?;
+        parse_response(response)
     }
+}
 
-    /// Makes a POST request to the specified path with the given request body and query parameters, and deserializes the response body.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - The path for the POST request.
-    /// * `request` - The request body for the POST request.
-    /// * `query` - The query parameters for the POST request.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn post_with_query<I, Q, O>(
-        &self,
-        path: &str,
-        request: I,
-        query: &Q,
-    ) -> Result<O, AriError>
-    where
-        I: Serialize,
-        O: DeserializeOwned,
-        Q: Serialize + ?Sized,
-    {
-        let request_maker = || async {
-            let mut req = self
-                .client
-                .post(self.url(path))
-                .query(query)
-                .headers(self.headers());
-            if !serde_json::to_value(&request)?.is_null() {
-                req = req.json(&request);
-            }
-            req.build().map_err(|e| AriError::Internal(e.to_string()))
-        };
+/// Encodes the query parameters of a request.
+fn query_string<Q>(query: &Q) -> Result<String, AriError>
+where
+    Q: Serialize + ?Sized,
+{
+    serde_urlencoded::to_string(query).map_err(|e| AriError::Internal(e.to_string()))
+}
 
-        self.execute(request_maker).await
+/// Encodes the body of a request, omitted when it serializes to `null`.
+fn json_body<I>(request: &I) -> Result<Option<String>, AriError>
+where
+    I: Serialize,
+{
+    let body = serde_json::to_value(request)?;
+    if body.is_null() {
+        return Ok(None);
     }
+    Ok(Some(body.to_string()))
+}
 
-    /// Makes a PUT request to the specified path with the given request body and query parameters, and deserializes the response body.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - The path for the PUT request.
-    /// * `request` - The request body for the PUT request.
-    /// * `query` - The query parameters for the PUT request.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn put_with_query<I, O, Q>(
-        &self,
-        path: &str,
-        request: I,
-        query: &Q,
-    ) -> Result<O, AriError>
-    where
-        I: Serialize,
-        O: DeserializeOwned,
-        Q: Serialize + ?Sized,
-    {
-        let request_maker = || async {
-            let mut req = self
-                .client
-                .put(self.url(path))
-                .query(query)
-                .headers(self.headers());
-            if !serde_json::to_value(&request)?.is_null() {
-                req = req.json(&request);
-            }
-            req.build().map_err(|e| AriError::Internal(e.to_string()))
-        };
-
-        self.execute(request_maker).await
+/// Deserializes the body of a successful response, or turns an error status into an `AriError`.
+fn parse_response<O>(response: TransportResponse) -> Result<O, AriError>
+where
+    O: DeserializeOwned,
+{
+    if !response.is_success() {
+        let code = reqwest::StatusCode::from_u16(response.status)
+            .unwrap_or(reqwest::StatusCode::INTERNAL_SERVER_ERROR);
+        let content = Some(response.body).filter(|body| !body.is_empty());
+        return Err(AriError::new(code, content));
+    }
+    if response.body.is_empty() {
+        return Ok(serde_json::from_str("null")?);
     }
 
-    /// Makes a PUT request to the specified path with the given request body and deserializes the response body.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - The path for the PUT request.
-    /// * `request` - The request body for the PUT request.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    pub(crate) async fn put<I, O>(&self, path: &str, request: I) -> Result<O, AriError>
-    where
-        I: Serialize,
-        O: DeserializeOwned,
-    {
-        let request_maker = || async {
-            let mut req = self.client.put(self.url(path)).headers(self.headers());
-            if !serde_json::to_value(&request)?.is_null() {
-                req = req.json(&request);
-            }
-            req.build().map_err(|e| AriError::Internal(e.to_string()))
-        };
+    Ok(serde_json::from_str(&response.body)?)
+}
 
-        self.execute(request_maker).await
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::channels::params::{OriginateRequest, PlayRequest};
+    use crate::apis::transport::TransportFuture;
+    use std::sync::Mutex;
+
+    /// Records the requests and answers them with a canned response.
+    #[derive(Debug)]
+    struct Recorder {
+        requests: Arc<Mutex<Vec<TransportRequest>>>,
+        response: TransportResponse,
     }
 
-    /// Constructs the full URL for the given path.
-    ///
-    /// # Arguments
-    ///
-    /// * `path` - The path to append to the base URL.
-    ///
-    /// # Returns
-    ///
-    /// The full URL as a string.
-    pub(crate) fn url(&self, path: impl Into<String> + Display) -> String {
-        format!("{}/ari{}", self.config.api_base, path)
+    impl AriTransport for Recorder {
+        fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+            self.requests.lock().unwrap().push(request);
+            let response = self.response.clone();
+            Box::pin(async move { Ok(response) })
+        }
     }
 
-    /// Constructs the headers for the HTTP requests.
-    ///
-    /// # Returns
-    ///
-    /// A `HeaderMap` containing the necessary headers.
-    pub(crate) fn headers(&self) -> reqwest::header::HeaderMap {
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            reqwest::header::CONTENT_TYPE,
-            "application/json".parse().unwrap(),
-        );
-        headers.insert(
-            reqwest::header::AUTHORIZATION,
-            format!(
-                "Basic {}",
-                BASE64_STANDARD
-                    .encode(format!("{}:{}", self.config.username, self.config.password))
-            )
-            .parse()
-            .unwrap(),
+    fn client(response: TransportResponse) -> (Client, Arc<Mutex<Vec<TransportRequest>>>) {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let config = Config::new("http://localhost:8088", "user", "secret");
+        let client = Client::with_config(config).with_transport(Recorder {
+            requests: requests.clone(),
+            response,
+        });
+        (client, requests)
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_the_transport() {
+        let (client, requests) = client(TransportResponse::new(204, ""));
+
+        client
+            .channels()
+            .play(PlayRequest::new("1", "sound:hello"))
+            .await
+            .unwrap_err();
+        client.channels().answer("1").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests[0].method, Method::POST);
+        assert!(requests[0]
+            .url
+            .starts_with("http://localhost:8088/ari/channels/1/play?"));
+        assert!(requests[0].url.contains("media=sound%3Ahello"));
+        assert_eq!(
+            requests[1].url,
+            "http://localhost:8088/ari/channels/1/answer"
         );
-        headers
+        assert!(requests[1].headers.contains(&(
+            "Authorization".to_string(),
+            "Basic dXNlcjpzZWNyZXQ=".to_string()
+        )));
     }
 
-    /// Executes an HTTP request and retries on rate limit.
-    ///
-    /// # Arguments
-    ///
-    /// * `request_maker` - A closure that creates the request.
-    ///
-    /// # Returns
-    ///
-    /// A `Result` containing the deserialized response body or an `AriError`.
-    ///
-    /// The `request_maker` serves one purpose: to be able to create the request again
-    /// to retry the API call after getting rate limited. `request_maker` is async because
-    /// `reqwest::multipart::Form` is created by async calls to read files for uploads.
-    async fn execute<O, M, Fut>(&self, request_maker: M) -> Result<O, AriError>
-    where
-        O: DeserializeOwned,
-        M: Fn() -> Fut,
-        Fut: core::future::Future<Output = Result<reqwest::Request, AriError>>,
-    {
-        let response = self
-            .client
-            .execute(request_maker().await?)
+    #[tokio::test]
+    async fn test_error_statuses_become_api_errors() {
+        let (client, requests) = client(TransportResponse::new(404, r#"{"message":"nope"}"#));
+
+        let error = client
+            .channels()
+            .originate(OriginateRequest::new("PJSIP/1000"))
             .await
-            .map_err(|e| AriError::Internal(e.to_string()))?;
-
-        match response.error_for_status_ref() {
-            Ok(_) => {
-                let body = response
-                    .text()
-                    .await
-                    .map_err(|e| AriError::Internal(e.to_string()))?;
-                if body.is_empty() {
-                    return Ok(serde_json::from_str("null")?);
-                }
-
-                Ok(serde_json::from_str(&body)?)
+            .unwrap_err();
+
+        match error {
+            AriError::Api(e) => {
+                assert_eq!(e.code, reqwest::StatusCode::NOT_FOUND);
+                assert_eq!(e.content.as_deref(), Some(r#"{"message":"nope"}"#));
             }
-            Err(e) => Err(AriError::Http {
-                raw: e,
-                body: response.text().await.unwrap_or_default(),
-            }),
+            other => panic!("unexpected error {:?}", other),
         }
+        assert_eq!(requests.lock().unwrap()[0].method, Method::POST);
     }
 }
@@ -1,6 +1,7 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::device_states as operations;
 use serde::Serialize;
 
 pub struct DeviceStats<'c> {
@@ -15,7 +16,7 @@ impl<'c> DeviceStats<'c> {
 
 impl DeviceStats<'_> {
     pub async fn list(&self) -> crate::errors::Result<Vec<models::DeviceState>> {
-        self.client.get("/deviceStates").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// Retrieve the current state of a device
@@ -24,7 +25,7 @@ impl DeviceStats<'_> {
         device_name: impl Into<String> + Send,
     ) -> crate::errors::Result<models::DeviceState> {
         self.client
-            .get(format!("/deviceStates/{}", device_name.into()).as_str())
+            .call(&operations::GET, &[&device_name.into()], &(), ())
             .await
     }
 
@@ -35,10 +36,11 @@ impl DeviceStats<'_> {
         state: State,
     ) -> crate::errors::Result<()> {
         self.client
-            .put_with_query(
-                format!("/deviceStates/{}", device_name.into()).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::UPDATE,
+                &[&device_name.into()],
                 &[("deviceState", state)],
+                (),
             )
             .await
     }
@@ -48,7 +50,7 @@ impl DeviceStats<'_> {
         device_name: impl Into<String> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/deviceStates/{}", device_name.into()).as_str())
+            .call(&operations::DELETE, &[&device_name.into()], &(), ())
             .await
     }
 }
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// DeviceState : Represents the state of a device.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DeviceState {
     /// Name of the device.
     #[serde(rename = "name")]
@@ -11,7 +11,7 @@ pub struct DeviceState {
     pub state: DeviceStateState,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum DeviceStateState {
     #[serde(rename = "UNKNOWN")]
     #[default]
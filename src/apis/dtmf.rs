@@ -0,0 +1,240 @@
+//! DTMF digits, as received from and sent to channels.
+//!
+//! ```
+//! use asterisk_ari::apis::channels::params::DtmfRequest;
+//! use asterisk_ari::apis::dtmf::{DtmfDigit, DtmfSequence};
+//!
+//! # fn example() -> asterisk_ari::Result<()> {
+//! // Dial an extension, wait a second, then enter a PIN.
+//! let dtmf = "1234".parse::<DtmfSequence>()?.long_pause().digits("0000#")?;
+//! let request = DtmfRequest::new("1736284349.1", dtmf);
+//!
+//! let star = DtmfDigit::try_from('*')?;
+//! let request = DtmfRequest::new("1736284349.1", star);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::errors::{AriError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// A DTMF digit: `0`-`9`, `*`, `#` or `A`-`E`, as declared by ARI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DtmfDigit(char);
+
+impl DtmfDigit {
+    /// The digit, as a character. Letters are uppercase.
+    pub fn as_char(&self) -> char {
+        self.0
+    }
+}
+
+/// The digit `0`.
+impl Default for DtmfDigit {
+    fn default() -> Self {
+        DtmfDigit('0')
+    }
+}
+
+impl TryFrom<char> for DtmfDigit {
+    type Error = AriError;
+
+    /// Validates a digit; lowercase letters are accepted.
+    fn try_from(digit: char) -> Result<Self> {
+        match digit.to_ascii_uppercase() {
+            digit @ ('0'..='9' | '*' | '#' | 'A'..='E') => Ok(DtmfDigit(digit)),
+            _ => Err(AriError::InvalidDtmf(digit.to_string())),
+        }
+    }
+}
+
+impl FromStr for DtmfDigit {
+    type Err = AriError;
+
+    fn from_str(digit: &str) -> Result<Self> {
+        let mut chars = digit.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => c.try_into(),
+            _ => Err(AriError::InvalidDtmf(digit.to_string())),
+        }
+    }
+}
+
+impl Display for DtmfDigit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<char> for DtmfDigit {
+    fn eq(&self, other: &char) -> bool {
+        self.0 == *other
+    }
+}
+
+impl From<DtmfDigit> for char {
+    fn from(digit: DtmfDigit) -> Self {
+        digit.0
+    }
+}
+
+impl Serialize for DtmfDigit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DtmfDigit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// DTMF digits to send to a channel, with optional pauses.
+///
+/// Parses from a string of digits where `w` pauses half a second and `W` a second, as
+/// Asterisk does.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DtmfSequence(String);
+
+impl DtmfSequence {
+    /// An empty sequence.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a digit.
+    pub fn digit(mut self, digit: DtmfDigit) -> Self {
+        self.0.push(digit.0);
+        self
+    }
+
+    /// Appends digits, failing on any character which is not a digit.
+    pub fn digits(mut self, digits: &str) -> Result<Self> {
+        for digit in digits.chars() {
+            self = self.digit(digit.try_into()?);
+        }
+        Ok(self)
+    }
+
+    /// Appends a pause of half a second.
+    pub fn pause(mut self) -> Self {
+        self.0.push('w');
+        self
+    }
+
+    /// Appends a pause of a second.
+    pub fn long_pause(mut self) -> Self {
+        self.0.push('W');
+        self
+    }
+
+    /// The sequence, as sent to Asterisk.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the sequence is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for DtmfSequence {
+    type Err = AriError;
+
+    fn from_str(sequence: &str) -> Result<Self> {
+        let mut parsed = DtmfSequence::new();
+        for c in sequence.chars() {
+            parsed = match c {
+                'w' => parsed.pause(),
+                'W' => parsed.long_pause(),
+                c => parsed.digit(
+                    DtmfDigit::try_from(c)
+                        .map_err(|_| AriError::InvalidDtmf(format!("{c:?} in {sequence:?}")))?,
+                ),
+            };
+        }
+        Ok(parsed)
+    }
+}
+
+impl Display for DtmfSequence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<DtmfDigit> for DtmfSequence {
+    fn from(digit: DtmfDigit) -> Self {
+        DtmfSequence::new().digit(digit)
+    }
+}
+
+impl From<Vec<DtmfDigit>> for DtmfSequence {
+    fn from(digits: Vec<DtmfDigit>) -> Self {
+        digits
+            .into_iter()
+            .fold(DtmfSequence::new(), DtmfSequence::digit)
+    }
+}
+
+impl<const N: usize> From<[DtmfDigit; N]> for DtmfSequence {
+    fn from(digits: [DtmfDigit; N]) -> Self {
+        digits
+            .into_iter()
+            .fold(DtmfSequence::new(), DtmfSequence::digit)
+    }
+}
+
+impl Serialize for DtmfSequence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(DtmfDigit::try_from('b').unwrap(), 'B');
+        assert_eq!("#".parse::<DtmfDigit>().unwrap().as_char(), '#');
+        assert_eq!(DtmfDigit::try_from('e').unwrap(), 'E');
+        assert!(DtmfDigit::try_from('F').is_err());
+        assert!("12".parse::<DtmfDigit>().is_err());
+
+        let digit: DtmfDigit = serde_json::from_str(r#""7""#).unwrap();
+        assert_eq!(serde_json::to_string(&digit).unwrap(), r#""7""#);
+    }
+
+    #[test]
+    fn test_sequences() {
+        let sequence: DtmfSequence = "12w*W#".parse().unwrap();
+        assert_eq!(sequence.as_str(), "12w*W#");
+        assert_eq!(
+            DtmfSequence::new()
+                .digits("12")
+                .unwrap()
+                .pause()
+                .digit('*'.try_into().unwrap())
+                .long_pause()
+                .digits("#")
+                .unwrap(),
+            sequence
+        );
+        assert!(matches!(
+            "12,3".parse::<DtmfSequence>(),
+            Err(AriError::InvalidDtmf(_))
+        ));
+        assert_eq!(
+            serde_urlencoded::to_string([("dtmf", &sequence)]).unwrap(),
+            "dtmf=12w*W%23"
+        );
+    }
+}
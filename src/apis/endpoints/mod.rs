@@ -2,6 +2,9 @@ pub mod models;
 pub mod params;
 
 use crate::apis::client::Client;
+use crate::apis::generated::endpoints as operations;
+use crate::apis::ids::EndpointRef;
+use crate::apis::operation::Body;
 use std::fmt::Display;
 
 pub struct Endpoints<'c> {
@@ -17,7 +20,7 @@ impl<'c> Endpoints<'c> {
 impl Endpoints<'_> {
     /// List all endpoints.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Endpoint>> {
-        self.client.get("/endpoints").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// List available endpoints for a given endpoint technology.
@@ -25,17 +28,24 @@ impl Endpoints<'_> {
         &self,
         tech: impl Into<String> + Display + Send,
     ) -> crate::errors::Result<Vec<models::Endpoint>> {
-        self.client.get(format!("/endpoints/{tech}").as_str()).await
+        self.client
+            .call(&operations::LIST_BY_TECH, &[&tech.into()], &(), ())
+            .await
     }
 
     /// Details for an endpoint.
     pub async fn get(
         &self,
-        tech: impl Into<String> + Send,
-        resource: impl Into<String> + Send,
+        endpoint: impl Into<EndpointRef> + Send,
     ) -> crate::errors::Result<models::Endpoint> {
+        let endpoint = endpoint.into();
         self.client
-            .get(format!("/endpoints/{}/{}", tech.into(), resource.into()).as_str())
+            .call(
+                &operations::GET,
+                &[&endpoint.technology, &endpoint.resource],
+                &(),
+                (),
+            )
             .await
     }
 
@@ -45,7 +55,12 @@ impl Endpoints<'_> {
         request: params::SendMessageRequest,
     ) -> crate::errors::Result<()> {
         self.client
-            .put_with_query("/endpoints/sendMessage", &request.variables, &request)
+            .call(
+                &operations::SEND_MESSAGE,
+                &[],
+                &request,
+                Body::new().field("variables", &request.variables)?,
+            )
             .await
     }
 
@@ -55,14 +70,11 @@ impl Endpoints<'_> {
         request: params::SendMessageToEndpointRequest,
     ) -> crate::errors::Result<()> {
         self.client
-            .put_with_query(
-                format!(
-                    "/endpoints/{}/{}/sendMessage",
-                    request.tech, request.resource
-                )
-                .as_str(),
-                &request.variables,
+            .call(
+                &operations::SEND_MESSAGE_TO_ENDPOINT,
+                &[&request.endpoint.technology, &request.endpoint.resource],
                 &request,
+                Body::new().field("variables", &request.variables)?,
             )
             .await
     }
@@ -70,7 +82,12 @@ impl Endpoints<'_> {
     /// Refer an endpoint or technology URI to some technology URI or endpoint
     pub async fn refer(&self, request: params::ReferRequest) -> crate::errors::Result<()> {
         self.client
-            .put_with_query("/endpoints/refer", &request.variables, &request)
+            .call(
+                &operations::REFER,
+                &[],
+                &request,
+                Body::new().field("variables", &request.variables)?,
+            )
             .await
     }
 
@@ -80,10 +97,11 @@ impl Endpoints<'_> {
         request: params::ReferToEndpointRequest,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/endpoints/{}/{}/refer", request.tech, request.resource).as_str(),
-                &request.variables,
+            .call(
+                &operations::REFER_TO_ENDPOINT,
+                &[&request.endpoint.technology, &request.endpoint.resource],
                 &request,
+                Body::new().field("variables", &request.variables)?,
             )
             .await
     }
@@ -1,22 +1,31 @@
-use serde::Deserialize;
+use crate::apis::ids::{ChannelId, EndpointRef};
+use serde::{Deserialize, Serialize};
 
 /// Endpoint : An external device that may offer/accept calls to/from Asterisk.
 ///
 /// Unlike most resources, which have a single unique identifier, an endpoint is uniquely identified by the technology/resource pair.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Endpoint {
     /// Technology of the endpoint
     pub technology: String,
     /// Identifier of the endpoint, specific to the given technology.
     pub resource: String,
     /// Endpoint's state
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<State>,
     /// Id's of channels associated with this endpoint
-    pub channel_ids: Vec<String>,
+    pub channel_ids: Vec<ChannelId>,
 }
 
 ///  ['unknown' or 'offline' or 'online']
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+impl Endpoint {
+    /// Reference to the endpoint, e.g. `PJSIP/1000`.
+    pub fn reference(&self) -> EndpointRef {
+        EndpointRef::new(&self.technology, &self.resource)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum State {
     #[serde(rename = "unknown")]
     #[default]
@@ -28,14 +37,18 @@ pub enum State {
 }
 
 /// TextMessage : A text message.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TextMessage {
     /// A technology specific URI specifying the source of the message. For pjsip technology, any SIP URI can be specified. For xmpp, the URI must correspond to the client connection being used to send the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub from: Option<String>,
     /// A technology specific URI specifying the destination of the message. Valid technologies include pjsip, and xmp. The destination of a message should be an endpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to: Option<String>,
     /// The text of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
     /// Technology specific key/value pairs (JSON object) associated with the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub variables: Option<serde_json::Value>,
 }
@@ -1,3 +1,4 @@
+use crate::apis::ids::EndpointRef;
 use derive_new::new;
 use derive_setters::Setters;
 use serde::Serialize;
@@ -71,17 +72,11 @@ pub struct SendMessageRequest {
 #[setters(prefix = "with_")]
 #[setters(into, strip_option)]
 pub struct SendMessageToEndpointRequest {
-    /// Technology of the endpoint
+    /// Endpoint to send the message to.
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) tech: String,
-
-    /// ID of the endpoint
-    #[serde(skip_serializing)]
-    #[setters(skip)]
-    #[new(into)]
-    pub(crate) resource: String,
+    pub(crate) endpoint: EndpointRef,
 
     /// The endpoint resource or technology specific identity to send this message from. Valid resources are pjsip, and xmpp.
     #[setters(skip)]
@@ -108,17 +103,11 @@ pub struct SendMessageToEndpointRequest {
 #[setters(prefix = "with_")]
 #[setters(into, strip_option)]
 pub struct ReferToEndpointRequest {
-    /// Technology of the endpoint
-    #[serde(skip_serializing)]
-    #[setters(skip)]
-    #[new(into)]
-    pub(crate) tech: String,
-
-    /// ID of the endpoint
+    /// Endpoint to refer.
     #[serde(skip_serializing)]
     #[setters(skip)]
     #[new(into)]
-    pub(crate) resource: String,
+    pub(crate) endpoint: EndpointRef,
 
     /// The endpoint resource or technology specific identity to send this message from.
     ///
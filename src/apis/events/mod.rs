@@ -2,6 +2,8 @@ pub mod models;
 pub mod params;
 
 use crate::apis::client::Client;
+use crate::apis::generated::events as operations;
+use crate::apis::operation::Body;
 
 pub struct Events<'c> {
     client: &'c Client,
@@ -17,10 +19,11 @@ impl Events<'_> {
     /// Generate a user event.
     pub async fn generate(&self, request: params::GenerateRequest) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/events/user/{}", request.event_name).as_str(),
-                &request.variables,
+            .call(
+                &operations::USER_EVENT,
+                &[&request.event_name],
                 &request,
+                Body::new().field("variables", &request.variables)?,
             )
             .await
     }
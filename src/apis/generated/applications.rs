@@ -0,0 +1,54 @@
+//! Operations of `applications.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /applications`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/applications",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /applications/{applicationName}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/applications/{applicationName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /applications/{applicationName}/subscription`
+pub const SUBSCRIBE: Operation = Operation {
+    nickname: "subscribe",
+    method: Method::POST,
+    path: "/applications/{applicationName}/subscription",
+    query: &["eventSource"],
+    required: &["eventSource"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /applications/{applicationName}/subscription`
+pub const UNSUBSCRIBE: Operation = Operation {
+    nickname: "unsubscribe",
+    method: Method::DELETE,
+    path: "/applications/{applicationName}/subscription",
+    query: &["eventSource"],
+    required: &["eventSource"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /applications/{applicationName}/eventFilter`
+pub const FILTER: Operation = Operation {
+    nickname: "filter",
+    method: Method::PUT,
+    path: "/applications/{applicationName}/eventFilter",
+    query: &[],
+    required: &[],
+    body: BodyParams::Whole("filter"),
+};
@@ -0,0 +1,164 @@
+//! Operations of `asterisk.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /asterisk/config/dynamic/{configClass}/{objectType}/{id}`
+pub const GET_OBJECT: Operation = Operation {
+    nickname: "getObject",
+    method: Method::GET,
+    path: "/asterisk/config/dynamic/{configClass}/{objectType}/{id}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /asterisk/config/dynamic/{configClass}/{objectType}/{id}`
+pub const UPDATE_OBJECT: Operation = Operation {
+    nickname: "updateObject",
+    method: Method::PUT,
+    path: "/asterisk/config/dynamic/{configClass}/{objectType}/{id}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&["fields"]),
+};
+
+/// `DELETE /asterisk/config/dynamic/{configClass}/{objectType}/{id}`
+pub const DELETE_OBJECT: Operation = Operation {
+    nickname: "deleteObject",
+    method: Method::DELETE,
+    path: "/asterisk/config/dynamic/{configClass}/{objectType}/{id}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/info`
+pub const GET_INFO: Operation = Operation {
+    nickname: "getInfo",
+    method: Method::GET,
+    path: "/asterisk/info",
+    query: &["only"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/ping`
+pub const PING: Operation = Operation {
+    nickname: "ping",
+    method: Method::GET,
+    path: "/asterisk/ping",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/modules`
+pub const LIST_MODULES: Operation = Operation {
+    nickname: "listModules",
+    method: Method::GET,
+    path: "/asterisk/modules",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/modules/{moduleName}`
+pub const GET_MODULE: Operation = Operation {
+    nickname: "getModule",
+    method: Method::GET,
+    path: "/asterisk/modules/{moduleName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /asterisk/modules/{moduleName}`
+pub const LOAD_MODULE: Operation = Operation {
+    nickname: "loadModule",
+    method: Method::POST,
+    path: "/asterisk/modules/{moduleName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /asterisk/modules/{moduleName}`
+pub const UNLOAD_MODULE: Operation = Operation {
+    nickname: "unloadModule",
+    method: Method::DELETE,
+    path: "/asterisk/modules/{moduleName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /asterisk/modules/{moduleName}`
+pub const RELOAD_MODULE: Operation = Operation {
+    nickname: "reloadModule",
+    method: Method::PUT,
+    path: "/asterisk/modules/{moduleName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/logging`
+pub const LIST_LOG_CHANNELS: Operation = Operation {
+    nickname: "listLogChannels",
+    method: Method::GET,
+    path: "/asterisk/logging",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /asterisk/logging/{logChannelName}`
+pub const ADD_LOG: Operation = Operation {
+    nickname: "addLog",
+    method: Method::POST,
+    path: "/asterisk/logging/{logChannelName}",
+    query: &["configuration"],
+    required: &["configuration"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /asterisk/logging/{logChannelName}`
+pub const DELETE_LOG: Operation = Operation {
+    nickname: "deleteLog",
+    method: Method::DELETE,
+    path: "/asterisk/logging/{logChannelName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /asterisk/logging/{logChannelName}/rotate`
+pub const ROTATE_LOG: Operation = Operation {
+    nickname: "rotateLog",
+    method: Method::PUT,
+    path: "/asterisk/logging/{logChannelName}/rotate",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /asterisk/variable`
+pub const GET_GLOBAL_VAR: Operation = Operation {
+    nickname: "getGlobalVar",
+    method: Method::GET,
+    path: "/asterisk/variable",
+    query: &["variable"],
+    required: &["variable"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /asterisk/variable`
+pub const SET_GLOBAL_VAR: Operation = Operation {
+    nickname: "setGlobalVar",
+    method: Method::POST,
+    path: "/asterisk/variable",
+    query: &["variable", "value"],
+    required: &["variable"],
+    body: BodyParams::Keys(&[]),
+};
@@ -0,0 +1,144 @@
+//! Operations of `bridges.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /bridges`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/bridges",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges`
+pub const CREATE: Operation = Operation {
+    nickname: "create",
+    method: Method::POST,
+    path: "/bridges",
+    query: &["type", "bridgeId", "name"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}`
+pub const CREATE_WITH_ID: Operation = Operation {
+    nickname: "createWithId",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}",
+    query: &["type", "name"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /bridges/{bridgeId}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/bridges/{bridgeId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /bridges/{bridgeId}`
+pub const DESTROY: Operation = Operation {
+    nickname: "destroy",
+    method: Method::DELETE,
+    path: "/bridges/{bridgeId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/addChannel`
+pub const ADD_CHANNEL: Operation = Operation {
+    nickname: "addChannel",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/addChannel",
+    query: &["channel", "role", "absorbDTMF", "mute", "inhibitConnectedLineUpdates"],
+    required: &["channel"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/removeChannel`
+pub const REMOVE_CHANNEL: Operation = Operation {
+    nickname: "removeChannel",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/removeChannel",
+    query: &["channel"],
+    required: &["channel"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/videoSource/{channelId}`
+pub const SET_VIDEO_SOURCE: Operation = Operation {
+    nickname: "setVideoSource",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/videoSource/{channelId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /bridges/{bridgeId}/videoSource`
+pub const CLEAR_VIDEO_SOURCE: Operation = Operation {
+    nickname: "clearVideoSource",
+    method: Method::DELETE,
+    path: "/bridges/{bridgeId}/videoSource",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/moh`
+pub const START_MOH: Operation = Operation {
+    nickname: "startMoh",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/moh",
+    query: &["mohClass"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /bridges/{bridgeId}/moh`
+pub const STOP_MOH: Operation = Operation {
+    nickname: "stopMoh",
+    method: Method::DELETE,
+    path: "/bridges/{bridgeId}/moh",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/play`
+pub const PLAY: Operation = Operation {
+    nickname: "play",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/play",
+    query: &["media", "lang", "offsetms", "skipms", "playbackId"],
+    required: &["media"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/play/{playbackId}`
+pub const PLAY_WITH_ID: Operation = Operation {
+    nickname: "playWithId",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/play/{playbackId}",
+    query: &["media", "lang", "offsetms", "skipms"],
+    required: &["media"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /bridges/{bridgeId}/record`
+pub const RECORD: Operation = Operation {
+    nickname: "record",
+    method: Method::POST,
+    path: "/bridges/{bridgeId}/record",
+    query: &["name", "format", "maxDurationSeconds", "maxSilenceSeconds", "ifExists", "beep", "terminateOn"],
+    required: &["name", "format"],
+    body: BodyParams::Keys(&[]),
+};
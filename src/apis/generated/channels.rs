@@ -0,0 +1,314 @@
+//! Operations of `channels.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /channels`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/channels",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels`
+pub const ORIGINATE: Operation = Operation {
+    nickname: "originate",
+    method: Method::POST,
+    path: "/channels",
+    query: &["endpoint", "extension", "context", "priority", "label", "app", "appArgs", "callerId", "timeout", "channelId", "otherChannelId", "originator", "formats"],
+    required: &["endpoint"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `POST /channels/create`
+pub const CREATE: Operation = Operation {
+    nickname: "create",
+    method: Method::POST,
+    path: "/channels/create",
+    query: &["endpoint", "app", "appArgs", "channelId", "otherChannelId", "originator", "formats"],
+    required: &["endpoint", "app"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `GET /channels/{channelId}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/channels/{channelId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}`
+pub const ORIGINATE_WITH_ID: Operation = Operation {
+    nickname: "originateWithId",
+    method: Method::POST,
+    path: "/channels/{channelId}",
+    query: &["endpoint", "extension", "context", "priority", "label", "app", "appArgs", "callerId", "timeout", "otherChannelId", "originator", "formats"],
+    required: &["endpoint"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `DELETE /channels/{channelId}`
+pub const HANGUP: Operation = Operation {
+    nickname: "hangup",
+    method: Method::DELETE,
+    path: "/channels/{channelId}",
+    query: &["reason_code", "reason"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/continue`
+pub const CONTINUE_IN_DIALPLAN: Operation = Operation {
+    nickname: "continueInDialplan",
+    method: Method::POST,
+    path: "/channels/{channelId}/continue",
+    query: &["context", "extension", "priority", "label"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/move`
+pub const MOVE: Operation = Operation {
+    nickname: "move",
+    method: Method::POST,
+    path: "/channels/{channelId}/move",
+    query: &["app", "appArgs"],
+    required: &["app"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/redirect`
+pub const REDIRECT: Operation = Operation {
+    nickname: "redirect",
+    method: Method::POST,
+    path: "/channels/{channelId}/redirect",
+    query: &["endpoint"],
+    required: &["endpoint"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/answer`
+pub const ANSWER: Operation = Operation {
+    nickname: "answer",
+    method: Method::POST,
+    path: "/channels/{channelId}/answer",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/ring`
+pub const RING: Operation = Operation {
+    nickname: "ring",
+    method: Method::POST,
+    path: "/channels/{channelId}/ring",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /channels/{channelId}/ring`
+pub const RING_STOP: Operation = Operation {
+    nickname: "ringStop",
+    method: Method::DELETE,
+    path: "/channels/{channelId}/ring",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/dtmf`
+pub const SEND_DTMF: Operation = Operation {
+    nickname: "sendDTMF",
+    method: Method::POST,
+    path: "/channels/{channelId}/dtmf",
+    query: &["dtmf", "before", "between", "duration", "after"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/mute`
+pub const MUTE: Operation = Operation {
+    nickname: "mute",
+    method: Method::POST,
+    path: "/channels/{channelId}/mute",
+    query: &["direction"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /channels/{channelId}/mute`
+pub const UNMUTE: Operation = Operation {
+    nickname: "unmute",
+    method: Method::DELETE,
+    path: "/channels/{channelId}/mute",
+    query: &["direction"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/hold`
+pub const HOLD: Operation = Operation {
+    nickname: "hold",
+    method: Method::POST,
+    path: "/channels/{channelId}/hold",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /channels/{channelId}/hold`
+pub const UNHOLD: Operation = Operation {
+    nickname: "unhold",
+    method: Method::DELETE,
+    path: "/channels/{channelId}/hold",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/moh`
+pub const START_MOH: Operation = Operation {
+    nickname: "startMoh",
+    method: Method::POST,
+    path: "/channels/{channelId}/moh",
+    query: &["mohClass"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /channels/{channelId}/moh`
+pub const STOP_MOH: Operation = Operation {
+    nickname: "stopMoh",
+    method: Method::DELETE,
+    path: "/channels/{channelId}/moh",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/silence`
+pub const START_SILENCE: Operation = Operation {
+    nickname: "startSilence",
+    method: Method::POST,
+    path: "/channels/{channelId}/silence",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /channels/{channelId}/silence`
+pub const STOP_SILENCE: Operation = Operation {
+    nickname: "stopSilence",
+    method: Method::DELETE,
+    path: "/channels/{channelId}/silence",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/play`
+pub const PLAY: Operation = Operation {
+    nickname: "play",
+    method: Method::POST,
+    path: "/channels/{channelId}/play",
+    query: &["media", "lang", "offsetms", "skipms", "playbackId"],
+    required: &["media"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/play/{playbackId}`
+pub const PLAY_WITH_ID: Operation = Operation {
+    nickname: "playWithId",
+    method: Method::POST,
+    path: "/channels/{channelId}/play/{playbackId}",
+    query: &["media", "lang", "offsetms", "skipms"],
+    required: &["media"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/record`
+pub const RECORD: Operation = Operation {
+    nickname: "record",
+    method: Method::POST,
+    path: "/channels/{channelId}/record",
+    query: &["name", "format", "maxDurationSeconds", "maxSilenceSeconds", "ifExists", "beep", "terminateOn"],
+    required: &["name", "format"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /channels/{channelId}/variable`
+pub const GET_CHANNEL_VAR: Operation = Operation {
+    nickname: "getChannelVar",
+    method: Method::GET,
+    path: "/channels/{channelId}/variable",
+    query: &["variable"],
+    required: &["variable"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/variable`
+pub const SET_CHANNEL_VAR: Operation = Operation {
+    nickname: "setChannelVar",
+    method: Method::POST,
+    path: "/channels/{channelId}/variable",
+    query: &["variable", "value"],
+    required: &["variable"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/snoop`
+pub const SNOOP_CHANNEL: Operation = Operation {
+    nickname: "snoopChannel",
+    method: Method::POST,
+    path: "/channels/{channelId}/snoop",
+    query: &["spy", "whisper", "app", "appArgs", "snoopId"],
+    required: &["app"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/snoop/{snoopId}`
+pub const SNOOP_CHANNEL_WITH_ID: Operation = Operation {
+    nickname: "snoopChannelWithId",
+    method: Method::POST,
+    path: "/channels/{channelId}/snoop/{snoopId}",
+    query: &["spy", "whisper", "app", "appArgs"],
+    required: &["app"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/{channelId}/dial`
+pub const DIAL: Operation = Operation {
+    nickname: "dial",
+    method: Method::POST,
+    path: "/channels/{channelId}/dial",
+    query: &["caller", "timeout"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /channels/{channelId}/rtp_statistics`
+pub const RTPSTATISTICS: Operation = Operation {
+    nickname: "rtpstatistics",
+    method: Method::GET,
+    path: "/channels/{channelId}/rtp_statistics",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /channels/externalMedia`
+pub const EXTERNAL_MEDIA: Operation = Operation {
+    nickname: "externalMedia",
+    method: Method::POST,
+    path: "/channels/externalMedia",
+    query: &["channelId", "app", "external_host", "encapsulation", "transport", "connection_type", "format", "direction", "data"],
+    required: &["app", "external_host", "format"],
+    body: BodyParams::Keys(&["variables"]),
+};
@@ -0,0 +1,44 @@
+//! Operations of `deviceStates.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /deviceStates`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/deviceStates",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /deviceStates/{deviceName}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/deviceStates/{deviceName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /deviceStates/{deviceName}`
+pub const UPDATE: Operation = Operation {
+    nickname: "update",
+    method: Method::PUT,
+    path: "/deviceStates/{deviceName}",
+    query: &["deviceState"],
+    required: &["deviceState"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /deviceStates/{deviceName}`
+pub const DELETE: Operation = Operation {
+    nickname: "delete",
+    method: Method::DELETE,
+    path: "/deviceStates/{deviceName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
@@ -0,0 +1,74 @@
+//! Operations of `endpoints.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /endpoints`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/endpoints",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /endpoints/sendMessage`
+pub const SEND_MESSAGE: Operation = Operation {
+    nickname: "sendMessage",
+    method: Method::PUT,
+    path: "/endpoints/sendMessage",
+    query: &["to", "from", "body"],
+    required: &["to", "from"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `POST /endpoints/refer`
+pub const REFER: Operation = Operation {
+    nickname: "refer",
+    method: Method::POST,
+    path: "/endpoints/refer",
+    query: &["to", "from", "refer_to", "to_self"],
+    required: &["to", "from", "refer_to"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `GET /endpoints/{tech}`
+pub const LIST_BY_TECH: Operation = Operation {
+    nickname: "listByTech",
+    method: Method::GET,
+    path: "/endpoints/{tech}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /endpoints/{tech}/{resource}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/endpoints/{tech}/{resource}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /endpoints/{tech}/{resource}/sendMessage`
+pub const SEND_MESSAGE_TO_ENDPOINT: Operation = Operation {
+    nickname: "sendMessageToEndpoint",
+    method: Method::PUT,
+    path: "/endpoints/{tech}/{resource}/sendMessage",
+    query: &["from", "body"],
+    required: &["from"],
+    body: BodyParams::Keys(&["variables"]),
+};
+
+/// `POST /endpoints/{tech}/{resource}/refer`
+pub const REFER_TO_ENDPOINT: Operation = Operation {
+    nickname: "referToEndpoint",
+    method: Method::POST,
+    path: "/endpoints/{tech}/{resource}/refer",
+    query: &["from", "refer_to", "to_self"],
+    required: &["from", "refer_to"],
+    body: BodyParams::Keys(&["variables"]),
+};
@@ -0,0 +1,24 @@
+//! Operations of `events.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /events`
+pub const EVENT_WEBSOCKET: Operation = Operation {
+    nickname: "eventWebsocket",
+    method: Method::GET,
+    path: "/events",
+    query: &["app", "subscribeAll"],
+    required: &["app"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /events/user/{eventName}`
+pub const USER_EVENT: Operation = Operation {
+    nickname: "userEvent",
+    method: Method::POST,
+    path: "/events/user/{eventName}",
+    query: &["application", "source"],
+    required: &["application"],
+    body: BodyParams::Keys(&["variables"]),
+};
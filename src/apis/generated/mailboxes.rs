@@ -0,0 +1,44 @@
+//! Operations of `mailboxes.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /mailboxes`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/mailboxes",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /mailboxes/{mailboxName}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/mailboxes/{mailboxName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `PUT /mailboxes/{mailboxName}`
+pub const UPDATE: Operation = Operation {
+    nickname: "update",
+    method: Method::PUT,
+    path: "/mailboxes/{mailboxName}",
+    query: &["oldMessages", "newMessages"],
+    required: &["oldMessages", "newMessages"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /mailboxes/{mailboxName}`
+pub const DELETE: Operation = Operation {
+    nickname: "delete",
+    method: Method::DELETE,
+    path: "/mailboxes/{mailboxName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
@@ -0,0 +1,17 @@
+//! Bindings of ARI, generated by `cargo xtask codegen` from the definitions in
+//! `rest-api/api-docs`; do not edit.
+//!
+//! Each module of a resource declares its operations, which the APIs of the client send
+//! their requests through, e.g. [`channels::ORIGINATE`].
+
+pub mod applications;
+pub mod asterisk;
+pub mod bridges;
+pub mod channels;
+pub mod device_states;
+pub mod endpoints;
+pub mod events;
+pub mod mailboxes;
+pub mod playbacks;
+pub mod recordings;
+pub mod sounds;
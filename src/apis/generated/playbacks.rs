@@ -0,0 +1,34 @@
+//! Operations of `playbacks.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /playbacks/{playbackId}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/playbacks/{playbackId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /playbacks/{playbackId}`
+pub const STOP: Operation = Operation {
+    nickname: "stop",
+    method: Method::DELETE,
+    path: "/playbacks/{playbackId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /playbacks/{playbackId}/control`
+pub const CONTROL: Operation = Operation {
+    nickname: "control",
+    method: Method::POST,
+    path: "/playbacks/{playbackId}/control",
+    query: &["operation"],
+    required: &["operation"],
+    body: BodyParams::Keys(&[]),
+};
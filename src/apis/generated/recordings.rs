@@ -0,0 +1,124 @@
+//! Operations of `recordings.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /recordings/stored`
+pub const LIST_STORED: Operation = Operation {
+    nickname: "listStored",
+    method: Method::GET,
+    path: "/recordings/stored",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /recordings/stored/{recordingName}`
+pub const GET_STORED: Operation = Operation {
+    nickname: "getStored",
+    method: Method::GET,
+    path: "/recordings/stored/{recordingName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /recordings/stored/{recordingName}`
+pub const DELETE_STORED: Operation = Operation {
+    nickname: "deleteStored",
+    method: Method::DELETE,
+    path: "/recordings/stored/{recordingName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /recordings/stored/{recordingName}/file`
+pub const GET_STORED_FILE: Operation = Operation {
+    nickname: "getStoredFile",
+    method: Method::GET,
+    path: "/recordings/stored/{recordingName}/file",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /recordings/stored/{recordingName}/copy`
+pub const COPY_STORED: Operation = Operation {
+    nickname: "copyStored",
+    method: Method::POST,
+    path: "/recordings/stored/{recordingName}/copy",
+    query: &["destinationRecordingName"],
+    required: &["destinationRecordingName"],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /recordings/live/{recordingName}`
+pub const GET_LIVE: Operation = Operation {
+    nickname: "getLive",
+    method: Method::GET,
+    path: "/recordings/live/{recordingName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /recordings/live/{recordingName}`
+pub const CANCEL: Operation = Operation {
+    nickname: "cancel",
+    method: Method::DELETE,
+    path: "/recordings/live/{recordingName}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /recordings/live/{recordingName}/stop`
+pub const STOP: Operation = Operation {
+    nickname: "stop",
+    method: Method::POST,
+    path: "/recordings/live/{recordingName}/stop",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /recordings/live/{recordingName}/pause`
+pub const PAUSE: Operation = Operation {
+    nickname: "pause",
+    method: Method::POST,
+    path: "/recordings/live/{recordingName}/pause",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /recordings/live/{recordingName}/pause`
+pub const UNPAUSE: Operation = Operation {
+    nickname: "unpause",
+    method: Method::DELETE,
+    path: "/recordings/live/{recordingName}/pause",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `POST /recordings/live/{recordingName}/mute`
+pub const MUTE: Operation = Operation {
+    nickname: "mute",
+    method: Method::POST,
+    path: "/recordings/live/{recordingName}/mute",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `DELETE /recordings/live/{recordingName}/mute`
+pub const UNMUTE: Operation = Operation {
+    nickname: "unmute",
+    method: Method::DELETE,
+    path: "/recordings/live/{recordingName}/mute",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
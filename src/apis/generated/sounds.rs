@@ -0,0 +1,24 @@
+//! Operations of `sounds.json`, generated by `cargo xtask codegen`; do not edit.
+
+use crate::apis::operation::{BodyParams, Operation};
+use crate::apis::transport::Method;
+
+/// `GET /sounds`
+pub const LIST: Operation = Operation {
+    nickname: "list",
+    method: Method::GET,
+    path: "/sounds",
+    query: &["lang", "format"],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
+
+/// `GET /sounds/{soundId}`
+pub const GET: Operation = Operation {
+    nickname: "get",
+    method: Method::GET,
+    path: "/sounds/{soundId}",
+    query: &[],
+    required: &[],
+    body: BodyParams::Keys(&[]),
+};
@@ -0,0 +1,242 @@
+//! Identifiers of the ARI resources.
+//!
+//! Each kind of resource has its own type, so that a bridge id cannot be passed where a
+//! channel id is expected. They convert from `&str` and `String`, and dereference to `str`.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+/// Declares a newtype over `String` identifying a resource.
+macro_rules! id_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Wraps an identifier.
+            pub fn new(id: impl Into<String>) -> Self {
+                $name(id.into())
+            }
+
+            /// The identifier, as a string.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Unwraps the identifier.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(id: String) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<&String> for $name {
+            fn from(id: &String) -> Self {
+                $name(id.clone())
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(id: &str) -> Self {
+                $name(id.to_string())
+            }
+        }
+
+        impl From<&$name> for $name {
+            fn from(id: &$name) -> Self {
+                id.clone()
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<String> for $name {
+            fn eq(&self, other: &String) -> bool {
+                &self.0 == other
+            }
+        }
+
+        impl PartialEq<$name> for str {
+            fn eq(&self, other: &$name) -> bool {
+                self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for &str {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl PartialEq<$name> for String {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+    };
+}
+
+id_type!(
+    /// Id of a channel, e.g. `1736284349.1`.
+    ChannelId
+);
+id_type!(
+    /// Id of a bridge.
+    BridgeId
+);
+id_type!(
+    /// Id of a playback.
+    PlaybackId
+);
+id_type!(
+    /// Name of a live or stored recording.
+    RecordingName
+);
+id_type!(
+    /// Name of a Stasis application.
+    AppName
+);
+
+/// An endpoint, by technology and resource, e.g. `PJSIP/1000`.
+///
+/// Converts from `"PJSIP/1000"` and from `("PJSIP", "1000")`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EndpointRef {
+    /// Technology of the endpoint, e.g. `PJSIP`.
+    pub technology: String,
+    /// Resource of the endpoint within the technology, e.g. `1000`.
+    pub resource: String,
+}
+
+impl EndpointRef {
+    /// Creates a reference to an endpoint.
+    pub fn new(technology: impl Into<String>, resource: impl Into<String>) -> Self {
+        EndpointRef {
+            technology: technology.into(),
+            resource: resource.into(),
+        }
+    }
+}
+
+impl Display for EndpointRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.technology, self.resource)
+    }
+}
+
+impl From<&str> for EndpointRef {
+    fn from(endpoint: &str) -> Self {
+        match endpoint.split_once('/') {
+            Some((technology, resource)) => EndpointRef::new(technology, resource),
+            None => EndpointRef::new(endpoint, ""),
+        }
+    }
+}
+
+impl From<String> for EndpointRef {
+    fn from(endpoint: String) -> Self {
+        endpoint.as_str().into()
+    }
+}
+
+impl From<&EndpointRef> for EndpointRef {
+    fn from(endpoint: &EndpointRef) -> Self {
+        endpoint.clone()
+    }
+}
+
+impl<T: Into<String>, R: Into<String>> From<(T, R)> for EndpointRef {
+    fn from((technology, resource): (T, R)) -> Self {
+        EndpointRef::new(technology, resource)
+    }
+}
+
+impl Serialize for EndpointRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for EndpointRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(String::deserialize(deserializer)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_serialize_as_strings() {
+        let id: ChannelId = serde_json::from_str(r#""1736284349.1""#).unwrap();
+        assert_eq!(id, "1736284349.1");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""1736284349.1""#);
+        assert_eq!(format!("/channels/{id}"), "/channels/1736284349.1");
+    }
+
+    #[test]
+    fn test_endpoint_refs() {
+        let endpoint = EndpointRef::from("PJSIP/1000");
+        assert_eq!(endpoint, EndpointRef::new("PJSIP", "1000"));
+        assert_eq!(endpoint, ("PJSIP", "1000").into());
+        assert_eq!(endpoint.to_string(), "PJSIP/1000");
+
+        let endpoints: Vec<EndpointRef> = serde_json::from_str(r#"["PJSIP/1000"]"#).unwrap();
+        assert_eq!(endpoints, vec![endpoint]);
+        assert_eq!(
+            serde_json::to_string(&endpoints).unwrap(),
+            r#"["PJSIP/1000"]"#
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::mailboxes as operations;
 
 pub struct Mailboxes<'c> {
     client: &'c Client,
@@ -15,7 +16,7 @@ impl<'c> Mailboxes<'c> {
 impl Mailboxes<'_> {
     /// List all mailboxes.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Mailbox>> {
-        self.client.get("/mailboxes").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// Retrieve the current state of a device
@@ -24,7 +25,7 @@ impl Mailboxes<'_> {
         mailbox_name: impl Into<String> + Send,
     ) -> crate::errors::Result<models::Mailbox> {
         self.client
-            .get(format!("/mailboxes/{}", mailbox_name.into()).as_str())
+            .call(&operations::GET, &[&mailbox_name.into()], &(), ())
             .await
     }
 
@@ -38,10 +39,11 @@ impl Mailboxes<'_> {
         new_messages: u32,
     ) -> crate::errors::Result<()> {
         self.client
-            .put_with_query(
-                format!("/mailboxes/{}", mailbox_name.into()).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::UPDATE,
+                &[&mailbox_name.into()],
                 &[("oldMessages", old_messages), ("newMessages", new_messages)],
+                (),
             )
             .await
     }
@@ -52,7 +54,7 @@ impl Mailboxes<'_> {
         mailbox_name: impl Into<String> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/mailboxes/{}", mailbox_name.into()).as_str())
+            .call(&operations::DELETE, &[&mailbox_name.into()], &(), ())
             .await
     }
 }
@@ -0,0 +1,288 @@
+//! Media URIs played on channels and bridges.
+//!
+//! ```
+//! use asterisk_ari::apis::channels::params::PlayRequest;
+//! use asterisk_ari::apis::media::{MediaUri, Tone};
+//!
+//! let request = PlayRequest::new(
+//!     "1736284349.1",
+//!     [
+//!         MediaUri::sound("queue-thereare"),
+//!         MediaUri::number(3),
+//!         MediaUri::sound("queue-callswaiting"),
+//!         Tone::new("ring").with_tonezone("fr").into(),
+//!     ],
+//! )
+//! .with_lang("fr");
+//! ```
+
+use crate::apis::ids::RecordingName;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+
+/// A media to play, as one of the URIs supported by ARI.
+///
+/// The language of the sounds is given to the play request, with `with_lang`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MediaUri {
+    /// A sound file, by its id (e.g. `hello-world` or `custom/welcome`).
+    Sound(String),
+    /// A stored recording.
+    Recording(RecordingName),
+    /// A number, said as a whole (e.g. "forty two").
+    Number(i64),
+    /// Digits, said one by one.
+    Digits(String),
+    /// Characters, spelled one by one.
+    Characters(String),
+    /// A tone.
+    Tone(Tone),
+    /// A URI of another scheme, sent as is.
+    Raw(String),
+}
+
+impl MediaUri {
+    /// A sound file.
+    pub fn sound(id: impl Into<String>) -> Self {
+        MediaUri::Sound(id.into())
+    }
+
+    /// A stored recording.
+    pub fn recording(name: impl Into<RecordingName>) -> Self {
+        MediaUri::Recording(name.into())
+    }
+
+    /// A number.
+    pub fn number(number: i64) -> Self {
+        MediaUri::Number(number)
+    }
+
+    /// Digits.
+    pub fn digits(digits: impl Into<String>) -> Self {
+        MediaUri::Digits(digits.into())
+    }
+
+    /// Characters.
+    pub fn characters(characters: impl Into<String>) -> Self {
+        MediaUri::Characters(characters.into())
+    }
+
+    /// A tone of the default tone zone.
+    pub fn tone(indication: impl Into<String>) -> Self {
+        MediaUri::Tone(Tone::new(indication))
+    }
+}
+
+impl Display for MediaUri {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MediaUri::Sound(id) => write!(f, "sound:{id}"),
+            MediaUri::Recording(name) => write!(f, "recording:{name}"),
+            MediaUri::Number(number) => write!(f, "number:{number}"),
+            MediaUri::Digits(digits) => write!(f, "digits:{digits}"),
+            MediaUri::Characters(characters) => write!(f, "characters:{characters}"),
+            MediaUri::Tone(tone) => write!(f, "{tone}"),
+            MediaUri::Raw(uri) => f.write_str(uri),
+        }
+    }
+}
+
+/// Parses a URI, e.g. `sound:hello-world`. URIs of unknown schemes, or which do not parse,
+/// are kept as [`MediaUri::Raw`].
+impl From<&str> for MediaUri {
+    fn from(uri: &str) -> Self {
+        let Some((scheme, value)) = uri.split_once(':') else {
+            return MediaUri::Raw(uri.to_string());
+        };
+        match scheme {
+            "sound" => MediaUri::sound(value),
+            "recording" => MediaUri::recording(value),
+            "number" => match value.parse() {
+                Ok(number) => MediaUri::Number(number),
+                Err(_) => MediaUri::Raw(uri.to_string()),
+            },
+            "digits" => MediaUri::digits(value),
+            "characters" => MediaUri::characters(value),
+            "tone" => {
+                let mut options = value.split(';');
+                let mut tone = Tone::new(options.next().unwrap_or_default());
+                for option in options {
+                    match option.split_once('=') {
+                        Some(("tonezone", zone)) => tone.tonezone = Some(zone.to_string()),
+                        _ => return MediaUri::Raw(uri.to_string()),
+                    }
+                }
+                MediaUri::Tone(tone)
+            }
+            _ => MediaUri::Raw(uri.to_string()),
+        }
+    }
+}
+
+impl From<String> for MediaUri {
+    fn from(uri: String) -> Self {
+        uri.as_str().into()
+    }
+}
+
+impl From<&String> for MediaUri {
+    fn from(uri: &String) -> Self {
+        uri.as_str().into()
+    }
+}
+
+impl From<Tone> for MediaUri {
+    fn from(tone: Tone) -> Self {
+        MediaUri::Tone(tone)
+    }
+}
+
+impl Serialize for MediaUri {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A tone, by the name of an indication (e.g. `ring`, `busy`, `congestion`) or a tone list
+/// (e.g. `!950/330,!1400/330`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tone {
+    /// Indication or tone list.
+    pub indication: String,
+    /// Country code of the tone zone the indication is taken from (e.g. `fr`), the channel's
+    /// by default.
+    pub tonezone: Option<String>,
+}
+
+impl Tone {
+    /// A tone of the default tone zone.
+    pub fn new(indication: impl Into<String>) -> Self {
+        Tone {
+            indication: indication.into(),
+            tonezone: None,
+        }
+    }
+
+    /// Takes the indication from another tone zone.
+    pub fn with_tonezone(mut self, tonezone: impl Into<String>) -> Self {
+        self.tonezone = Some(tonezone.into());
+        self
+    }
+}
+
+impl Display for Tone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tone:{}", self.indication)?;
+        if let Some(tonezone) = &self.tonezone {
+            write!(f, ";tonezone={tonezone}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The media of a play request, played one after the other.
+///
+/// Converts from a single media or a list of them. Asterisk separates the URIs by commas, so
+/// tone lists, which contain commas, can only be played by indication.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MediaList(pub Vec<MediaUri>);
+
+impl Display for MediaList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, media) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{media}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for MediaList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl From<MediaUri> for MediaList {
+    fn from(media: MediaUri) -> Self {
+        MediaList(vec![media])
+    }
+}
+
+impl From<Tone> for MediaList {
+    fn from(tone: Tone) -> Self {
+        MediaList(vec![tone.into()])
+    }
+}
+
+impl From<&str> for MediaList {
+    fn from(uri: &str) -> Self {
+        MediaList(vec![uri.into()])
+    }
+}
+
+impl From<String> for MediaList {
+    fn from(uri: String) -> Self {
+        MediaList(vec![uri.into()])
+    }
+}
+
+impl From<&String> for MediaList {
+    fn from(uri: &String) -> Self {
+        MediaList(vec![uri.into()])
+    }
+}
+
+impl<T: Into<MediaUri>> From<Vec<T>> for MediaList {
+    fn from(media: Vec<T>) -> Self {
+        MediaList(media.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<MediaUri>, const N: usize> From<[T; N]> for MediaList {
+    fn from(media: [T; N]) -> Self {
+        MediaList(media.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_media_uris() {
+        let uris = [
+            (MediaUri::sound("custom/welcome"), "sound:custom/welcome"),
+            (MediaUri::recording("greeting"), "recording:greeting"),
+            (MediaUri::number(-42), "number:-42"),
+            (MediaUri::digits("0123"), "digits:0123"),
+            (MediaUri::characters("abc"), "characters:abc"),
+            (MediaUri::tone("ring"), "tone:ring"),
+            (
+                Tone::new("busy").with_tonezone("it").into(),
+                "tone:busy;tonezone=it",
+            ),
+            (MediaUri::Raw("video:intro".into()), "video:intro"),
+        ];
+        for (media, uri) in uris {
+            assert_eq!(media.to_string(), uri);
+            assert_eq!(MediaUri::from(uri), media);
+        }
+    }
+
+    #[test]
+    fn test_media_lists() {
+        let media = MediaList::from([MediaUri::sound("vm-youhave"), MediaUri::number(2)]);
+        assert_eq!(media.to_string(), "sound:vm-youhave,number:2");
+        assert_eq!(
+            MediaList::from("sound:beep").0,
+            vec![MediaUri::sound("beep")]
+        );
+        assert_eq!(
+            serde_urlencoded::to_string([("media", &media)]).unwrap(),
+            "media=sound%3Avm-youhave%2Cnumber%3A2"
+        );
+    }
+}
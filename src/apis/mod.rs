@@ -1,17 +1,27 @@
+use chrono::{DateTime, Utc};
 use serde::Serializer;
 
 pub mod applications;
 pub mod asterisk;
 pub mod bridges;
+pub mod capabilities;
+pub mod cassette;
 pub mod channels;
 pub mod client;
 pub mod device_stats;
+pub mod dtmf;
 pub mod endpoints;
 pub mod events;
+#[rustfmt::skip]
+pub mod generated;
+pub mod ids;
 pub mod mailboxes;
+pub mod media;
+pub mod operation;
 pub mod playbacks;
 pub mod recordings;
 pub mod sounds;
+pub mod transport;
 
 pub mod params {
     use serde::{Deserialize, Serialize};
@@ -101,3 +111,11 @@ where
 {
     s.serialize_str(x.join(",").as_str())
 }
+
+/// Serializes a timestamp the way Asterisk formats them, e.g. `2020-11-22T20:12:51.214+0000`.
+pub(crate) fn serialize_timestamp<S>(timestamp: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    s.collect_str(&timestamp.format("%Y-%m-%dT%H:%M:%S%.3f%z"))
+}
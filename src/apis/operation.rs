@@ -0,0 +1,161 @@
+//! Operations of ARI, as declared by its definitions.
+//!
+//! The operations are generated into [`crate::apis::generated`], and every request of the
+//! client goes through one of them, so that the routes cannot drift from Asterisk.
+
+use crate::errors::Result;
+use reqwest::Method;
+use serde::{Serialize, Serializer};
+use std::fmt::Display;
+
+/// An operation, e.g. `GET /channels/{channelId}/variable`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operation {
+    /// Name of the operation in the definitions, e.g. `getChannelVar`.
+    pub nickname: &'static str,
+    /// HTTP method.
+    pub method: Method,
+    /// Path, with its parameters in braces.
+    pub path: &'static str,
+    /// Query parameters.
+    pub query: &'static [&'static str],
+    /// Required query parameters.
+    pub required: &'static [&'static str],
+    /// Parameters sent in the JSON body.
+    pub body: BodyParams,
+}
+
+/// How the parameters of an operation are sent in the body of its requests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BodyParams {
+    /// Keys of the body, e.g. `variables` holding the variables of a new channel.
+    Keys(&'static [&'static str]),
+    /// A parameter which is the whole body, e.g. the `filter` of the events of an
+    /// application.
+    Whole(&'static str),
+}
+
+impl Operation {
+    /// The path, with its parameters replaced in order by `args`.
+    pub fn path_with(&self, args: &[&(dyn Display + Sync)]) -> String {
+        let mut args = args.iter();
+        self.path
+            .split('/')
+            .map(|segment| match segment.starts_with('{') {
+                true => args.next().map(ToString::to_string).unwrap_or_default(),
+                false => segment.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Panics when a request does not follow the definition of the operation: a path
+    /// parameter is missing, or a query parameter or body key is not declared.
+    ///
+    /// The requests of the client are checked in its tests.
+    #[cfg(test)]
+    pub(crate) fn check(&self, args: usize, query: &str, body: Option<&str>) {
+        let params = self.path.matches('{').count();
+        assert_eq!(args, params, "{}: expected {params} path parameters", self);
+
+        let keys: Vec<&str> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split('=').next().unwrap_or_default())
+            .collect();
+        for key in &keys {
+            assert!(
+                self.query.contains(key),
+                "{self}: unknown query parameter {key:?}"
+            );
+        }
+        for key in self.required {
+            assert!(
+                keys.contains(key),
+                "{self}: missing query parameter {key:?}"
+            );
+        }
+
+        let Some(body) = body else {
+            return;
+        };
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        let Some(body) = body.as_object() else {
+            panic!("{self}: the body is not an object: {body}");
+        };
+        let BodyParams::Keys(keys) = self.body else {
+            return;
+        };
+        for key in body.keys() {
+            assert!(
+                keys.contains(&key.as_str()),
+                "{self}: unknown body key {key:?}"
+            );
+        }
+    }
+}
+
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+/// The JSON body of a request, with a key for each parameter set.
+///
+/// Serializes to `null`, i.e. no body, when no parameter is set.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Body(serde_json::Map<String, serde_json::Value>);
+
+impl Body {
+    /// An empty body.
+    pub fn new() -> Self {
+        Body::default()
+    }
+
+    /// Sets a parameter, unless it serializes to `null`.
+    pub fn field(mut self, name: &str, value: &impl Serialize) -> Result<Self> {
+        let value = serde_json::to_value(value)?;
+        if !value.is_null() {
+            self.0.insert(name.to_string(), value);
+        }
+        Ok(self)
+    }
+}
+
+impl Serialize for Body {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0.is_empty() {
+            true => serializer.serialize_unit(),
+            false => self.0.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::generated::channels;
+
+    #[test]
+    fn test_paths_and_checks() {
+        let path = channels::GET_CHANNEL_VAR.path_with(&[&"1234"]);
+        assert_eq!(path, "/channels/1234/variable");
+
+        channels::GET_CHANNEL_VAR.check(1, "variable=CALLERID%28name%29", None);
+        channels::ORIGINATE.check(0, "endpoint=PJSIP%2F1000", Some(r#"{"variables":{}}"#));
+        let unknown =
+            std::panic::catch_unwind(|| channels::GET_CHANNEL_VAR.check(1, "name=x", None));
+        assert!(unknown.is_err());
+        let missing = std::panic::catch_unwind(|| channels::ORIGINATE.check(0, "", None));
+        assert!(missing.is_err());
+
+        let body = Body::new()
+            .field("variables", &None::<serde_json::Value>)
+            .unwrap();
+        assert_eq!(serde_json::to_value(body).unwrap(), serde_json::Value::Null);
+    }
+}
@@ -1,6 +1,8 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::playbacks as operations;
+use crate::apis::ids::PlaybackId;
 use serde::Serialize;
 
 pub struct Playbacks<'c> {
@@ -14,33 +16,39 @@ impl<'c> Playbacks<'c> {
 }
 
 impl Playbacks<'_> {
+    /// Get a playback's details.
     pub async fn get(
         &self,
-        playback_id: impl Into<String>,
-    ) -> crate::errors::Result<Vec<models::Playback>> {
+        playback_id: impl Into<PlaybackId> + Send,
+    ) -> crate::errors::Result<models::Playback> {
         self.client
-            .get(format!("/playback/{}", playback_id.into()).as_str())
+            .call(&operations::GET, &[&playback_id.into()], &(), ())
             .await
     }
 
+    /// Control a playback.
     pub async fn control(
         &self,
-        playback_id: impl Into<String>,
+        playback_id: impl Into<PlaybackId> + Send,
         operation: Operation,
     ) -> crate::errors::Result<()> {
         self.client
-            .post_with_query(
-                format!("/playback/{}/control", playback_id.into()).as_str(),
-                vec![] as Vec<String>,
+            .call(
+                &operations::CONTROL,
+                &[&playback_id.into()],
                 &[("operation", operation)],
+                (),
             )
             .await
     }
 
     /// Stop a playback
-    pub async fn stop(&self, playback_id: impl Into<String>) -> crate::errors::Result<()> {
+    pub async fn stop(
+        &self,
+        playback_id: impl Into<PlaybackId> + Send,
+    ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/playback/{}", playback_id.into()).as_str())
+            .call(&operations::STOP, &[&playback_id.into()], &(), ())
             .await
     }
 }
@@ -1,28 +1,29 @@
-use serde::Deserialize;
+use crate::apis::ids::PlaybackId;
+use serde::{Deserialize, Serialize};
 
 /// Playback : Object representing the playback of media to a channel
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Playback {
     /// ID for this playback operation
-    #[serde(rename = "id")]
-    pub id: Option<String>,
+    #[serde(rename = "id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<PlaybackId>,
     /// The URI for the media currently being played back.
-    #[serde(rename = "media_uri")]
+    #[serde(rename = "media_uri", skip_serializing_if = "Option::is_none")]
     pub media_uri: Option<String>,
     /// If a list of URIs is being played, the next media URI to be played back.
-    #[serde(rename = "next_media_uri")]
+    #[serde(rename = "next_media_uri", skip_serializing_if = "Option::is_none")]
     pub next_media_uri: Option<String>,
     /// URI for the channel or bridge to play the media on
-    #[serde(rename = "target_uri")]
+    #[serde(rename = "target_uri", skip_serializing_if = "Option::is_none")]
     pub target_uri: Option<String>,
     /// For media types that support multiple languages, the language requested for playback.
-    #[serde(rename = "language")]
+    #[serde(rename = "language", skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
     /// Current state of the playback operation.
     #[serde(rename = "state")]
     pub state: PlaybackState,
 }
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum PlaybackState {
     #[serde(rename = "queued")]
     #[default]
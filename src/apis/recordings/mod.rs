@@ -1,4 +1,6 @@
 use crate::apis::client::Client;
+use crate::apis::generated::recordings as operations;
+use crate::apis::ids::RecordingName;
 
 pub mod models;
 
@@ -11,11 +13,11 @@ impl<'c> Recordings<'c> {
         Self { client }
     }
 
-    pub fn stored(&self) -> StoredRecordings {
+    pub fn stored(&self) -> StoredRecordings<'_> {
         StoredRecordings::new(self.client)
     }
 
-    pub fn live(&self) -> LiveRecordings {
+    pub fn live(&self) -> LiveRecordings<'_> {
         LiveRecordings::new(self.client)
     }
 }
@@ -33,32 +35,39 @@ impl<'c> StoredRecordings<'c> {
 impl StoredRecordings<'_> {
     /// List recordings that are complete.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::StoredRecording>> {
-        self.client.get("/recordings/stored").await
+        self.client
+            .call(&operations::LIST_STORED, &[], &(), ())
+            .await
     }
 
     /// Get a stored recording's details.
     pub async fn get(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<models::StoredRecording> {
         self.client
-            .get(format!("/recordings/stored/{}", recording_name.into()).as_str())
+            .call(&operations::GET_STORED, &[&recording_name.into()], &(), ())
             .await
     }
 
     pub async fn delete(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/recordings/stored/{}", recording_name.into()).as_str())
+            .call(
+                &operations::DELETE_STORED,
+                &[&recording_name.into()],
+                &(),
+                (),
+            )
             .await
     }
 
     /// Get the file associated with the stored recording.
     pub async fn file(
         &self,
-        _recording_name: impl Into<String> + Send,
+        _recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<Vec<u8>> {
         unimplemented!("This function is not implemented yet"); // todo implement first teh get_raw, so will return the raw bytes
                                                                 // self.client
@@ -69,14 +78,15 @@ impl StoredRecordings<'_> {
     /// Copy a stored recording.
     pub async fn copy(
         &self,
-        recording_name: impl Into<String> + Send,
-        destination: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
+        destination: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<models::StoredRecording> {
         self.client
-            .post_with_query(
-                format!("/recordings/stored/{}/copy", recording_name.into()).as_str(),
-                vec![] as Vec<String>,
-                &[("destination", destination.into())],
+            .call(
+                &operations::COPY_STORED,
+                &[&recording_name.into()],
+                &[("destinationRecordingName", destination.into())],
+                (),
             )
             .await
     }
@@ -93,41 +103,33 @@ impl<'c> LiveRecordings<'c> {
 }
 
 impl LiveRecordings<'_> {
-    /// List live recordings
-    pub async fn list(&self) -> crate::errors::Result<Vec<models::LiveRecording>> {
-        self.client.get("/recordings/live").await
-    }
-
     /// Get a live recording's details.
     pub async fn get(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<models::LiveRecording> {
         self.client
-            .get(format!("/recordings/live/{}", recording_name.into()).as_str())
+            .call(&operations::GET_LIVE, &[&recording_name.into()], &(), ())
             .await
     }
 
     /// Stop a live recording and discard it.
     pub async fn discard(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/recordings/live/{}", recording_name.into()).as_str())
+            .call(&operations::CANCEL, &[&recording_name.into()], &(), ())
             .await
     }
 
     /// Stop a live recording and store it.
     pub async fn stop(
         &self,
-        recording_name: impl Into<String> + Send,
-    ) -> crate::errors::Result<models::StoredRecording> {
+        recording_name: impl Into<RecordingName> + Send,
+    ) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/recordings/live/{}/stop", recording_name.into()).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::STOP, &[&recording_name.into()], &(), ())
             .await
     }
 
@@ -137,23 +139,20 @@ impl LiveRecordings<'_> {
     /// Pausing a recording suspends silence detection, which will be restarted when the recording is unpaused. Paused time is not included in the accounting for maxDurationSeconds.
     pub async fn pause(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/recordings/live/{}/pause", recording_name.into()).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::PAUSE, &[&recording_name.into()], &(), ())
             .await
     }
 
     /// Unpause a live recording.
     pub async fn unpause(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/recordings/live/{}/pause", recording_name.into()).as_str())
+            .call(&operations::UNPAUSE, &[&recording_name.into()], &(), ())
             .await
     }
 
@@ -163,23 +162,20 @@ impl LiveRecordings<'_> {
     /// Muting a recording suspends silence detection, which will be restarted when the recording is unmuted.
     pub async fn mute(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .post(
-                format!("/recordings/live/{}/mute", recording_name.into()).as_str(),
-                vec![] as Vec<String>,
-            )
+            .call(&operations::MUTE, &[&recording_name.into()], &(), ())
             .await
     }
 
     /// Unmute a live recording.
     pub async fn unmute(
         &self,
-        recording_name: impl Into<String> + Send,
+        recording_name: impl Into<RecordingName> + Send,
     ) -> crate::errors::Result<()> {
         self.client
-            .delete(format!("/recordings/live/{}/mute", recording_name.into()).as_str())
+            .call(&operations::UNMUTE, &[&recording_name.into()], &(), ())
             .await
     }
 }
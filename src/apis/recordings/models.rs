@@ -1,13 +1,14 @@
-use serde::Deserialize;
+use crate::apis::ids::RecordingName;
+use serde::{Deserialize, Serialize};
 
 /// LiveRecording:
 ///
 /// A recording that is in progress
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct LiveRecording {
     /// Base name for the recording
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: RecordingName,
     /// Recording format (wav, gsm, etc.)
     #[serde(rename = "format")]
     pub format: String,
@@ -32,7 +33,7 @@ pub struct LiveRecording {
     pub cause: Option<String>,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub enum LiveRecordingState {
     #[serde(rename = "recording")]
     #[default]
@@ -48,10 +49,10 @@ pub enum LiveRecordingState {
 /// StoredRecording:
 ///
 /// A past recording that may be played back.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StoredRecording {
     #[serde(rename = "name")]
-    pub name: String,
+    pub name: RecordingName,
     #[serde(rename = "format")]
     pub format: String,
 }
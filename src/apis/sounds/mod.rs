@@ -1,6 +1,7 @@
 pub mod models;
 
 use crate::apis::client::Client;
+use crate::apis::generated::sounds as operations;
 use std::fmt::Display;
 
 pub struct Sounds<'c> {
@@ -16,7 +17,7 @@ impl<'c> Sounds<'c> {
 impl Sounds<'_> {
     /// List all sounds.
     pub async fn list(&self) -> crate::errors::Result<Vec<models::Sound>> {
-        self.client.get("/sounds").await
+        self.client.call(&operations::LIST, &[], &(), ()).await
     }
 
     /// Get a sound's details.
@@ -25,7 +26,7 @@ impl Sounds<'_> {
         sound_id: impl Into<String> + Display + Send,
     ) -> crate::errors::Result<models::Sound> {
         self.client
-            .get(format!("/sounds/{sound_id}").as_str())
+            .call(&operations::GET, &[&sound_id.into()], &(), ())
             .await
     }
 }
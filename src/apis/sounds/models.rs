@@ -1,13 +1,13 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 /// Sound : A media file that may be played back.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sound {
     /// Sound's identifier.
     #[serde(rename = "id")]
     pub id: String,
     /// Text description of the sound, usually the words spoken.
-    #[serde(rename = "text")]
+    #[serde(rename = "text", skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
     /// The formats and languages in which this sound is available.
     #[serde(rename = "formats")]
@@ -15,7 +15,7 @@ pub struct Sound {
 }
 
 /// FormatLanguage : Identifies the format and language of a sound file
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct FormatLanguage {
     #[serde(rename = "language")]
     pub language: String,
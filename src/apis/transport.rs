@@ -0,0 +1,145 @@
+//! Transport carrying the REST requests to Asterisk.
+//!
+//! [`Client`](crate::apis::client::Client) sends every request through an [`AriTransport`],
+//! [`ReqwestTransport`] by default. Another transport can be plugged in with
+//! [`Client::with_transport`](crate::apis::client::Client::with_transport), to record the
+//! requests, answer them from a mock, or use another HTTP stack.
+//!
+//! ```
+//! use asterisk_ari::apis::transport::{
+//!     AriTransport, TransportFuture, TransportRequest, TransportResponse,
+//! };
+//!
+//! /// Answers every request with an empty list.
+//! #[derive(Debug)]
+//! struct Empty;
+//!
+//! impl AriTransport for Empty {
+//!     fn send(&self, _request: TransportRequest) -> TransportFuture<'_> {
+//!         Box::pin(async { Ok(TransportResponse::new(200, "[]")) })
+//!     }
+//! }
+//! ```
+
+use crate::config::Config;
+use crate::errors::{AriError, Result};
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+pub use reqwest::Method;
+
+/// Future returned by [`AriTransport::send`].
+pub type TransportFuture<'a> = Pin<Box<dyn Future<Output = Result<TransportResponse>> + Send + 'a>>;
+
+/// A REST request, ready to be sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransportRequest {
+    /// HTTP method.
+    pub method: Method,
+    /// Full URL, query string included.
+    pub url: String,
+    /// Headers, authorization included.
+    pub headers: Vec<(String, String)>,
+    /// JSON body, if any.
+    pub body: Option<String>,
+}
+
+/// The response to a REST request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransportResponse {
+    /// HTTP status code.
+    pub status: u16,
+    /// Body, empty when there is none.
+    pub body: String,
+}
+
+impl TransportResponse {
+    /// Creates a response.
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        TransportResponse {
+            status,
+            body: body.into(),
+        }
+    }
+
+    /// Whether the status code is a success (2xx).
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Sends the REST requests of the client and returns the responses.
+///
+/// Responses with an error status are returned as `Ok`; only failing to get a response at
+/// all (connection refused, timeout...) is an error.
+pub trait AriTransport: Debug + Send + Sync {
+    /// Sends a request.
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_>;
+}
+
+/// The default transport, sending the requests with `reqwest`.
+#[derive(Clone, Debug)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport sending the requests with a `reqwest` client.
+    pub fn new(client: reqwest::Client) -> Self {
+        ReqwestTransport { client }
+    }
+
+    /// Creates a transport with the timeouts and TLS options of a configuration.
+    pub fn with_config(config: &Config) -> Result<Self> {
+        let mut builder = reqwest::Client::builder()
+            .connect_timeout(config.connect_timeout)
+            .http2_keep_alive_interval(Some(config.ping_interval))
+            .http2_keep_alive_while_idle(true)
+            .danger_accept_invalid_certs(config.accept_invalid_certs);
+        if let Some(timeout) = config.request_timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(pem) = &config.root_certificate {
+            let certificate = reqwest::Certificate::from_pem(pem)
+                .map_err(|e| AriError::InvalidConfig(e.to_string()))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| AriError::InvalidConfig(e.to_string()))?;
+        Ok(ReqwestTransport::new(client))
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        ReqwestTransport::with_config(&Config::default()).unwrap()
+    }
+}
+
+impl AriTransport for ReqwestTransport {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let mut builder = self.client.request(request.method, request.url);
+            for (name, value) in request.headers {
+                builder = builder.header(name, value);
+            }
+            if let Some(body) = request.body {
+                builder = builder.body(body);
+            }
+
+            let response = builder
+                .send()
+                .await
+                .map_err(|e| AriError::Internal(e.to_string()))?;
+            let status = response.status().as_u16();
+            let body = response
+                .text()
+                .await
+                .map_err(|e| AriError::Internal(e.to_string()))?;
+
+            Ok(TransportResponse { status, body })
+        })
+    }
+}
@@ -0,0 +1,415 @@
+//! Local mirror of the channels, bridges and endpoints of Asterisk.
+//!
+//! A [`StateCache`] is seeded from the list APIs and then kept up to date from the events,
+//! so questions like "which channels are in this bridge" are answered synchronously, without
+//! an HTTP round trip. The cache is resynchronized whenever the WebSocket reconnects or the
+//! cache lags behind the events, as the events missed in the meantime are lost.
+//!
+//! Asterisk only sends the events of the resources the application is subscribed to, so the
+//! client should be started with `subscribeAll` (the default) or subscribed to the resources
+//! of interest.
+
+use crate::apis::bridges::models::Bridge;
+use crate::apis::channels::models::Channel;
+use crate::apis::endpoints::models::Endpoint;
+use crate::apis::ids::{BridgeId, ChannelId, EndpointRef};
+use crate::errors::Result;
+use crate::ws::models::{ConnectionState, Event};
+use crate::AriClient;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tokio::sync::broadcast;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, warn};
+
+/// A synchronous view over the channels, bridges and endpoints of Asterisk.
+///
+/// The cache stops following the events when dropped.
+pub struct StateCache {
+    snapshot: Arc<RwLock<Snapshot>>,
+    _watcher: DropGuard,
+}
+
+impl StateCache {
+    /// Seeds a cache from the current state of Asterisk and starts following the events.
+    pub async fn start(client: &AriClient) -> Result<Self> {
+        // Subscribe before seeding, so no event can be missed. Events already reflected by the
+        // seed are applied again, which leaves the state unchanged.
+        let events = client.subscribe();
+        let states = client.connection_states();
+        let snapshot = Arc::new(RwLock::new(Snapshot::fetch(client).await?));
+        let stop_signal = CancellationToken::new();
+
+        tokio::spawn(watch(
+            client.clone(),
+            snapshot.clone(),
+            events,
+            states,
+            stop_signal.clone(),
+        ));
+
+        Ok(StateCache {
+            snapshot,
+            _watcher: stop_signal.drop_guard(),
+        })
+    }
+
+    /// A channel, by id.
+    pub fn channel(&self, channel_id: impl Into<ChannelId>) -> Option<Channel> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .channels
+            .get(&channel_id.into())
+            .cloned()
+    }
+
+    /// All the channels.
+    pub fn channels(&self) -> Vec<Channel> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .channels
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// A bridge, by id.
+    pub fn bridge(&self, bridge_id: impl Into<BridgeId>) -> Option<Bridge> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .bridges
+            .get(&bridge_id.into())
+            .cloned()
+    }
+
+    /// All the bridges.
+    pub fn bridges(&self) -> Vec<Bridge> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .bridges
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// The channels in a bridge.
+    pub fn bridge_channels(&self, bridge_id: impl Into<BridgeId>) -> Vec<Channel> {
+        let snapshot = self.snapshot.read().unwrap();
+        snapshot
+            .bridges
+            .get(&bridge_id.into())
+            .map(|bridge| {
+                bridge
+                    .channels
+                    .iter()
+                    .filter_map(|id| snapshot.channels.get(id).cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The bridge a channel is in.
+    pub fn channel_bridge(&self, channel_id: impl Into<ChannelId>) -> Option<Bridge> {
+        let channel_id = channel_id.into();
+        self.snapshot
+            .read()
+            .unwrap()
+            .bridges
+            .values()
+            .find(|bridge| bridge.channels.contains(&channel_id))
+            .cloned()
+    }
+
+    /// An endpoint, e.g. `PJSIP/1000`.
+    pub fn endpoint(&self, endpoint: impl Into<EndpointRef>) -> Option<Endpoint> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .endpoints
+            .get(&endpoint.into())
+            .cloned()
+    }
+
+    /// All the endpoints.
+    pub fn endpoints(&self) -> Vec<Endpoint> {
+        self.snapshot
+            .read()
+            .unwrap()
+            .endpoints
+            .values()
+            .cloned()
+            .collect()
+    }
+}
+
+/// Applies the events to the snapshot, and fetches a new one after a reconnection or a lag.
+async fn watch(
+    client: AriClient,
+    snapshot: Arc<RwLock<Snapshot>>,
+    mut events: broadcast::Receiver<Event>,
+    mut states: broadcast::Receiver<ConnectionState>,
+    stop_signal: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            event = events.recv() => match event {
+                Ok(event) => snapshot.write().unwrap().apply(&event),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    warn!("state cache lagged behind, {} events skipped", skipped);
+                    resync(&client, &snapshot).await;
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    debug!("state cache stopped: event stream closed");
+                    break;
+                }
+            },
+            state = states.recv() => match state {
+                Ok(ConnectionState::Reconnected) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                    resync(&client, &snapshot).await;
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+/// Replaces the snapshot with the current state of Asterisk.
+async fn resync(client: &AriClient, snapshot: &RwLock<Snapshot>) {
+    match Snapshot::fetch(client).await {
+        Ok(fresh) => *snapshot.write().unwrap() = fresh,
+        Err(e) => warn!("unable to resynchronize the state cache: {}", e),
+    }
+}
+
+/// The state mirrored by the cache.
+#[derive(Debug, Default)]
+struct Snapshot {
+    channels: HashMap<ChannelId, Channel>,
+    bridges: HashMap<BridgeId, Bridge>,
+    endpoints: HashMap<EndpointRef, Endpoint>,
+}
+
+impl Snapshot {
+    /// Lists the current channels, bridges and endpoints.
+    async fn fetch(client: &AriClient) -> Result<Self> {
+        let mut snapshot = Snapshot::default();
+        for channel in client.channels().list().await? {
+            snapshot.put_channel(channel);
+        }
+        for bridge in client.bridges().list().await? {
+            snapshot.put_bridge(bridge);
+        }
+        for endpoint in client.endpoints().list().await? {
+            snapshot.put_endpoint(endpoint);
+        }
+        Ok(snapshot)
+    }
+
+    fn put_channel(&mut self, channel: Channel) {
+        self.channels.insert(channel.id.clone(), channel);
+    }
+
+    fn put_bridge(&mut self, bridge: Bridge) {
+        self.bridges.insert(bridge.id.clone(), bridge);
+    }
+
+    fn put_endpoint(&mut self, endpoint: Endpoint) {
+        self.endpoints.insert(endpoint.reference(), endpoint);
+    }
+
+    /// Updates the snapshot from an event.
+    fn apply(&mut self, event: &Event) {
+        match event {
+            Event::ChannelCreated(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelStateChange(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelCallerId(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelConnectedLine(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelDialplan(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelHangupRequest(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelHold(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelUnhold(e) => self.put_channel(e.data.channel.clone()),
+            Event::StasisStart(e) => self.put_channel(e.data.channel.clone()),
+            Event::ChannelVarSet(e) => {
+                if let Some(channel) = &e.data.channel {
+                    self.put_channel(channel.clone());
+                }
+            }
+            Event::ChannelDestroyed(e) => {
+                let channel_id = &e.data.channel.id;
+                self.channels.remove(channel_id);
+                for bridge in self.bridges.values_mut() {
+                    bridge.channels.retain(|id| id != channel_id);
+                }
+            }
+            Event::BridgeCreated(e) => self.put_bridge(e.data.bridge.clone()),
+            Event::BridgeVideoSourceChanged(e) => self.put_bridge(e.data.bridge.clone()),
+            Event::BridgeDestroyed(e) => {
+                self.bridges.remove(&e.data.bridge.id);
+            }
+            Event::BridgeMerged(e) => {
+                self.bridges.remove(&e.data.bridge_from.id);
+                self.put_bridge(e.data.bridge.clone());
+            }
+            Event::ChannelEnteredBridge(e) => {
+                self.put_bridge(e.data.bridge.clone());
+                if let Some(channel) = &e.data.channel {
+                    self.put_channel(channel.clone());
+                }
+            }
+            Event::ChannelLeftBridge(e) => {
+                self.put_bridge(e.data.bridge.clone());
+                self.put_channel(e.data.channel.clone());
+            }
+            Event::EndpointStateChange(e) => self.put_endpoint(e.data.endpoint.clone()),
+            Event::ContactStatusChange(e) => self.put_endpoint(e.data.endpoint.clone()),
+            Event::PeerStatusChange(e) => self.put_endpoint(e.data.endpoint.clone()),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: serde_json::Value) -> Event {
+        let mut json = json;
+        json["application"] = "test".into();
+        json["timestamp"] = "2025-01-07T22:12:29.571+0100".into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn channel(id: &str, state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": format!("PJSIP/{id}"),
+            "state": state,
+            "caller": { "name": "", "number": "" },
+            "connected": { "name": "", "number": "" },
+            "accountcode": "",
+            "dialplan": { "context": "default", "exten": "100", "priority": 1, "app_name": "", "app_data": "" },
+            "creationtime": "2025-01-07T22:12:29.369+0100",
+            "language": "en"
+        })
+    }
+
+    fn bridge(id: &str, channels: &[&str]) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "technology": "simple_bridge",
+            "bridge_type": "mixing",
+            "bridge_class": "stasis",
+            "creator": "Stasis",
+            "name": "",
+            "channels": channels,
+            "creationtime": "2025-01-07T22:12:29.369+0100"
+        })
+    }
+
+    #[test]
+    fn test_apply_channel_lifecycle() {
+        let mut snapshot = Snapshot::default();
+
+        snapshot.apply(&event(serde_json::json!({
+            "type": "ChannelCreated",
+            "channel": channel("a", "Ring"),
+        })));
+        snapshot.apply(&event(serde_json::json!({
+            "type": "ChannelStateChange",
+            "channel": channel("a", "Up"),
+        })));
+        assert_eq!(
+            snapshot.channels["a"].state,
+            crate::apis::channels::models::ChannelState::Up
+        );
+
+        snapshot.apply(&event(serde_json::json!({
+            "type": "ChannelDestroyed",
+            "cause": 16,
+            "cause_txt": "Normal Clearing",
+            "channel": channel("a", "Up"),
+        })));
+        assert!(snapshot.channels.is_empty());
+    }
+
+    #[test]
+    fn test_apply_bridge_membership() {
+        let mut snapshot = Snapshot::default();
+
+        snapshot.apply(&event(serde_json::json!({
+            "type": "BridgeCreated",
+            "bridge": bridge("b1", &[]),
+        })));
+        snapshot.apply(&event(serde_json::json!({
+            "type": "ChannelEnteredBridge",
+            "bridge": bridge("b1", &["a", "b"]),
+            "channel": channel("b", "Up"),
+        })));
+        assert_eq!(snapshot.bridges["b1"].channels, vec!["a", "b"]);
+
+        snapshot.apply(&event(serde_json::json!({
+            "type": "BridgeMerged",
+            "bridge": bridge("b2", &["a", "b", "c"]),
+            "bridge_from": bridge("b1", &[]),
+        })));
+        assert!(!snapshot.bridges.contains_key("b1"));
+
+        snapshot.apply(&event(serde_json::json!({
+            "type": "ChannelDestroyed",
+            "cause": 16,
+            "cause_txt": "Normal Clearing",
+            "channel": channel("c", "Up"),
+        })));
+        assert_eq!(snapshot.bridges["b2"].channels, vec!["a", "b"]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn test_resync_when_lagging_behind() {
+        use crate::testing::MockAriServer;
+        use std::time::Duration;
+
+        let server = MockAriServer::start().await.unwrap();
+        let client = AriClient::with_config(server.config());
+        server.add_channel("a", "PJSIP/1000", "test").unwrap();
+        server.add_channel("b", "PJSIP/1001", "test").unwrap();
+
+        // The watcher misses the first of the two events sent before it starts.
+        let (sender, events) = broadcast::channel(1);
+        for id in ["x", "y"] {
+            sender
+                .send(event(serde_json::json!({
+                    "type": "ChannelCreated",
+                    "channel": channel(id, "Ring"),
+                })))
+                .unwrap();
+        }
+        let snapshot = Arc::new(RwLock::new(Snapshot::default()));
+        let stop_signal = CancellationToken::new();
+        tokio::spawn(watch(
+            client.clone(),
+            snapshot.clone(),
+            events,
+            client.connection_states(),
+            stop_signal.clone(),
+        ));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !snapshot.read().unwrap().channels.contains_key("a") {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        stop_signal.cancel();
+        let snapshot = snapshot.read().unwrap();
+        assert!(snapshot.channels.contains_key("b"));
+        assert!(!snapshot.channels.contains_key("x"));
+    }
+}
@@ -0,0 +1,479 @@
+//! Higher level call primitives built on the channel APIs and the event stream.
+
+pub mod models;
+pub mod params;
+
+use crate::apis::bridges::models::BridgeType;
+use crate::apis::bridges::params::{AddChannelRequest, CreateWithIdRequest};
+use crate::apis::channels::models::{ChannelState, DialStatus};
+use crate::apis::channels::params::{
+    App, CreateRequest, DeleteRequest, DialRequest, MohRequest, OriginateWithIdRequest,
+    PlayWithPlaybackIdRequest,
+};
+use crate::apis::ids::{BridgeId, ChannelId};
+use crate::errors::{AriError, Result};
+use crate::ws::models::Event;
+use crate::AriClient;
+use models::{ConnectOutcome, Leg, OriginateOutcome};
+use params::{ConnectOptions, Ringback};
+use std::time::Duration;
+use tokio::sync::broadcast::Receiver;
+use tracing::debug;
+
+pub struct Calls<'c> {
+    client: &'c AriClient,
+}
+
+impl<'c> Calls<'c> {
+    pub fn new(client: &'c AriClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Calls<'_> {
+    /// Originate a call and wait until it is answered or fails.
+    ///
+    /// *Implementation Notes*
+    /// The outcome is taken from the `Dial` status of the originated channel, from its hangup cause
+    /// once destroyed, or from the channel reaching the `Up` state (or entering the Stasis application).
+    /// The channel id is known before the channel is created, so no event can be missed.
+    /// If nothing happens within `timeout`, the channel is hung up and `NoAnswer` is returned.
+    ///
+    /// Asterisk only sends the events of the channel to the application it is originated into,
+    /// so a request without an application is sent with the one of the client, and a request
+    /// continuing to an extension is rejected.
+    pub async fn originate_and_wait(
+        &self,
+        request: OriginateWithIdRequest,
+        timeout: Duration,
+    ) -> Result<OriginateOutcome> {
+        let request = match (&request.app, &request.extension) {
+            (Some(_), _) => request,
+            (None, None) => {
+                let application = self
+                    .client
+                    .application()
+                    .ok_or_else(|| AriError::Internal("the client is not started".to_string()))?;
+                request.with_app(App::new(application))
+            }
+            (None, Some(_)) => {
+                return Err(AriError::Internal(
+                    "the outcome of a channel originated to an extension cannot be observed"
+                        .to_string(),
+                ))
+            }
+        };
+        let channel_id = request.channel_id.clone();
+        let mut events = self.client.subscribe();
+
+        self.client.channels().create_with_id(request).await?;
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut dial_status = DialStatus::Pending;
+        loop {
+            let event =
+                match tokio::time::timeout_at(deadline, crate::client::next_event(&mut events))
+                    .await
+                {
+                    Ok(event) => event?,
+                    Err(_) => {
+                        debug!("originated channel {} timed out", channel_id);
+                        if let Err(e) = self
+                            .client
+                            .channels()
+                            .delete(DeleteRequest::new(&channel_id))
+                            .await
+                        {
+                            debug!("unable to hang up channel {}: {}", channel_id, e);
+                        }
+                        return Ok(OriginateOutcome::NoAnswer);
+                    }
+                };
+
+            match event {
+                Event::StasisStart(e) if e.data.channel.id == channel_id => {
+                    return Ok(OriginateOutcome::Answered(e.data.channel))
+                }
+                Event::ChannelStateChange(e)
+                    if e.data.channel.id == channel_id
+                        && e.data.channel.state == ChannelState::Up =>
+                {
+                    return Ok(OriginateOutcome::Answered(e.data.channel))
+                }
+                Event::Dial(e) if e.data.peer.as_ref().map(|p| &p.id) == Some(&channel_id) => {
+                    if let Some(outcome) = OriginateOutcome::from_dial_status(&e.data.dialstatus) {
+                        return Ok(outcome);
+                    }
+                    dial_status = e.data.dialstatus;
+                }
+                Event::ChannelDestroyed(e) if e.data.channel.id == channel_id => {
+                    debug!(
+                        "originated channel {} destroyed (dial status: {:?}, cause: {})",
+                        channel_id, dial_status, e.data.cause
+                    );
+                    return Ok(OriginateOutcome::from_cause(e.data.cause));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Connect a channel of the application to an endpoint.
+    ///
+    /// *Implementation Notes*
+    /// The endpoint is dialed on a new channel while the inbound channel hears the configured ringback.
+    /// Once the endpoint answers, the inbound channel is answered and both are placed in a mixing bridge
+    /// until either of them hangs up, then the other one is hung up as well.
+    /// If the endpoint does not answer, the inbound channel is left in the application to be handled further.
+    /// The bridge and the dialed channel are cleaned up on every exit path, errors included.
+    pub async fn connect_call(
+        &self,
+        inbound: impl Into<ChannelId> + Send,
+        endpoint: impl Into<String> + Send,
+        options: ConnectOptions,
+    ) -> Result<ConnectOutcome> {
+        let application = self
+            .client
+            .application()
+            .ok_or_else(|| AriError::Internal("the client is not started".to_string()))?;
+
+        let mut call = Call {
+            client: self.client,
+            inbound: inbound.into(),
+            outbound: crate::client::unique_id("connect").into(),
+            bridge: crate::client::unique_id("connect").into(),
+            events: self.client.subscribe(),
+        };
+
+        let result = call.connect(&application, endpoint.into(), &options).await;
+
+        call.cleanup(matches!(result, Ok(ConnectOutcome::Completed { .. })))
+            .await;
+
+        result
+    }
+}
+
+/// Which leg of a call an event reports as gone.
+pub(crate) fn hung_up(event: &Event, inbound: &ChannelId, outbound: &ChannelId) -> Option<Leg> {
+    let channel = match event {
+        Event::StasisEnd(e) => &e.data.channel,
+        Event::ChannelHangupRequest(e) => &e.data.channel,
+        Event::ChannelDestroyed(e) => &e.data.channel,
+        _ => return None,
+    };
+
+    if &channel.id == inbound {
+        Some(Leg::Inbound)
+    } else if &channel.id == outbound {
+        Some(Leg::Outbound)
+    } else {
+        None
+    }
+}
+
+/// A call between a channel of the application and a dialed endpoint.
+struct Call<'c> {
+    client: &'c AriClient,
+    inbound: ChannelId,
+    outbound: ChannelId,
+    bridge: BridgeId,
+    events: Receiver<Event>,
+}
+
+impl Call<'_> {
+    async fn connect(
+        &mut self,
+        application: &str,
+        endpoint: String,
+        options: &ConnectOptions,
+    ) -> Result<ConnectOutcome> {
+        let channels = self.client.channels();
+        let bridges = self.client.bridges();
+
+        bridges
+            .create_with_id(CreateWithIdRequest::new(&self.bridge).with_type(BridgeType::Mixing))
+            .await?;
+
+        let mut outbound = CreateRequest::new(endpoint, App::new(application))
+            .with_channel_id(&self.outbound)
+            .with_originator(&self.inbound);
+        if let Some(caller_id) = &options.caller_id {
+            outbound = outbound.with_variables(serde_json::json!({ "CALLERID(all)": caller_id }));
+        }
+        channels.create(outbound).await?;
+
+        let mut ringback_playback = None;
+        match &options.ringback {
+            Ringback::Ring => channels.ring(&self.inbound).await?,
+            Ringback::EarlyMedia => self.bridge_legs().await?,
+            Ringback::Moh(class) => {
+                let mut request = MohRequest::new(&self.inbound);
+                if let Some(class) = class {
+                    request = request.with_moh_class(class);
+                }
+                channels.moh(request).await?;
+            }
+            Ringback::Media(uri) => {
+                let playback_id = crate::client::unique_id("connect");
+                channels
+                    .play_with_playback_id(PlayWithPlaybackIdRequest::new(
+                        &self.inbound,
+                        &playback_id,
+                        uri,
+                    ))
+                    .await?;
+                ringback_playback = Some(playback_id);
+            }
+            Ringback::None => {}
+        }
+
+        channels
+            .dial(
+                DialRequest::new(&self.outbound)
+                    .with_caller(&self.inbound)
+                    .with_timeout(options.timeout),
+            )
+            .await?;
+
+        // Leave some room to the dial timeout, so the dial status is received before giving up.
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(u64::from(options.timeout) + 5);
+        if let Some(outcome) = self.wait_answer(deadline).await? {
+            return Ok(outcome);
+        }
+
+        match &options.ringback {
+            Ringback::Moh(_) => channels.unmoh(&self.inbound).await?,
+            Ringback::Media(_) => {
+                if let Some(playback_id) = ringback_playback {
+                    if let Err(e) = self.client.playbacks().stop(&playback_id).await {
+                        debug!("unable to stop ringback {}: {}", playback_id, e);
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Answering also stops the ringing indication.
+        channels.answer(&self.inbound).await?;
+        if options.ringback != Ringback::EarlyMedia {
+            self.bridge_legs().await?;
+        }
+
+        loop {
+            let event = crate::client::next_event(&mut self.events).await?;
+            if let Some(leg) = hung_up(&event, &self.inbound, &self.outbound) {
+                return Ok(ConnectOutcome::Completed { hung_up_by: leg });
+            }
+        }
+    }
+
+    /// Waits for the outbound channel to be answered.
+    ///
+    /// Returns the outcome of the call if it ends before being answered.
+    async fn wait_answer(
+        &mut self,
+        deadline: tokio::time::Instant,
+    ) -> Result<Option<ConnectOutcome>> {
+        loop {
+            let event = match tokio::time::timeout_at(
+                deadline,
+                crate::client::next_event(&mut self.events),
+            )
+            .await
+            {
+                Ok(event) => event?,
+                Err(_) => return Ok(Some(ConnectOutcome::NoAnswer)),
+            };
+
+            match &event {
+                Event::Dial(e) if e.data.peer.as_ref().map(|p| &p.id) == Some(&self.outbound) => {
+                    if e.data.dialstatus == DialStatus::Answer {
+                        return Ok(None);
+                    }
+                    if let Some(outcome) = OriginateOutcome::from_dial_status(&e.data.dialstatus) {
+                        return Ok(ConnectOutcome::unanswered(outcome));
+                    }
+                }
+                Event::ChannelStateChange(e)
+                    if e.data.channel.id == self.outbound
+                        && e.data.channel.state == ChannelState::Up =>
+                {
+                    return Ok(None)
+                }
+                Event::ChannelDestroyed(e) if e.data.channel.id == self.outbound => {
+                    return Ok(ConnectOutcome::unanswered(OriginateOutcome::from_cause(
+                        e.data.cause,
+                    )))
+                }
+                _ => {}
+            }
+
+            if hung_up(&event, &self.inbound, &self.outbound) == Some(Leg::Inbound) {
+                return Ok(Some(ConnectOutcome::CallerHangup));
+            }
+        }
+    }
+
+    /// Places both legs in the bridge.
+    async fn bridge_legs(&self) -> Result<()> {
+        self.client
+            .bridges()
+            .add_channel(AddChannelRequest::new(
+                &self.bridge,
+                format!("{},{}", self.inbound, self.outbound),
+            ))
+            .await
+    }
+
+    /// Destroys the bridge and hangs up the outbound channel, and the inbound one if asked.
+    ///
+    /// Errors are only logged, as the resources may already be gone.
+    async fn cleanup(&self, hangup_inbound: bool) {
+        if let Err(e) = self.client.bridges().delete(&self.bridge).await {
+            debug!("unable to destroy bridge {}: {}", self.bridge, e);
+        }
+
+        let mut channels = vec![&self.outbound];
+        if hangup_inbound {
+            channels.push(&self.inbound);
+        }
+        for channel_id in channels {
+            if let Err(e) = self
+                .client
+                .channels()
+                .delete(DeleteRequest::new(channel_id))
+                .await
+            {
+                debug!("unable to hang up channel {}: {}", channel_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::models::{ConnectOutcome, Leg, OriginateOutcome};
+    use super::params::ConnectOptions;
+    use crate::apis::channels::params::{
+        App, CreateRequest, DialRequest, Extension, OriginateRequest, OriginateWithIdRequest,
+    };
+    use crate::testing::models::Pickup;
+    use crate::testing::MockAriServer;
+    use crate::{AriClient, AriError};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_connect_call_bridges_the_created_channel() {
+        let server = MockAriServer::start().await.unwrap();
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let caller = server.call("PJSIP/1000", "test");
+        let hang_up = async {
+            // The caller hangs up once both legs are in the bridge.
+            while server
+                .requests()
+                .iter()
+                .all(|request| !request.path.ends_with("/addChannel"))
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            caller.hang_up();
+        };
+        let calls = client.calls();
+        let connect = calls.connect_call(caller.id(), "PJSIP/2000", ConnectOptions::new());
+        let (outcome, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(connect, hang_up)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            outcome.unwrap(),
+            ConnectOutcome::Completed {
+                hung_up_by: Leg::Inbound
+            }
+        );
+        let create = &server.requests_to("POST", "/channels/create")[0];
+        assert_eq!(create.param("endpoint"), Some("PJSIP/2000"));
+        assert_eq!(create.param("originator"), Some(caller.id()));
+        assert!(!server.received("POST", "/channels"));
+        let outbound = create.param("channelId").unwrap();
+        let dial = &server.requests_to("POST", &format!("/channels/{outbound}/dial"))[0];
+        assert_eq!(dial.param("caller"), Some(caller.id()));
+        assert!(caller.is_hung_up());
+        assert!(server.channel(outbound).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_only_created_channels_can_be_dialed() {
+        let server = MockAriServer::start().await.unwrap();
+        let client = AriClient::with_config(server.config());
+
+        let originated = client
+            .channels()
+            .originate(OriginateRequest::new("PJSIP/1000"))
+            .await
+            .unwrap();
+        let dial = client
+            .channels()
+            .dial(DialRequest::new(&originated.id))
+            .await;
+        assert!(matches!(dial, Err(AriError::Api(e)) if e.code == 409));
+
+        let created = client
+            .channels()
+            .create(CreateRequest::new("PJSIP/1001", App::new("test")))
+            .await
+            .unwrap();
+        client
+            .channels()
+            .dial(DialRequest::new(&created.id))
+            .await
+            .unwrap();
+        let again = client.channels().dial(DialRequest::new(&created.id)).await;
+        assert!(matches!(again, Err(AriError::Api(e)) if e.code == 409));
+    }
+
+    #[tokio::test]
+    async fn test_originate_and_wait_reports_the_outcome() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_pickup("PJSIP/1001", Pickup::Busy);
+        server.set_pickup("PJSIP/1002", Pickup::Ring);
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+        let calls = client.calls();
+        let timeout = Duration::from_secs(5);
+
+        // Without an application, the channel is originated into the one of the client.
+        let request = OriginateWithIdRequest::new("answered", "PJSIP/1000");
+        match calls.originate_and_wait(request, timeout).await.unwrap() {
+            OriginateOutcome::Answered(channel) => assert_eq!(channel.id.as_str(), "answered"),
+            other => panic!("unexpected {other:?}"),
+        }
+        let originate = &server.requests_to("POST", "/channels/answered")[0];
+        assert_eq!(originate.param("app"), Some("test"));
+
+        let request = OriginateWithIdRequest::new("busy", "PJSIP/1001");
+        let outcome = calls.originate_and_wait(request, timeout).await.unwrap();
+        assert_eq!(outcome, OriginateOutcome::Busy);
+
+        let request = OriginateWithIdRequest::new("ringing", "PJSIP/1002");
+        let outcome = calls
+            .originate_and_wait(request, Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert_eq!(outcome, OriginateOutcome::NoAnswer);
+        assert!(server.received("DELETE", "/channels/ringing"));
+        assert!(server.channel("ringing").is_none());
+
+        let request = OriginateWithIdRequest::new("dialplan", "PJSIP/1000")
+            .with_extension(Extension::new("1000".to_string()));
+        assert!(calls.originate_and_wait(request, timeout).await.is_err());
+        assert!(!server.received("POST", "/channels/dialplan"));
+    }
+}
@@ -0,0 +1,140 @@
+use crate::apis::channels::models::{Channel, DialStatus, HangupCause};
+
+/// OriginateOutcome : How an originated call ended up.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+pub enum OriginateOutcome {
+    /// The call was answered.
+    Answered(Channel),
+    /// The callee was busy.
+    Busy,
+    /// The callee did not answer in time.
+    NoAnswer,
+    /// The network was congested.
+    Congestion,
+    /// The call failed with the given hangup cause.
+    Failed(HangupCause),
+}
+
+/// ConnectOutcome : How a call between two parties ended up.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConnectOutcome {
+    /// The parties were connected until one of them hung up.
+    Completed {
+        /// The leg that hung up first.
+        hung_up_by: Leg,
+    },
+    /// The caller hung up before the endpoint answered.
+    CallerHangup,
+    /// The endpoint was busy.
+    Busy,
+    /// The endpoint did not answer in time.
+    NoAnswer,
+    /// The network was congested.
+    Congestion,
+    /// Dialing the endpoint failed with the given hangup cause.
+    Failed(HangupCause),
+}
+
+/// Leg : One of the two channels of a connected call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Leg {
+    /// The channel that entered the application.
+    Inbound,
+    /// The channel dialed by the application.
+    Outbound,
+}
+
+impl ConnectOutcome {
+    /// Converts the outcome of a dial, or returns `None` when it was answered.
+    pub(crate) fn unanswered(outcome: OriginateOutcome) -> Option<Self> {
+        match outcome {
+            OriginateOutcome::Answered(_) => None,
+            OriginateOutcome::Busy => Some(ConnectOutcome::Busy),
+            OriginateOutcome::NoAnswer => Some(ConnectOutcome::NoAnswer),
+            OriginateOutcome::Congestion => Some(ConnectOutcome::Congestion),
+            OriginateOutcome::Failed(cause) => Some(ConnectOutcome::Failed(cause)),
+        }
+    }
+}
+
+impl OriginateOutcome {
+    /// Maps a final `Dial` status to an outcome.
+    ///
+    /// Returns `None` for progress statuses (`RINGING`, `PROGRESS`, ...), for `ANSWER` (the
+    /// answered channel comes with its own event) and for statuses that need a hangup
+    /// cause to be reported (`CHANUNAVAIL`, `CANCEL`, ...).
+    pub(crate) fn from_dial_status(dial_status: &DialStatus) -> Option<Self> {
+        match dial_status {
+            DialStatus::Busy => Some(OriginateOutcome::Busy),
+            DialStatus::NoAnswer => Some(OriginateOutcome::NoAnswer),
+            DialStatus::Congestion => Some(OriginateOutcome::Congestion),
+            _ => None,
+        }
+    }
+
+    /// Maps the cause of a channel hung up before being answered to an outcome.
+    pub(crate) fn from_cause(cause: HangupCause) -> Self {
+        match cause {
+            HangupCause::UserBusy => OriginateOutcome::Busy,
+            HangupCause::NoUserResponse | HangupCause::NoAnswer => OriginateOutcome::NoAnswer,
+            HangupCause::NormalCircuitCongestion | HangupCause::SwitchCongestion => {
+                OriginateOutcome::Congestion
+            }
+            cause => OriginateOutcome::Failed(cause),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcome_from_dial_status() {
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::Busy),
+            Some(OriginateOutcome::Busy)
+        );
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::NoAnswer),
+            Some(OriginateOutcome::NoAnswer)
+        );
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::Congestion),
+            Some(OriginateOutcome::Congestion)
+        );
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::Ringing),
+            None
+        );
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::Answer),
+            None
+        );
+        assert_eq!(
+            OriginateOutcome::from_dial_status(&DialStatus::ChanUnavail),
+            None
+        );
+    }
+
+    #[test]
+    fn test_outcome_from_cause() {
+        assert_eq!(
+            OriginateOutcome::from_cause(HangupCause::UserBusy),
+            OriginateOutcome::Busy
+        );
+        assert_eq!(
+            OriginateOutcome::from_cause(HangupCause::NoAnswer),
+            OriginateOutcome::NoAnswer
+        );
+        assert_eq!(
+            OriginateOutcome::from_cause(HangupCause::NormalCircuitCongestion),
+            OriginateOutcome::Congestion
+        );
+        assert_eq!(
+            OriginateOutcome::from_cause(HangupCause::Unallocated),
+            OriginateOutcome::Failed(HangupCause::Unallocated)
+        );
+    }
+}
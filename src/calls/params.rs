@@ -0,0 +1,37 @@
+use derive_new::new;
+use derive_setters::Setters;
+
+#[derive(Clone, Debug, PartialEq, new, Setters)]
+#[setters(prefix = "with_")]
+#[setters(into, strip_option)]
+pub struct ConnectOptions {
+    /// Seconds to wait for the endpoint to answer.
+    #[new(value = "30")]
+    pub(crate) timeout: u32,
+
+    /// Caller ID presented to the endpoint, e.g. `"Alice" <1000>`.
+    ///
+    /// If omitted, the endpoint sees the caller ID of the inbound channel.
+    #[new(default)]
+    pub(crate) caller_id: Option<String>,
+
+    /// What the inbound channel hears while the endpoint is dialed.
+    #[new(default)]
+    pub(crate) ringback: Ringback,
+}
+
+/// What the caller hears while waiting for the other party to answer.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Ringback {
+    /// Indicate ringing on the inbound channel.
+    #[default]
+    Ring,
+    /// Bridge the channels right away, so the caller hears the early media of the endpoint.
+    EarlyMedia,
+    /// Play music on hold, with the given class or the default one.
+    Moh(Option<String>),
+    /// Play a media URI, e.g. `tone:ring;tonezone=it`.
+    Media(String),
+    /// Leave the inbound channel as it is.
+    None,
+}
@@ -0,0 +1,283 @@
+//! Call detail records generated from the events.
+//!
+//! A [`CdrGenerator`] follows the life of every channel (creation, answer, bridges joined,
+//! dial status and hangup cause) and writes a [`models::CallDetailRecord`] to a
+//! [`sinks::CdrSink`] once the channel is destroyed. Unlike the CDRs of Asterisk, the records
+//! reflect what happened in the Stasis application.
+//!
+//! ```no_run
+//! use asterisk_ari::cdr::sinks::JsonLinesSink;
+//! use asterisk_ari::cdr::CdrGenerator;
+//! use asterisk_ari::AriClient;
+//!
+//! # fn example(client: AriClient) -> std::io::Result<()> {
+//! let file = std::fs::OpenOptions::new()
+//!     .create(true)
+//!     .append(true)
+//!     .open("cdr.jsonl")?;
+//! let _cdr = CdrGenerator::start(&client, JsonLinesSink::new(file));
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod models;
+pub mod sinks;
+
+use crate::apis::channels::models::{Channel, ChannelState, DialStatus, HangupCause};
+use crate::apis::ids::{BridgeId, ChannelId};
+use crate::ws::models::Event;
+use crate::AriClient;
+use chrono::{DateTime, Utc};
+use models::CallDetailRecord;
+use sinks::CdrSink;
+use std::collections::HashMap;
+use std::sync::mpsc;
+use tokio::sync::broadcast;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, warn};
+
+/// Writes a call detail record for every channel destroyed.
+///
+/// The records are written by a dedicated thread, so that a slow sink never blocks the event
+/// loop. The generator stops when dropped. Channels already existing when it starts are
+/// recorded from their creation time, but what happened to them before is unknown.
+pub struct CdrGenerator {
+    _watcher: DropGuard,
+}
+
+impl CdrGenerator {
+    /// Starts recording the channels from the events received by the client.
+    pub fn start(client: &AriClient, mut sink: impl CdrSink + 'static) -> Self {
+        let stop_signal = CancellationToken::new();
+
+        let (records, received) = mpsc::channel::<CallDetailRecord>();
+        std::thread::spawn(move || {
+            for record in received {
+                if let Err(e) = sink.write(&record) {
+                    warn!(
+                        "unable to write the record of channel {}: {}",
+                        record.channel_id, e
+                    );
+                }
+            }
+        });
+
+        tokio::spawn(watch(records, client.subscribe(), stop_signal.clone()));
+
+        CdrGenerator {
+            _watcher: stop_signal.drop_guard(),
+        }
+    }
+}
+
+async fn watch(
+    records: mpsc::Sender<CallDetailRecord>,
+    mut events: broadcast::Receiver<Event>,
+    stop_signal: CancellationToken,
+) {
+    let mut recorder = Recorder::default();
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            event = crate::client::next_event(&mut events) => match event {
+                Ok(event) => {
+                    if let Some(record) = recorder.apply(&event) {
+                        if records.send(record).is_err() {
+                            warn!("call detail records stopped: the writer stopped");
+                            break;
+                        }
+                    }
+                }
+                Err(e) => {
+                    debug!("call detail records stopped: {}", e);
+                    break;
+                }
+            },
+        }
+    }
+}
+
+/// A channel being recorded.
+#[derive(Debug)]
+struct Open {
+    channel: Channel,
+    context: String,
+    exten: String,
+    answer: Option<DateTime<Utc>>,
+    dial_status: Option<DialStatus>,
+    bridges: Vec<BridgeId>,
+}
+
+/// The channels being recorded, updated from the events.
+#[derive(Debug, Default)]
+struct Recorder {
+    channels: HashMap<ChannelId, Open>,
+}
+
+impl Recorder {
+    /// Updates the channels from an event, returning the record of the channel it destroyed.
+    fn apply(&mut self, event: &Event) -> Option<CallDetailRecord> {
+        let at = event.timestamp().unwrap_or_else(Utc::now);
+
+        let snapshot = match event {
+            Event::ChannelCreated(e) => Some(&e.data.channel),
+            Event::ChannelStateChange(e) => Some(&e.data.channel),
+            Event::ChannelCallerId(e) => Some(&e.data.channel),
+            Event::ChannelConnectedLine(e) => Some(&e.data.channel),
+            Event::ChannelDialplan(e) => Some(&e.data.channel),
+            Event::ChannelHangupRequest(e) => Some(&e.data.channel),
+            Event::StasisStart(e) => Some(&e.data.channel),
+            Event::StasisEnd(e) => Some(&e.data.channel),
+            _ => None,
+        };
+        if let Some(channel) = snapshot {
+            self.observe(channel, at);
+        }
+
+        match event {
+            Event::ChannelEnteredBridge(e) => {
+                if let Some(channel) = &e.data.channel {
+                    let open = self.observe(channel, at);
+                    if !open.bridges.contains(&e.data.bridge.id) {
+                        open.bridges.push(e.data.bridge.id.clone());
+                    }
+                }
+            }
+            // An empty status means the dial is in progress.
+            Event::Dial(e) if e.data.dialstatus != DialStatus::Pending => {
+                for channel in [&e.data.caller, &e.data.peer].into_iter().flatten() {
+                    self.observe(channel, at).dial_status = Some(e.data.dialstatus.clone());
+                }
+            }
+            Event::ChannelDestroyed(e) => {
+                self.observe(&e.data.channel, at);
+                let open = self.channels.remove(&e.data.channel.id)?;
+                return Some(open.close(at, e.data.cause, &e.data.cause_txt));
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    /// Refreshes the snapshot of a channel, opening its record when first seen.
+    fn observe(&mut self, channel: &Channel, at: DateTime<Utc>) -> &mut Open {
+        let open = self
+            .channels
+            .entry(channel.id.clone())
+            .or_insert_with(|| Open {
+                channel: channel.clone(),
+                context: channel.dialplan.context.clone(),
+                exten: channel.dialplan.extension.clone(),
+                answer: None,
+                dial_status: None,
+                bridges: vec![],
+            });
+
+        if open.answer.is_none() && channel.state == ChannelState::Up {
+            open.answer = Some(at);
+        }
+        open.channel = channel.clone();
+        open
+    }
+}
+
+impl Open {
+    fn close(self, end: DateTime<Utc>, cause: HangupCause, cause_txt: &str) -> CallDetailRecord {
+        let start = self.channel.creation_time;
+        CallDetailRecord {
+            channel_id: self.channel.id,
+            channel_name: self.channel.name,
+            caller: self.channel.caller,
+            connected: self.channel.connected,
+            account_code: self.channel.account_code,
+            context: self.context,
+            exten: self.exten,
+            start,
+            answer: self.answer,
+            end,
+            duration: (end - start).num_seconds(),
+            billsec: self.answer.map_or(0, |answer| (end - answer).num_seconds()),
+            dial_status: self.dial_status,
+            cause,
+            cause_txt: cause_txt.to_string(),
+            bridges: self.bridges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: serde_json::Value, at: &str) -> Event {
+        let mut json = json;
+        json["application"] = "test".into();
+        json["timestamp"] = format!("2025-01-07T22:{at}.000+0100").into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn channel(state: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "1736284349.1",
+            "name": "PJSIP/1000-00000001",
+            "state": state,
+            "caller": { "name": "John", "number": "1000" },
+            "connected": { "name": "", "number": "" },
+            "accountcode": "",
+            "dialplan": { "context": "default", "exten": "100", "priority": 1, "app_name": "", "app_data": "" },
+            "creationtime": "2025-01-07T22:12:29.000+0100",
+            "language": "en"
+        })
+    }
+
+    #[test]
+    fn test_record_answered_channel() {
+        let mut recorder = Recorder::default();
+        let events = [
+            event(
+                serde_json::json!({ "type": "ChannelCreated", "channel": channel("Ring") }),
+                "12:29",
+            ),
+            event(
+                serde_json::json!({ "type": "ChannelStateChange", "channel": channel("Up") }),
+                "12:32",
+            ),
+            event(
+                serde_json::json!({
+                    "type": "ChannelEnteredBridge",
+                    "bridge": {
+                        "id": "b1", "technology": "simple_bridge", "bridge_type": "mixing",
+                        "bridge_class": "stasis", "creator": "Stasis", "name": "",
+                        "channels": ["1736284349.1"], "creationtime": "2025-01-07T22:12:30.000+0100"
+                    },
+                    "channel": channel("Up"),
+                }),
+                "12:33",
+            ),
+        ];
+        for event in &events {
+            assert!(recorder.apply(event).is_none());
+        }
+
+        let record = recorder
+            .apply(&event(
+                serde_json::json!({
+                    "type": "ChannelDestroyed",
+                    "cause": 16,
+                    "cause_txt": "Normal Clearing",
+                    "channel": channel("Up"),
+                }),
+                "13:32",
+            ))
+            .unwrap();
+
+        assert_eq!(record.caller.number, "1000");
+        assert_eq!(record.exten, "100");
+        assert_eq!(record.duration, 63);
+        assert_eq!(record.billsec, 60);
+        assert_eq!(record.bridges, vec!["b1"]);
+        assert_eq!(record.cause, HangupCause::NormalClearing);
+        assert!(recorder.channels.is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::apis::channels::models::{CallerId, DialStatus, HangupCause};
+use crate::apis::ids::{BridgeId, ChannelId};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// CallDetailRecord : Summary of the life of a channel, as seen by the application.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct CallDetailRecord {
+    /// Id of the channel.
+    pub channel_id: ChannelId,
+    /// Name of the channel (e.g. `PJSIP/1000-00000001`).
+    pub channel_name: String,
+    /// Caller ID of the channel when it was destroyed.
+    pub caller: CallerId,
+    /// Connected line of the channel when it was destroyed.
+    pub connected: CallerId,
+    /// Account code of the channel.
+    pub account_code: String,
+    /// Dialplan context the channel was first seen in.
+    pub context: String,
+    /// Dialplan extension the channel was first seen in.
+    pub exten: String,
+    /// When the channel was created.
+    pub start: DateTime<Utc>,
+    /// When the channel was answered, if it was.
+    pub answer: Option<DateTime<Utc>>,
+    /// When the channel was destroyed.
+    pub end: DateTime<Utc>,
+    /// Seconds between the creation and the destruction of the channel.
+    pub duration: i64,
+    /// Seconds between the answer and the destruction of the channel, `0` if it was never answered.
+    pub billsec: i64,
+    /// Last final status of a dial the channel took part in (e.g. `ANSWER`, `BUSY`).
+    pub dial_status: Option<DialStatus>,
+    /// Hangup cause.
+    pub cause: HangupCause,
+    /// Text representation of the hangup cause.
+    pub cause_txt: String,
+    /// Ids of the bridges the channel joined, in order.
+    pub bridges: Vec<BridgeId>,
+}
@@ -0,0 +1,195 @@
+use crate::apis::channels::models::DialStatus;
+use crate::cdr::models::CallDetailRecord;
+use crate::errors::Result;
+use std::io::Write;
+
+/// Destination of the call detail records.
+pub trait CdrSink: Send {
+    /// Writes a record.
+    fn write(&mut self, record: &CallDetailRecord) -> Result<()>;
+}
+
+/// Writes the records as JSON Lines, one JSON object per line.
+pub struct JsonLinesSink<W> {
+    writer: W,
+}
+
+impl<W: Write + Send> JsonLinesSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: Write + Send> CdrSink for JsonLinesSink<W> {
+    fn write(&mut self, record: &CallDetailRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Columns of the CSV output.
+const CSV_HEADER: [&str; 18] = [
+    "channel_id",
+    "channel_name",
+    "caller_name",
+    "caller_number",
+    "connected_name",
+    "connected_number",
+    "account_code",
+    "context",
+    "exten",
+    "start",
+    "answer",
+    "end",
+    "duration",
+    "billsec",
+    "dial_status",
+    "cause",
+    "cause_txt",
+    "bridges",
+];
+
+/// Writes the records as CSV, with a header line.
+///
+/// Timestamps are written in RFC 3339, and the bridges joined are `;`-separated.
+pub struct CsvSink<W> {
+    writer: W,
+    header_written: bool,
+}
+
+impl<W: Write + Send> CsvSink<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            header_written: false,
+        }
+    }
+
+    /// Creates a sink appending to a file that already has a header line.
+    pub fn without_header(writer: W) -> Self {
+        Self {
+            writer,
+            header_written: true,
+        }
+    }
+
+    fn write_row<'a>(&mut self, fields: impl Iterator<Item = &'a str>) -> Result<()> {
+        let line = fields.map(escape).collect::<Vec<_>>().join(",");
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\r\n")?;
+        Ok(())
+    }
+}
+
+impl<W: Write + Send> CdrSink for CsvSink<W> {
+    fn write(&mut self, record: &CallDetailRecord) -> Result<()> {
+        if !self.header_written {
+            self.write_row(CSV_HEADER.into_iter())?;
+            self.header_written = true;
+        }
+
+        let answer = record.answer.map(|at| at.to_rfc3339()).unwrap_or_default();
+        let fields = [
+            record.channel_id.to_string(),
+            record.channel_name.clone(),
+            record.caller.name.clone(),
+            record.caller.number.clone(),
+            record.connected.name.clone(),
+            record.connected.number.clone(),
+            record.account_code.clone(),
+            record.context.clone(),
+            record.exten.clone(),
+            record.start.to_rfc3339(),
+            answer,
+            record.end.to_rfc3339(),
+            record.duration.to_string(),
+            record.billsec.to_string(),
+            record
+                .dial_status
+                .as_ref()
+                .map_or_else(String::new, DialStatus::to_string),
+            record.cause.code().to_string(),
+            record.cause_txt.clone(),
+            record.bridges.join(";"),
+        ];
+        self.write_row(fields.iter().map(String::as_str))?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, a quote or a line break (RFC 4180).
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::channels::models::{CallerId, DialStatus, HangupCause};
+    use chrono::{DateTime, Utc};
+
+    fn record() -> CallDetailRecord {
+        let at = |ts| DateTime::<Utc>::from_timestamp(ts, 0).unwrap();
+        CallDetailRecord {
+            channel_id: "1736284349.1".into(),
+            channel_name: "PJSIP/1000-00000001".to_string(),
+            caller: CallerId {
+                name: "Doe, John".to_string(),
+                number: "1000".to_string(),
+            },
+            connected: CallerId::default(),
+            account_code: String::new(),
+            context: "default".to_string(),
+            exten: "100".to_string(),
+            start: at(1736284349),
+            answer: Some(at(1736284352)),
+            end: at(1736284412),
+            duration: 63,
+            billsec: 60,
+            dial_status: Some(DialStatus::Answer),
+            cause: HangupCause::NormalClearing,
+            cause_txt: "Normal Clearing".to_string(),
+            bridges: vec!["b1".into(), "b2".into()],
+        }
+    }
+
+    #[test]
+    fn test_csv_sink() {
+        let mut output = vec![];
+        let mut sink = CsvSink::new(&mut output);
+        sink.write(&record()).unwrap();
+        sink.write(&record()).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("channel_id,channel_name,caller_name,"));
+        assert!(lines[0].ends_with(",cause,cause_txt,bridges"));
+        assert_eq!(
+            lines[1],
+            "1736284349.1,PJSIP/1000-00000001,\"Doe, John\",1000,,,,default,100,\
+             2025-01-07T21:12:29+00:00,2025-01-07T21:12:32+00:00,2025-01-07T21:13:32+00:00,\
+             63,60,ANSWER,16,Normal Clearing,b1;b2"
+        );
+    }
+
+    #[test]
+    fn test_json_lines_sink() {
+        let mut output = vec![];
+        JsonLinesSink::new(&mut output).write(&record()).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with('\n'));
+        let json: serde_json::Value = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(json["caller"]["name"], "Doe, John");
+        assert_eq!(json["billsec"], 60);
+        assert_eq!(json["bridges"], serde_json::json!(["b1", "b2"]));
+    }
+}
@@ -5,7 +5,7 @@ use std::future::Future;
 use std::ops::Deref;
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 type Handler = Arc<
     dyn Fn(
@@ -16,6 +16,9 @@ type Handler = Arc<
         + Sync,
 >;
 
+/// Number of events buffered for each subscriber before it starts lagging behind.
+const EVENTS_CAPACITY: usize = 1024;
+
 /// `AriClient` is a client for interacting with the Asterisk REST Interface (ARI).
 /// It manages the connection to the ARI and handles events.
 #[derive(Clone)]
@@ -23,20 +26,84 @@ pub struct AriClient {
     client: Arc<apis::client::Client>,
     ws: Arc<tokio::sync::Mutex<ws::client::Client>>,
     event_handlers: Arc<RwLock<HashMap<String, Handler>>>,
+    events: tokio::sync::broadcast::Sender<ws::models::Event>,
+    connection_states: tokio::sync::broadcast::Sender<ws::models::ConnectionState>,
+    application: Arc<RwLock<Option<String>>>,
+    journal: Option<ws::journal::Journal>,
+    /// Kinds of events allowed besides the ones with a handler, when filtering the events.
+    event_filter: Option<Vec<ws::models::EventKind>>,
+    subscriptions: crate::subscriptions::Subscriptions,
 }
 
 impl AriClient {
     /// Creates a new `AriClient` with the given configuration.
     pub fn with_config(config: crate::config::Config) -> Self {
+        let ws = ws::client::Client::with_config(config.clone());
         AriClient {
-            client: Arc::new(apis::client::Client::with_config(config.clone())),
-            ws: Arc::new(tokio::sync::Mutex::new(ws::client::Client::with_config(
-                config,
-            ))),
+            client: Arc::new(apis::client::Client::with_config(config)),
+            connection_states: ws.states(),
+            ws: Arc::new(tokio::sync::Mutex::new(ws)),
             event_handlers: Arc::new(RwLock::new(HashMap::new())),
+            events: tokio::sync::broadcast::channel(EVENTS_CAPACITY).0,
+            application: Arc::new(RwLock::new(None)),
+            journal: None,
+            event_filter: None,
+            subscriptions: Default::default(),
         }
     }
 
+    /// Records every frame received from Asterisk into a journal, to replay it later with
+    /// [`AriClient::replay`]. Takes effect on the next call to `start`.
+    pub fn with_journal(mut self, journal: ws::journal::Journal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// Asks Asterisk to only send the events with a registered handler, plus the given kinds
+    /// of events, to cut the WebSocket traffic. The filter is pushed by `start`, from the
+    /// handlers registered at that time.
+    ///
+    /// The subscribers only receive the events let through, so the kinds they wait for (e.g.
+    /// `PlaybackFinished` for [`AriClient::ivr`]) must be given here.
+    pub fn with_event_filter(
+        mut self,
+        also_allowed: impl IntoIterator<Item = ws::models::EventKind>,
+    ) -> Self {
+        self.event_filter = Some(also_allowed.into_iter().collect());
+        self
+    }
+
+    /// Sends the REST requests through a transport instead of `reqwest`, e.g. to record them
+    /// or to answer them from a mock. The WebSocket connection is not affected.
+    pub fn with_transport(
+        mut self,
+        transport: impl apis::transport::AriTransport + 'static,
+    ) -> Self {
+        let client = apis::client::Client::with_config(self.client.config.clone());
+        self.client = Arc::new(client.with_transport(transport));
+        self
+    }
+
+    /// Subscribes to every event received by the client.
+    ///
+    /// Unlike the `on_*` handlers, any number of subscribers can observe the same event.
+    /// Events are delivered to subscribers before the registered handler is called, so
+    /// long-running flows waiting on a receiver must be spawned instead of awaited
+    /// inside a handler.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ws::models::Event> {
+        self.events.subscribe()
+    }
+
+    /// Subscribes to the changes of the WebSocket connection state.
+    ///
+    /// Events sent by Asterisk while the connection is down are lost, so anything built
+    /// from the events should be refreshed on `ConnectionState::Reconnected`.
+    pub fn connection_states(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<ws::models::ConnectionState> {
+        self.connection_states.subscribe()
+    }
+
     /// Registers a handler for unknown events.
     pub fn on_unknown_event<F, Fut>(&mut self, handler: F) -> &mut Self
     where
@@ -68,17 +135,116 @@ impl AriClient {
         &mut self,
         application_name: impl Into<String>,
     ) -> crate::errors::Result<()> {
-        let mut stream = self
-            .ws
-            .lock()
-            .await
-            .connect(ws::params::ListenRequest::new(application_name))
+        let application_name = application_name.into();
+        let states = self.connection_states();
+        let stream = {
+            let mut ws = self.ws.lock().await;
+            ws.set_journal(self.journal.clone());
+            ws.connect(ws::params::ListenRequest::new(application_name.clone()))
+                .await?
+        };
+        *self.application.write().unwrap() = Some(application_name.clone());
+
+        self.subscriptions.set_allowed(self.allowed_events());
+        self.subscriptions
+            .watch(self.client.clone(), application_name, states)
             .await?;
 
+        tokio::task::spawn(self.dispatch(stream));
+
+        Ok(())
+    }
+
+    /// The kinds of events with a handler, if the events are to be filtered.
+    fn allowed_events(&self) -> Option<Vec<ws::models::EventKind>> {
+        let mut kinds: Vec<ws::models::EventKind> = self.event_filter.clone()?;
+        kinds.extend(
+            self.event_handlers
+                .read()
+                .unwrap()
+                .keys()
+                .filter_map(|key| ws::models::EventKind::from_name(key)),
+        );
+        kinds.sort();
+        kinds.dedup();
+        Some(kinds)
+    }
+
+    /// Subscribes the application to the events of a resource, e.g. an endpoint or a device
+    /// state, when the client is started or right away if it already is.
+    ///
+    /// The client remembers the subscription, and subscribes again when it finds it lost
+    /// after reconnecting, e.g. because Asterisk restarted.
+    pub async fn subscribe_to(
+        &self,
+        source: apis::applications::models::EventSource,
+    ) -> crate::errors::Result<()> {
+        self.subscriptions.add(source.clone());
+        let Some(application) = self.application() else {
+            return Ok(());
+        };
+        self.client
+            .applications()
+            .subscribe(apis::applications::params::SubscribeRequest::new(
+                application.into(),
+                [source.to_string()],
+            ))
+            .await
+            .map(drop)
+    }
+
+    /// Unsubscribes the application from the events of a resource subscribed to with
+    /// [`AriClient::subscribe_to`].
+    pub async fn unsubscribe_from(
+        &self,
+        source: apis::applications::models::EventSource,
+    ) -> crate::errors::Result<()> {
+        self.subscriptions.remove(&source);
+        let Some(application) = self.application() else {
+            return Ok(());
+        };
+        self.client
+            .applications()
+            .unsubscribe(apis::applications::params::UnSubscribeRequest::new(
+                application.into(),
+                [source.to_string()],
+            ))
+            .await
+            .map(drop)
+    }
+
+    /// The event sources subscribed to with [`AriClient::subscribe_to`].
+    pub fn subscriptions(&self) -> Vec<apis::applications::models::EventSource> {
+        self.subscriptions.sources()
+    }
+
+    /// Feeds the events of a journal to the subscribers and the handlers, as if they came
+    /// from Asterisk, and returns once the journal is exhausted.
+    ///
+    /// The REST calls made by the handlers still reach the configured Asterisk.
+    pub async fn replay(
+        &mut self,
+        application_name: impl Into<String>,
+        source: ws::journal::ReplaySource,
+    ) -> crate::errors::Result<()> {
+        *self.application.write().unwrap() = Some(application_name.into());
+        self.dispatch(source.into_stream()).await;
+        Ok(())
+    }
+
+    /// Delivers the events of a stream to the subscribers, then to the registered handler.
+    fn dispatch(
+        &self,
+        mut stream: impl tokio_stream::Stream<Item = ws::models::Event> + Unpin + Send + 'static,
+    ) -> impl Future<Output = ()> + Send + 'static {
         let event_handlers = self.event_handlers.clone();
+        let events = self.events.clone();
         let client = self.client.clone();
-        tokio::task::spawn(async move {
+        async move {
             while let Some(event) = stream.next().await {
+                // Sending only fails when nobody is subscribed.
+                let _ = events.send(event.clone());
+
                 // Get a read lock to safely access the handlers.
                 let maybe_handler = {
                     let handlers = event_handlers.read().unwrap();
@@ -99,20 +265,34 @@ impl AriClient {
                     );
                 }
             }
-        });
-
-        Ok(())
+        }
     }
 
     /// Stops the ARI client.
     pub async fn stop(&mut self) -> Result<(), crate::errors::AriError> {
+        self.subscriptions.stop();
         self.ws.lock().await.disconnect().await
     }
 
+    /// Returns the name of the Stasis application the client was started with.
+    pub fn application(&self) -> Option<String> {
+        self.application.read().unwrap().clone()
+    }
+
     /// Returns a reference to the API client.
     pub fn client(&self) -> &apis::client::Client {
         &self.client
     }
+
+    /// Returns an instance of the `Calls` helpers.
+    pub fn calls(&self) -> crate::calls::Calls<'_> {
+        crate::calls::Calls::new(self)
+    }
+
+    /// Returns an instance of the `Ivr` runner.
+    pub fn ivr(&self) -> crate::ivr::Ivr<'_> {
+        crate::ivr::Ivr::new(self)
+    }
 }
 
 impl Deref for AriClient {
@@ -122,6 +302,30 @@ impl Deref for AriClient {
     }
 }
 
+/// Waits for the next event of a subscription, skipping over the events missed when lagging behind.
+pub(crate) async fn next_event(
+    events: &mut tokio::sync::broadcast::Receiver<ws::models::Event>,
+) -> crate::errors::Result<ws::models::Event> {
+    loop {
+        match events.recv().await {
+            Ok(event) => return Ok(event),
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                warn!("event subscriber lagged behind, {} events skipped", skipped);
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                return Err(crate::errors::AriError::Internal(
+                    "event stream closed".to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Generates a random identifier for the resources created by the client (channels, bridges, playbacks...).
+pub(crate) fn unique_id(prefix: &str) -> String {
+    format!("{prefix}-{:016x}", rand::random::<u64>())
+}
+
 /// Macro to create event handler methods for specific events.
 macro_rules! create_event_handler {
     ($($event_name:ident => $event_variant:ident),*) => {
@@ -157,6 +361,8 @@ macro_rules! create_event_handler {
 create_event_handler!(
     on_application_move_failed => ApplicationMoveFailed,
     on_application_replaced => ApplicationReplaced,
+    on_application_registered => ApplicationRegistered,
+    on_application_unregistered => ApplicationUnregistered,
     on_bridge_attended_transfer => BridgeAttendedTransfer,
     on_bridge_blind_transfer => BridgeBlindTransfer,
     on_bridge_created => BridgeCreated,
@@ -173,10 +379,12 @@ create_event_handler!(
     on_channel_hangup_request => ChannelHangupRequest,
     on_channel_hold => ChannelHold,
     on_channel_left_bridge => ChannelLeftBridge,
+    on_channel_rtp_statistics => ChannelRtpStatistics,
     on_channel_state_change => ChannelStateChange,
     on_channel_talking_finished => ChannelTalkingFinished,
     on_channel_talking_started => ChannelTalkingStarted,
     on_channel_tone_detected => ChannelToneDetected,
+    on_channel_transfer => ChannelTransfer,
     on_channel_unhold => ChannelUnhold,
     on_channel_user_event => ChannelUserEvent,
     on_channel_var_set => ChannelVarSet,
@@ -194,6 +402,7 @@ create_event_handler!(
     on_recording_failed => RecordingFailed,
     on_recording_finished => RecordingFinished,
     on_recording_started => RecordingStarted,
+    on_rest_response => RestResponse,
     on_stasis_end => StasisEnd,
     on_stasis_start => StasisStart,
     on_text_message_received => TextMessageReceived
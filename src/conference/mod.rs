@@ -0,0 +1,570 @@
+//! Conference rooms built on mixing bridges.
+//!
+//! A [`Conference`] owns a mixing bridge and keeps track of its participants from the
+//! channel events: who is in the room, who is muted and who is talking. Every change is
+//! published as a [`models::ConferenceEvent`], to keep user interfaces up to date.
+
+pub mod models;
+pub mod params;
+
+use crate::apis::bridges::models::BridgeType;
+use crate::apis::bridges::params::{
+    AddChannelRequest, CreateWithIdRequest, PlayRequest, RecordRequest,
+};
+use crate::apis::channels::models::Channel;
+use crate::apis::ids::{BridgeId, ChannelId, RecordingName};
+use crate::apis::params::Direction;
+use crate::errors::{AriError, Result};
+use crate::ws::models::Event;
+use crate::AriClient;
+use models::{ConferenceEvent, Participant, Role};
+use params::ConferenceOptions;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::debug;
+
+/// Number of conference events buffered for each subscriber.
+const EVENTS_CAPACITY: usize = 256;
+
+/// A conference room.
+///
+/// The room stops tracking its bridge when dropped; call [`Conference::destroy`] to also
+/// destroy the bridge.
+pub struct Conference {
+    client: AriClient,
+    bridge_id: BridgeId,
+    options: ConferenceOptions,
+    room: Arc<Mutex<Room>>,
+    events: broadcast::Sender<ConferenceEvent>,
+    _watcher: DropGuard,
+}
+
+impl Conference {
+    /// Creates the mixing bridge of a new room and starts tracking it.
+    pub async fn create(client: &AriClient, options: ConferenceOptions) -> Result<Self> {
+        let bridge_id = BridgeId::new(crate::client::unique_id("conference"));
+        let room = Arc::new(Mutex::new(Room::new(&bridge_id)));
+        let events = broadcast::channel(EVENTS_CAPACITY).0;
+        let stop_signal = CancellationToken::new();
+
+        // Subscribe before creating the bridge, so no event can be missed.
+        let subscription = client.subscribe();
+        client
+            .bridges()
+            .create_with_id(
+                CreateWithIdRequest::new(&bridge_id)
+                    .with_type(BridgeType::Mixing)
+                    .with_name(&options.name),
+            )
+            .await?;
+
+        tokio::spawn(watch(
+            client.clone(),
+            room.clone(),
+            events.clone(),
+            options.leave_announcement.clone(),
+            subscription,
+            stop_signal.clone(),
+        ));
+
+        Ok(Conference {
+            client: client.clone(),
+            bridge_id,
+            options,
+            room,
+            events,
+            _watcher: stop_signal.drop_guard(),
+        })
+    }
+
+    /// Id of the bridge of the room.
+    pub fn bridge_id(&self) -> &BridgeId {
+        &self.bridge_id
+    }
+
+    /// Subscribes to the changes of the room.
+    pub fn subscribe(&self) -> broadcast::Receiver<ConferenceEvent> {
+        self.events.subscribe()
+    }
+
+    /// The participants of the room, in joining order.
+    pub fn participants(&self) -> Vec<Participant> {
+        let mut participants: Vec<_> = self
+            .room
+            .lock()
+            .unwrap()
+            .participants
+            .values()
+            .cloned()
+            .collect();
+        participants.sort_by_key(|p| p.joined_at);
+        participants
+    }
+
+    /// Whether only moderators can join the room.
+    pub fn is_locked(&self) -> bool {
+        self.room.lock().unwrap().locked
+    }
+
+    /// Adds a channel to the room.
+    ///
+    /// Fails with `AriError::ConferenceLocked` if the room is locked and the channel is not a moderator.
+    pub async fn join(&self, channel: &Channel, role: Role) -> Result<()> {
+        if role != Role::Moderator && self.is_locked() {
+            return Err(AriError::ConferenceLocked(self.options.name.clone()));
+        }
+
+        if self.options.talk_detection {
+            self.client
+                .channels()
+                .set_variable(
+                    &channel.id,
+                    "TALK_DETECT(set)",
+                    self.options.talk_thresholds.clone().unwrap_or_default(),
+                )
+                .await?;
+        }
+
+        self.client
+            .bridges()
+            .add_channel(AddChannelRequest::new(&self.bridge_id, &channel.id))
+            .await?;
+
+        let muted = role == Role::Listener;
+        if muted {
+            self.client
+                .channels()
+                .mute(&channel.id, Direction::In)
+                .await?;
+        }
+
+        let name = if channel.caller.name.is_empty() {
+            channel.caller.number.clone()
+        } else {
+            channel.caller.name.clone()
+        };
+        let participant = Participant {
+            channel_id: channel.id.clone(),
+            name,
+            role,
+            muted,
+            talking: false,
+            joined_at: chrono::Utc::now(),
+        };
+        self.room
+            .lock()
+            .unwrap()
+            .participants
+            .insert(channel.id.clone(), participant.clone());
+        let _ = self.events.send(ConferenceEvent::Joined(participant));
+
+        if let Some(announcement) = &self.options.join_announcement {
+            announce(&self.client, &self.bridge_id, announcement).await;
+        }
+
+        Ok(())
+    }
+
+    /// Removes a channel from the room. The channel stays in the application.
+    pub async fn leave(&self, channel_id: impl Into<ChannelId> + Send) -> Result<()> {
+        // The participant is removed once the channel left the bridge.
+        self.client
+            .bridges()
+            .remove_channel(&self.bridge_id, channel_id)
+            .await
+    }
+
+    /// Mutes the audio coming from a participant.
+    pub async fn mute(&self, channel_id: impl Into<ChannelId> + Send) -> Result<()> {
+        self.set_muted(channel_id.into(), true).await
+    }
+
+    /// Unmutes the audio coming from a participant.
+    pub async fn unmute(&self, channel_id: impl Into<ChannelId> + Send) -> Result<()> {
+        self.set_muted(channel_id.into(), false).await
+    }
+
+    async fn set_muted(&self, channel_id: ChannelId, muted: bool) -> Result<()> {
+        let channels = self.client.channels();
+        if muted {
+            channels.mute(&channel_id, Direction::In).await?;
+        } else {
+            channels.unmute(&channel_id, Direction::In).await?;
+        }
+
+        if let Some(participant) = self.room.lock().unwrap().participants.get_mut(&channel_id) {
+            participant.muted = muted;
+        }
+        let _ = self.events.send(if muted {
+            ConferenceEvent::Muted(channel_id)
+        } else {
+            ConferenceEvent::Unmuted(channel_id)
+        });
+
+        Ok(())
+    }
+
+    /// Locks the room: only moderators can join.
+    pub fn lock(&self) {
+        self.room.lock().unwrap().locked = true;
+        let _ = self.events.send(ConferenceEvent::Locked);
+    }
+
+    /// Unlocks the room.
+    pub fn unlock(&self) {
+        self.room.lock().unwrap().locked = false;
+        let _ = self.events.send(ConferenceEvent::Unlocked);
+    }
+
+    /// Starts recording the mixed audio of the room.
+    pub async fn start_recording(&self, name: impl Into<RecordingName> + Send) -> Result<()> {
+        let name = name.into();
+        self.client
+            .bridges()
+            .record(RecordRequest::new(
+                &self.bridge_id,
+                &name,
+                &self.options.recording_format,
+            ))
+            .await?;
+
+        self.room.lock().unwrap().recording = Some(name.clone());
+        let _ = self.events.send(ConferenceEvent::RecordingStarted(name));
+        Ok(())
+    }
+
+    /// Stops and stores the recording of the room, if any.
+    pub async fn stop_recording(&self) -> Result<()> {
+        let Some(name) = self.room.lock().unwrap().recording.take() else {
+            return Ok(());
+        };
+
+        self.client.recordings().live().stop(&name).await?;
+        let _ = self.events.send(ConferenceEvent::RecordingStopped(name));
+        Ok(())
+    }
+
+    /// Destroys the bridge of the room. The participants stay in the application.
+    pub async fn destroy(self) -> Result<()> {
+        self.client.bridges().delete(&self.bridge_id).await
+    }
+}
+
+/// Plays a media to the whole room. Failures are only logged.
+async fn announce(client: &AriClient, bridge_id: &BridgeId, media: &str) {
+    if let Err(e) = client
+        .bridges()
+        .play(PlayRequest::new(bridge_id, media))
+        .await
+    {
+        debug!(
+            "unable to play {} on conference {}: {}",
+            media, bridge_id, e
+        );
+    }
+}
+
+/// Applies the events to the room until the bridge is destroyed or the conference dropped.
+async fn watch(
+    client: AriClient,
+    room: Arc<Mutex<Room>>,
+    events: broadcast::Sender<ConferenceEvent>,
+    leave_announcement: Option<String>,
+    mut subscription: broadcast::Receiver<Event>,
+    stop_signal: CancellationToken,
+) {
+    loop {
+        let event = tokio::select! {
+            _ = stop_signal.cancelled() => return,
+            event = crate::client::next_event(&mut subscription) => match event {
+                Ok(event) => event,
+                Err(_) => return,
+            },
+        };
+
+        let (bridge_id, changes) = {
+            let mut room = room.lock().unwrap();
+            (room.bridge_id.clone(), room.apply(&event))
+        };
+
+        for change in changes {
+            let ended = change == ConferenceEvent::Ended;
+            if let (ConferenceEvent::Left(_), Some(announcement)) = (&change, &leave_announcement) {
+                announce(&client, &bridge_id, announcement).await;
+            }
+
+            let _ = events.send(change);
+            if ended {
+                return;
+            }
+        }
+    }
+}
+
+/// State of a room, as seen from the events.
+#[derive(Debug)]
+struct Room {
+    bridge_id: BridgeId,
+    participants: HashMap<ChannelId, Participant>,
+    locked: bool,
+    recording: Option<RecordingName>,
+}
+
+impl Room {
+    fn new(bridge_id: &BridgeId) -> Self {
+        Room {
+            bridge_id: bridge_id.clone(),
+            participants: HashMap::new(),
+            locked: false,
+            recording: None,
+        }
+    }
+
+    /// Updates the room from an event, returning the resulting changes.
+    fn apply(&mut self, event: &Event) -> Vec<ConferenceEvent> {
+        match event {
+            Event::ChannelLeftBridge(e) if e.data.bridge.id == self.bridge_id => self
+                .participants
+                .remove(&e.data.channel.id)
+                .map(ConferenceEvent::Left)
+                .into_iter()
+                .collect(),
+            Event::ChannelTalkingStarted(e) => {
+                match self.participants.get_mut(&e.data.channel.id) {
+                    Some(participant) => {
+                        participant.talking = true;
+                        vec![ConferenceEvent::TalkingStarted(e.data.channel.id.clone())]
+                    }
+                    None => vec![],
+                }
+            }
+            Event::ChannelTalkingFinished(e) => match self.participants.get_mut(&e.data.channel.id)
+            {
+                Some(participant) => {
+                    participant.talking = false;
+                    vec![ConferenceEvent::TalkingFinished(
+                        e.data.channel.id.clone(),
+                        e.data.duration,
+                    )]
+                }
+                None => vec![],
+            },
+            Event::BridgeDestroyed(e) if e.data.bridge.id == self.bridge_id => {
+                let mut changes: Vec<_> = self
+                    .participants
+                    .drain()
+                    .map(|(_, participant)| ConferenceEvent::Left(participant))
+                    .collect();
+                changes.push(ConferenceEvent::Ended);
+                changes
+            }
+            _ => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::bridges::models::Bridge;
+    use crate::ws::models::{
+        BaseEvent, BridgeDestroyed, ChannelLeftBridge, ChannelTalkingFinished,
+        ChannelTalkingStarted,
+    };
+
+    fn base<T>(data: T) -> BaseEvent<T> {
+        BaseEvent {
+            asterisk_id: None,
+            application: "conference".to_string(),
+            timestamp: chrono::Utc::now(),
+            data,
+        }
+    }
+
+    fn channel(id: &str) -> Channel {
+        Channel {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    fn bridge(id: &str) -> Bridge {
+        Bridge {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    fn room() -> Room {
+        let mut room = Room::new(&"room-1".into());
+        room.participants.insert(
+            "alice".into(),
+            Participant {
+                channel_id: "alice".into(),
+                name: "Alice".to_string(),
+                role: Role::Moderator,
+                muted: false,
+                talking: false,
+                joined_at: chrono::Utc::now(),
+            },
+        );
+        room
+    }
+
+    #[test]
+    fn test_room_talk_detection() {
+        let mut room = room();
+
+        let changes = room.apply(&Event::ChannelTalkingStarted(base(ChannelTalkingStarted {
+            channel: channel("alice"),
+        })));
+        assert_eq!(
+            changes,
+            vec![ConferenceEvent::TalkingStarted("alice".into())]
+        );
+        assert!(room.participants["alice"].talking);
+
+        let changes = room.apply(&Event::ChannelTalkingFinished(base(
+            ChannelTalkingFinished {
+                channel: channel("alice"),
+                duration: 1200,
+            },
+        )));
+        assert_eq!(
+            changes,
+            vec![ConferenceEvent::TalkingFinished("alice".into(), 1200)]
+        );
+        assert!(!room.participants["alice"].talking);
+
+        // Channels that are not in the room are ignored.
+        let changes = room.apply(&Event::ChannelTalkingStarted(base(ChannelTalkingStarted {
+            channel: channel("bob"),
+        })));
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_room_left_and_destroyed() {
+        let mut room = room();
+
+        let changes = room.apply(&Event::ChannelLeftBridge(base(ChannelLeftBridge {
+            bridge: bridge("other-room"),
+            channel: channel("alice"),
+        })));
+        assert!(changes.is_empty());
+
+        let changes = room.apply(&Event::ChannelLeftBridge(base(ChannelLeftBridge {
+            bridge: bridge("room-1"),
+            channel: channel("alice"),
+        })));
+        assert!(
+            matches!(changes.as_slice(), [ConferenceEvent::Left(p)] if p.channel_id == "alice")
+        );
+        assert!(room.participants.is_empty());
+
+        let mut room = self::room();
+        let changes = room.apply(&Event::BridgeDestroyed(base(BridgeDestroyed {
+            bridge: bridge("room-1"),
+        })));
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[1], ConferenceEvent::Ended);
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod mock_tests {
+    use super::models::{ConferenceEvent, Role};
+    use super::params::ConferenceOptions;
+    use super::Conference;
+    use crate::apis::channels::models::Channel;
+    use crate::testing::MockAriServer;
+    use crate::{AriClient, AriError};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_conference_drives_the_bridge_and_its_channels() {
+        let server = MockAriServer::start().await.unwrap();
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+        let conference = Conference::create(
+            &client,
+            ConferenceOptions::new("room").with_join_announcement("sound:beep"),
+        )
+        .await
+        .unwrap();
+        let mut changes = conference.subscribe();
+        let bridge = format!("/bridges/{}", conference.bridge_id());
+        assert!(server.bridge(conference.bridge_id().as_str()).is_some());
+
+        let channel = |id: &str, endpoint: &str| -> Channel {
+            serde_json::from_value(server.add_channel(id, endpoint, "test").unwrap()).unwrap()
+        };
+        let (alice, bob) = (channel("alice", "PJSIP/1000"), channel("bob", "PJSIP/1001"));
+        conference.join(&alice, Role::Moderator).await.unwrap();
+        conference.join(&bob, Role::Listener).await.unwrap();
+
+        let talk_detect = server.requests_to("POST", "/channels/alice/variable");
+        assert_eq!(talk_detect[0].param("variable"), Some("TALK_DETECT(set)"));
+        let added: Vec<_> = server
+            .requests_to("POST", &format!("{bridge}/addChannel"))
+            .iter()
+            .filter_map(|request| request.param("channel").map(str::to_string))
+            .collect();
+        assert_eq!(added, ["alice", "bob"]);
+        assert_eq!(
+            server.bridge(conference.bridge_id().as_str()).unwrap()["channels"],
+            serde_json::json!(["alice", "bob"])
+        );
+        assert_eq!(
+            server.requests_to("POST", "/channels/bob/mute")[0].param("direction"),
+            Some("in")
+        );
+        assert!(!server.received("POST", "/channels/alice/mute"));
+        assert_eq!(
+            server.requests_to("POST", &format!("{bridge}/play")).len(),
+            2
+        );
+
+        conference.unmute("bob").await.unwrap();
+        conference.mute("alice").await.unwrap();
+        assert!(server.received("DELETE", "/channels/bob/mute"));
+        assert!(server.received("POST", "/channels/alice/mute"));
+
+        // Only moderators join a locked room.
+        conference.lock();
+        let carol = channel("carol", "PJSIP/1002");
+        assert!(matches!(
+            conference.join(&carol, Role::Speaker).await,
+            Err(AriError::ConferenceLocked(_))
+        ));
+        assert!(!server.received("POST", "/channels/carol/variable"));
+        assert_eq!(
+            server
+                .requests_to("POST", &format!("{bridge}/addChannel"))
+                .len(),
+            2
+        );
+        conference.unlock();
+
+        conference.leave("alice").await.unwrap();
+        let removed = server.requests_to("POST", &format!("{bridge}/removeChannel"));
+        assert_eq!(removed[0].param("channel"), Some("alice"));
+        let left = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let ConferenceEvent::Left(participant) = changes.recv().await.unwrap() {
+                    break participant;
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(left.channel_id, "alice");
+        assert_eq!(conference.participants().len(), 1);
+
+        conference.destroy().await.unwrap();
+        assert!(server.received("DELETE", &bridge));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::apis::ids::{ChannelId, RecordingName};
+use chrono::{DateTime, Utc};
+
+/// Participant : A channel taking part in a conference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Participant {
+    /// Id of the participant channel.
+    pub channel_id: ChannelId,
+    /// Caller ID name of the participant, or number when the name is empty.
+    pub name: String,
+    /// Role of the participant in the room.
+    pub role: Role,
+    /// Whether the audio coming from the participant is muted.
+    pub muted: bool,
+    /// Whether the participant is currently talking.
+    pub talking: bool,
+    /// When the participant joined the room.
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Role of a participant in a conference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Role {
+    /// Can join locked rooms and is never muted on join.
+    Moderator,
+    /// Talks and listens.
+    #[default]
+    Speaker,
+    /// Only listens: muted on join.
+    Listener,
+}
+
+/// ConferenceEvent : Changes of a conference, for UI updates.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConferenceEvent {
+    /// A participant joined the room.
+    Joined(Participant),
+    /// A participant left the room.
+    Left(Participant),
+    /// A participant was muted.
+    Muted(ChannelId),
+    /// A participant was unmuted.
+    Unmuted(ChannelId),
+    /// A participant started talking.
+    TalkingStarted(ChannelId),
+    /// A participant stopped talking, after talking for the given milliseconds.
+    TalkingFinished(ChannelId, u32),
+    /// The room was locked: only moderators can join.
+    Locked,
+    /// The room was unlocked.
+    Unlocked,
+    /// The room started being recorded with the given name.
+    RecordingStarted(RecordingName),
+    /// The recording of the room stopped.
+    RecordingStopped(RecordingName),
+    /// The bridge of the room was destroyed.
+    Ended,
+}
@@ -0,0 +1,34 @@
+use derive_new::new;
+use derive_setters::Setters;
+
+#[derive(Clone, Debug, PartialEq, new, Setters)]
+#[setters(prefix = "with_")]
+#[setters(into, strip_option)]
+pub struct ConferenceOptions {
+    /// Name to give to the bridge of the room.
+    #[setters(skip)]
+    #[new(into)]
+    pub(crate) name: String,
+
+    /// Media URI played to the room when a participant joins.
+    #[new(default)]
+    pub(crate) join_announcement: Option<String>,
+
+    /// Media URI played to the room when a participant leaves.
+    #[new(default)]
+    pub(crate) leave_announcement: Option<String>,
+
+    /// Whether to detect talkers, enabling `TALK_DETECT` on the participants.
+    #[new(value = "true")]
+    pub(crate) talk_detection: bool,
+
+    /// `TALK_DETECT(set)` value: `<dsp_silence_threshold>[,<dsp_talking_threshold>]`.
+    ///
+    /// If omitted, the Asterisk defaults are used.
+    #[new(default)]
+    pub(crate) talk_thresholds: Option<String>,
+
+    /// Format of the recordings of the room.
+    #[new(value = r#""wav".to_string()"#)]
+    pub(crate) recording_format: String,
+}
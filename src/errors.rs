@@ -1,4 +1,3 @@
-use reqwest::Error as ReqwError;
 use reqwest::StatusCode;
 use std::error::Error;
 use std::fmt::Display;
@@ -18,26 +17,44 @@ pub enum AriError {
     /// Error converting a byte array to a UTF-8 string.
     #[error("Conversion error: {0}")]
     Utf8(#[from] std::string::FromUtf8Error),
-    /// API-specific error.
+    /// An error status returned by Asterisk, along with the body of the response.
     #[error("Api Error: {0}")]
     Api(ApiError),
-    /// General HTTP error.
-    #[error("HTTP error: {raw} - body: {body}")]
-    Http {
-        /// The HTTP error.
-        raw: ReqwError,
-        /// The response body.
-        body: String,
-    },
     /// URL parsing error.
     #[error("URL parse error: {0}")]
     UrlParse(ParseError),
     /// WebSocket error.
     #[error("WebSocket error: {0}")]
-    Websocket(WSError),
+    Websocket(Box<WSError>),
+    /// I/O error.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// Invalid configuration.
+    #[error("Invalid configuration: {0}")]
+    InvalidConfig(String),
     /// Internal error.
     #[error("Internal error: {0}")]
     Internal(String),
+    /// IVR menu definition error.
+    #[error("Invalid IVR menu: {0}")]
+    InvalidMenu(String),
+    /// Invalid DTMF digit or sequence.
+    #[error("Invalid DTMF: {0}")]
+    InvalidDtmf(String),
+    /// The conference room is locked.
+    #[error("Conference {0} is locked")]
+    ConferenceLocked(String),
+    /// The Asterisk server is too old for a feature.
+    #[error("Unsupported by Asterisk {actual}, which requires {required}")]
+    Unsupported {
+        /// The releases with the feature.
+        required: String,
+        /// The version of the server.
+        actual: String,
+    },
+    /// A request replayed from a cassette was not recorded.
+    #[error("No recorded interaction for {0}")]
+    UnmatchedInteraction(String),
 }
 
 impl AriError {
@@ -94,7 +111,7 @@ impl From<WSError> for AriError {
     ///
     /// An `AriError` representing the WebSocket error.
     fn from(e: WSError) -> Self {
-        AriError::Websocket(e)
+        AriError::Websocket(Box::new(e))
     }
 }
 
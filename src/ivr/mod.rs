@@ -0,0 +1,383 @@
+//! Declarative IVR menus.
+//!
+//! A [`models::Menu`] describes a call tree: every node plays a prompt, collects DTMF and
+//! takes an [`models::Action`] according to the caller's choice. Menus are deserialized
+//! with serde, so they can live in JSON or TOML files edited outside the code:
+//!
+//! ```json
+//! {
+//!     "start": "main",
+//!     "nodes": {
+//!         "main": {
+//!             "prompt": ["sound:main-menu"],
+//!             "invalid_prompt": ["sound:option-is-invalid"],
+//!             "choices": {
+//!                 "1": { "action": "goto", "node": "sales" },
+//!                 "2": { "action": "queue", "queue": "support" },
+//!                 "0": { "action": "hangup" }
+//!             }
+//!         },
+//!         "sales": {
+//!             "prompt": ["sound:sales"],
+//!             "on_failure": { "action": "transfer", "endpoint": "PJSIP/1000" }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! A menu runs against a channel that entered the Stasis application. The run waits for
+//! events, so it must be spawned from the `StasisStart` handler rather than awaited in it:
+//!
+//! ```no_run
+//! use asterisk_ari::ivr::models::Menu;
+//! use asterisk_ari::{AriClient, Config};
+//!
+//! # fn example(menu: Menu) {
+//! let mut client = AriClient::with_config(Config::default());
+//! let ari = client.clone();
+//! client.on_stasis_start(move |_, event| {
+//!     let (ari, menu) = (ari.clone(), menu.clone());
+//!     async move {
+//!         tokio::spawn(async move {
+//!             let outcome = ari.ivr().run(&menu, &event.data.channel.id).await;
+//!             println!("IVR outcome: {:?}", outcome);
+//!         });
+//!         Ok(())
+//!     }
+//! });
+//! # }
+//! ```
+
+pub mod models;
+
+use crate::apis::channels::params::{
+    ContinueRequest, DeleteRequest, Extension, PlayWithPlaybackIdRequest,
+};
+use crate::apis::ids::{ChannelId, PlaybackId};
+use crate::errors::Result;
+use crate::ws::models::Event;
+use crate::AriClient;
+use models::{Action, Match, Menu, Node, Outcome};
+use std::time::Duration;
+use tokio::sync::broadcast::Receiver;
+use tracing::debug;
+
+pub struct Ivr<'c> {
+    client: &'c AriClient,
+}
+
+impl<'c> Ivr<'c> {
+    pub fn new(client: &'c AriClient) -> Self {
+        Self { client }
+    }
+}
+
+impl Ivr<'_> {
+    /// Runs the menu on a channel until the caller reaches an action that ends it.
+    ///
+    /// `goto` actions are followed, `transfer`, `dialplan` and `hangup` are executed on the
+    /// channel, while `queue` and `exit` are returned to the application to handle.
+    pub async fn run(
+        &self,
+        menu: &Menu,
+        channel_id: impl Into<ChannelId> + Send,
+    ) -> Result<Outcome> {
+        menu.validate()?;
+
+        let mut call = Call {
+            client: self.client,
+            channel_id: channel_id.into(),
+            events: self.client.subscribe(),
+        };
+
+        let mut current = &menu.start;
+        loop {
+            debug!(
+                "channel {} entering IVR node `{}`",
+                call.channel_id, current
+            );
+            let node = &menu.nodes[current];
+
+            let Some(action) = call.collect(node).await? else {
+                return Ok(Outcome::CallerHangup);
+            };
+
+            match action {
+                Action::Goto { node } => current = node,
+                Action::Transfer { endpoint } => {
+                    self.client
+                        .channels()
+                        .redirect(&call.channel_id, endpoint.as_str())
+                        .await?;
+                    return Ok(Outcome::Transferred(endpoint.clone()));
+                }
+                Action::Dialplan {
+                    context,
+                    extension,
+                    priority,
+                } => {
+                    let mut target = Extension::new(extension.clone());
+                    if let Some(context) = context {
+                        target = target.with_context(context);
+                    }
+                    if let Some(priority) = priority {
+                        target = target.with_priority(priority);
+                    }
+                    self.client
+                        .channels()
+                        .r#continue(ContinueRequest::new(&call.channel_id).with_extension(target))
+                        .await?;
+                    return Ok(Outcome::Continued);
+                }
+                Action::Queue { queue } => return Ok(Outcome::Queue(queue.clone())),
+                Action::Exit { value } => return Ok(Outcome::Exit(value.clone())),
+                Action::Hangup => {
+                    self.client
+                        .channels()
+                        .delete(DeleteRequest::new(&call.channel_id))
+                        .await?;
+                    return Ok(Outcome::Hangup);
+                }
+            }
+        }
+    }
+}
+
+/// What happened on the channel while waiting for the caller.
+enum Input {
+    Digit(char),
+    PlaybackFinished(PlaybackId),
+    HungUp,
+}
+
+/// Why the collection of a choice stopped without selecting one.
+enum Failure {
+    Invalid,
+    Timeout,
+}
+
+/// A channel running a menu, with its own subscription to the events.
+struct Call<'c> {
+    client: &'c AriClient,
+    channel_id: ChannelId,
+    events: Receiver<Event>,
+}
+
+impl Call<'_> {
+    /// Plays the node prompt and collects a choice, retrying as configured.
+    ///
+    /// Returns `None` if the caller hung up.
+    async fn collect<'n>(&mut self, node: &'n Node) -> Result<Option<&'n Action>> {
+        let mut failures = 0;
+        let mut notice: &[String] = &[];
+
+        loop {
+            let prompt = notice.iter().chain(node.prompt.iter());
+            let mut digits = String::new();
+
+            let failure = match self.play(prompt, &mut digits).await? {
+                None => return Ok(None),
+                Some(()) => match self.read(node, &mut digits).await? {
+                    None => return Ok(None),
+                    Some(Ok(action)) => return Ok(Some(action)),
+                    Some(Err(failure)) => failure,
+                },
+            };
+
+            failures += 1;
+            if failures > node.retries {
+                return Ok(Some(&node.on_failure));
+            }
+
+            notice = match failure {
+                Failure::Invalid => &node.invalid_prompt,
+                Failure::Timeout => &node.timeout_prompt,
+            };
+        }
+    }
+
+    /// Plays the media one after the other, stopping at the first digit (barge-in).
+    ///
+    /// Returns `None` if the caller hung up.
+    async fn play(
+        &mut self,
+        media: impl Iterator<Item = &String>,
+        digits: &mut String,
+    ) -> Result<Option<()>> {
+        for uri in media {
+            let playback_id = crate::client::unique_id("ivr");
+            self.client
+                .channels()
+                .play_with_playback_id(PlayWithPlaybackIdRequest::new(
+                    &self.channel_id,
+                    &playback_id,
+                    uri,
+                ))
+                .await?;
+
+            loop {
+                match self.next_input().await? {
+                    Input::Digit(digit) => {
+                        if let Err(e) = self.client.playbacks().stop(&playback_id).await {
+                            debug!("unable to stop playback {}: {}", playback_id, e);
+                        }
+                        digits.push(digit);
+                        return Ok(Some(()));
+                    }
+                    Input::PlaybackFinished(id) if id == playback_id => break,
+                    Input::PlaybackFinished(_) => {}
+                    Input::HungUp => return Ok(None),
+                }
+            }
+        }
+
+        Ok(Some(()))
+    }
+
+    /// Reads digits until they select a choice, cannot select any, or the caller stops typing.
+    ///
+    /// Returns `None` if the caller hung up.
+    async fn read<'n>(
+        &mut self,
+        node: &'n Node,
+        digits: &mut String,
+    ) -> Result<Option<std::result::Result<&'n Action, Failure>>> {
+        let timeout = Duration::from_secs(node.timeout);
+
+        loop {
+            if !digits.is_empty() {
+                match node.matches(digits) {
+                    Match::Choice(action) => return Ok(Some(Ok(action))),
+                    Match::Invalid => return Ok(Some(Err(Failure::Invalid))),
+                    Match::Partial => {}
+                }
+            }
+
+            let deadline = tokio::time::Instant::now() + timeout;
+            loop {
+                match tokio::time::timeout_at(deadline, self.next_input()).await {
+                    Err(_) => return Ok(Some(Err(Failure::Timeout))),
+                    Ok(input) => match input? {
+                        Input::Digit(digit) => {
+                            digits.push(digit);
+                            break;
+                        }
+                        Input::PlaybackFinished(_) => {}
+                        Input::HungUp => return Ok(None),
+                    },
+                }
+            }
+        }
+    }
+
+    /// Waits for the next event concerning the channel.
+    async fn next_input(&mut self) -> Result<Input> {
+        loop {
+            match crate::client::next_event(&mut self.events).await? {
+                Event::ChannelDtmfReceived(e) if e.data.channel.id == self.channel_id => {
+                    return Ok(Input::Digit(e.data.digit.as_char()));
+                }
+                Event::PlaybackFinished(e) => {
+                    if let Some(id) = e.data.playback.id {
+                        return Ok(Input::PlaybackFinished(id));
+                    }
+                }
+                Event::ChannelHangupRequest(e) if e.data.channel.id == self.channel_id => {
+                    return Ok(Input::HungUp)
+                }
+                Event::StasisEnd(e) if e.data.channel.id == self.channel_id => {
+                    return Ok(Input::HungUp)
+                }
+                Event::ChannelDestroyed(e) if e.data.channel.id == self.channel_id => {
+                    return Ok(Input::HungUp)
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::models::{Menu, Outcome};
+    use crate::testing::{CallerScript, MockAriServer};
+    use crate::AriClient;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_ivr_runs_the_menu_on_the_channel() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_playback_duration(Duration::from_millis(10));
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+        let ivr = client.ivr();
+        let menu: Menu = serde_json::from_value(serde_json::json!({
+            "start": "main",
+            "nodes": {
+                "main": {
+                    "prompt": ["sound:main-menu"],
+                    "invalid_prompt": ["sound:option-is-invalid"],
+                    "retries": 1,
+                    "choices": {
+                        "1": { "action": "goto", "node": "sales" },
+                        "0": { "action": "exit", "value": "operator" }
+                    }
+                },
+                "sales": {
+                    "prompt": ["sound:sales"],
+                    "choices": { "0": { "action": "hangup" } }
+                }
+            }
+        }))
+        .unwrap();
+
+        // An invalid choice plays the notice and the prompt again.
+        let caller = server.call("PJSIP/1000", "test");
+        let script = CallerScript::new()
+            .wait_playback_finished()
+            .dtmf("5")
+            .wait_playback_finished()
+            .dtmf("1")
+            .wait_playback_finished()
+            .dtmf("0");
+        let (outcome, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(ivr.run(&menu, caller.id()), caller.clone().run(script))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(outcome.unwrap(), Outcome::Hangup);
+        assert!(caller.is_hung_up());
+        assert!(server.received("DELETE", &format!("/channels/{}", caller.id())));
+        let played: Vec<String> = server
+            .requests()
+            .iter()
+            .filter(|request| {
+                request
+                    .path
+                    .starts_with(&format!("/channels/{}/play/", caller.id()))
+            })
+            .filter_map(|request| request.param("media").map(str::to_string))
+            .collect();
+        assert_eq!(
+            played,
+            [
+                "sound:main-menu",
+                "sound:option-is-invalid",
+                "sound:main-menu",
+                "sound:sales"
+            ]
+        );
+
+        // The caller hanging up ends the run.
+        let caller = server.call("PJSIP/1001", "test");
+        let script = CallerScript::new().wait_playback_finished().hang_up();
+        let (outcome, _) = tokio::time::timeout(Duration::from_secs(5), async {
+            tokio::join!(ivr.run(&menu, caller.id()), caller.clone().run(script))
+        })
+        .await
+        .unwrap();
+        assert_eq!(outcome.unwrap(), Outcome::CallerHangup);
+    }
+}
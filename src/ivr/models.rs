@@ -0,0 +1,272 @@
+use crate::errors::AriError;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Menu : A call tree made of named nodes.
+///
+/// Menus are plain serde structures, so they can be loaded from JSON, TOML or any other
+/// format supported by serde.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Menu {
+    /// Name of the node the caller enters first.
+    pub start: String,
+    /// Nodes of the menu, by name.
+    pub nodes: HashMap<String, Node>,
+}
+
+/// Node : A single prompt of the menu together with the choices it accepts.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Node {
+    /// Media URIs played when entering the node (e.g. `sound:main-menu`).
+    pub prompt: Vec<String>,
+
+    /// Actions to take, by the DTMF sequence the caller has to enter (e.g. `1` or `#`).
+    #[serde(default)]
+    pub choices: HashMap<String, Action>,
+
+    /// Number of times the caller can retry after an invalid entry or a timeout.
+    #[serde(default = "default_retries")]
+    pub retries: u32,
+
+    /// Seconds to wait for the first digit after the prompt ends, and between digits.
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+
+    /// Media URIs played before the prompt is repeated after an invalid entry.
+    #[serde(default)]
+    pub invalid_prompt: Vec<String>,
+
+    /// Media URIs played before the prompt is repeated after a timeout.
+    #[serde(default)]
+    pub timeout_prompt: Vec<String>,
+
+    /// Action to take once all the retries are used.
+    #[serde(default)]
+    pub on_failure: Action,
+}
+
+fn default_retries() -> u32 {
+    2
+}
+
+fn default_timeout() -> u64 {
+    5
+}
+
+/// Action : What to do once the caller made a choice.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Action {
+    /// Move to another node of the menu.
+    Goto {
+        /// Name of the node.
+        node: String,
+    },
+    /// Redirect the channel to an endpoint (e.g. `PJSIP/1000`).
+    Transfer {
+        /// Endpoint to redirect the channel to.
+        endpoint: String,
+    },
+    /// Exit the application and continue in the dialplan.
+    Dialplan {
+        /// The context to continue to.
+        context: Option<String>,
+        /// The extension to continue to.
+        extension: String,
+        /// The priority to continue to.
+        priority: Option<String>,
+    },
+    /// Hand the channel back to the application to be queued.
+    Queue {
+        /// Name of the queue.
+        queue: String,
+    },
+    /// Hand the channel back to the application with a value of choice.
+    Exit {
+        /// Value returned to the application.
+        value: String,
+    },
+    /// Hang up the channel.
+    #[default]
+    Hangup,
+}
+
+/// Outcome : How a menu run ended.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    /// The menu hung up the channel.
+    Hangup,
+    /// The caller hung up, or the channel left the application.
+    CallerHangup,
+    /// The channel was redirected to the endpoint.
+    Transferred(String),
+    /// The channel continued in the dialplan.
+    Continued,
+    /// The caller asked to be queued.
+    Queue(String),
+    /// The caller reached an exit of the menu.
+    Exit(String),
+}
+
+/// Result of matching the digits entered so far against the choices of a node.
+#[derive(Debug, PartialEq)]
+pub(crate) enum Match<'a> {
+    /// The digits select a choice.
+    Choice(&'a Action),
+    /// The digits are the beginning of at least one choice.
+    Partial,
+    /// No choice starts with the digits.
+    Invalid,
+}
+
+impl Node {
+    /// Matches the digits entered so far against the choices of the node.
+    pub(crate) fn matches(&self, digits: &str) -> Match<'_> {
+        if let Some(action) = self.choices.get(digits) {
+            return Match::Choice(action);
+        }
+
+        if self.choices.keys().any(|choice| choice.starts_with(digits)) {
+            Match::Partial
+        } else {
+            Match::Invalid
+        }
+    }
+}
+
+impl Menu {
+    /// Checks that the start node and all the `goto` targets exist, and that no choice
+    /// of a node is the beginning of another one, which would make it unreachable.
+    pub fn validate(&self) -> Result<(), AriError> {
+        if !self.nodes.contains_key(&self.start) {
+            return Err(AriError::InvalidMenu(format!(
+                "start node `{}` does not exist",
+                self.start
+            )));
+        }
+
+        for (name, node) in &self.nodes {
+            let actions = node.choices.values().chain(Some(&node.on_failure));
+            for action in actions {
+                if let Action::Goto { node: target } = action {
+                    if !self.nodes.contains_key(target) {
+                        return Err(AriError::InvalidMenu(format!(
+                            "node `{name}` goes to `{target}`, which does not exist"
+                        )));
+                    }
+                }
+            }
+
+            for choice in node.choices.keys() {
+                if choice.is_empty() {
+                    return Err(AriError::InvalidMenu(format!(
+                        "node `{name}` has an empty choice"
+                    )));
+                }
+
+                let shadowed = node
+                    .choices
+                    .keys()
+                    .find(|other| *other != choice && other.starts_with(choice.as_str()));
+                if let Some(other) = shadowed {
+                    return Err(AriError::InvalidMenu(format!(
+                        "node `{name}`: choice `{choice}` makes `{other}` unreachable"
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MENU: &str = r#"{
+        "start": "main",
+        "nodes": {
+            "main": {
+                "prompt": ["sound:main-menu"],
+                "invalid_prompt": ["sound:option-is-invalid"],
+                "choices": {
+                    "1": { "action": "goto", "node": "sales" },
+                    "2": { "action": "queue", "queue": "support" },
+                    "90": { "action": "transfer", "endpoint": "PJSIP/1000" },
+                    "0": { "action": "hangup" }
+                }
+            },
+            "sales": {
+                "prompt": ["sound:sales"],
+                "retries": 0,
+                "timeout": 3,
+                "on_failure": { "action": "dialplan", "context": "default", "extension": "100" }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_parse_menu() {
+        let menu: Menu = serde_json::from_str(MENU).unwrap();
+        menu.validate().unwrap();
+
+        let main = &menu.nodes["main"];
+        assert_eq!(main.retries, 2);
+        assert_eq!(main.timeout, 5);
+        assert_eq!(main.on_failure, Action::Hangup);
+        assert_eq!(
+            main.choices["2"],
+            Action::Queue {
+                queue: "support".to_string()
+            }
+        );
+
+        let sales = &menu.nodes["sales"];
+        assert_eq!(sales.retries, 0);
+        assert_eq!(
+            sales.on_failure,
+            Action::Dialplan {
+                context: Some("default".to_string()),
+                extension: "100".to_string(),
+                priority: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_node_matches() {
+        let menu: Menu = serde_json::from_str(MENU).unwrap();
+        let main = &menu.nodes["main"];
+
+        assert_eq!(main.matches("0"), Match::Choice(&Action::Hangup));
+        assert_eq!(main.matches("9"), Match::Partial);
+        assert!(matches!(main.matches("90"), Match::Choice(_)));
+        assert_eq!(main.matches("5"), Match::Invalid);
+        assert_eq!(main.matches("91"), Match::Invalid);
+    }
+
+    #[test]
+    fn test_validate_menu() {
+        let mut menu: Menu = serde_json::from_str(MENU).unwrap();
+        menu.start = "missing".to_string();
+        assert!(menu.validate().is_err());
+
+        let mut menu: Menu = serde_json::from_str(MENU).unwrap();
+        menu.nodes.get_mut("main").unwrap().choices.insert(
+            "3".to_string(),
+            Action::Goto {
+                node: "missing".to_string(),
+            },
+        );
+        assert!(menu.validate().is_err());
+
+        let mut menu: Menu = serde_json::from_str(MENU).unwrap();
+        menu.nodes
+            .get_mut("main")
+            .unwrap()
+            .choices
+            .insert("9".to_string(), Action::Hangup);
+        assert!(menu.validate().is_err());
+    }
+}
@@ -42,8 +42,7 @@
 //!         client
 //!             .channels()
 //!             .answer(&event.data.channel.id)
-//!             .await
-//!             .unwrap();
+//!             .await?;
 //!
 //!         client
 //!             .channels()
@@ -51,26 +50,21 @@
 //!                 &event.data.channel.id,
 //!                 "sound:tt-monkeys",
 //!             ))
-//!             .await
-//!             .unwrap();
+//!             .await?;
+//!
+//!         Ok(())
 //!     });
 //!
 //!     println!("Applications: {:?}", client.applications().list().await?);
 //!     println!("Ping: {:?}", client.asterisk().ping().await?);
 //!     println!("Info: {:?}", client.asterisk().info().await?);
 //!
-//!     let _client = client.clone();
-//!     tokio::spawn(async move {
-//!         _client.start("my-application".to_string()).await.unwrap();
-//!     });
+//!     client.start("my-application".to_string()).await?;
 //!
 //!     tokio::time::sleep(std::time::Duration::from_secs(30)).await;
 //!
 //!     println!("Stopping client");
-//!     client.stop();
-//!
-//!     println!("Await client to stop");
-//!     tokio::time::sleep(std::time::Duration::from_secs(4)).await;
+//!     client.stop().await?;
 //!
 //!     Ok(())
 //! }
@@ -90,5 +84,23 @@ mod errors;
 pub use errors::*;
 mod client;
 pub use client::*;
+/// Local state cache
+pub mod cache;
+/// Call helpers
+pub mod calls;
+/// Call detail records
+pub mod cdr;
+/// Conference rooms
+pub mod conference;
+/// Declarative IVR menus
+pub mod ivr;
+/// Call queues
+pub mod queue;
+/// Call sessions
+pub mod sessions;
+mod subscriptions;
+/// Mock ARI server for tests
+#[cfg(feature = "testing")]
+pub mod testing;
 /// WebSocket implementation
 pub mod ws;
@@ -0,0 +1,836 @@
+//! Call queues distributing callers to agents.
+//!
+//! A [`Queue`] keeps its callers in a holding bridge with music on hold, and offers them to
+//! its agents (endpoints) according to a [`models::Strategy`]. Once an agent answers, the
+//! caller leaves the holding bridge and is connected to the agent in a mixing bridge until
+//! either of them hangs up.
+//!
+//! The availability of the agents is kept up to date from the `EndpointStateChange` and
+//! `DeviceStateChanged` events, so the application has to be subscribed to the endpoints
+//! (or device states) of its agents, e.g. with `AriClient::subscribe_to`, which survives
+//! reconnections.
+//!
+//! ```no_run
+//! use asterisk_ari::queue::models::Strategy;
+//! use asterisk_ari::queue::params::{Overflow, QueueOptions};
+//! use asterisk_ari::queue::Queue;
+//! use asterisk_ari::AriClient;
+//!
+//! # async fn example(client: AriClient, channel_id: String) -> asterisk_ari::Result<()> {
+//! let queue = Queue::create(
+//!     &client,
+//!     QueueOptions::new("support")
+//!         .with_strategy(Strategy::LongestIdle)
+//!         .with_max_wait(300u32)
+//!         .with_overflow(Overflow::Transfer("PJSIP/voicemail".to_string()))
+//!         .with_announce_frequency(30u32),
+//! )
+//! .await?;
+//!
+//! queue.add_agent("PJSIP/1000", vec![]).await?;
+//! queue.add_agent("PJSIP/1001", vec![]).await?;
+//! queue.enqueue(channel_id, vec![]).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod models;
+pub mod params;
+
+use crate::apis::bridges::models::BridgeType;
+use crate::apis::bridges::params::{AddChannelRequest, CreateWithIdRequest, MohRequest};
+use crate::apis::channels::models::{ChannelState, DialStatus};
+use crate::apis::channels::params::{
+    App, ContinueRequest, CreateRequest, DeleteRequest, DialRequest, Extension, PlayRequest,
+};
+use crate::apis::ids::{BridgeId, ChannelId, EndpointRef};
+use crate::apis::media::MediaUri;
+use crate::errors::{AriError, Result};
+use crate::ws::models::Event;
+use crate::AriClient;
+use chrono::Utc;
+use models::{Agent, AgentState, Caller, QueueEvent, Strategy};
+use params::{Overflow, QueueOptions};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, warn};
+
+/// Number of queue events buffered for each subscriber.
+const EVENTS_CAPACITY: usize = 256;
+
+/// How often waiting callers are offered to the agents, and timeouts are checked.
+const TICK: Duration = Duration::from_secs(1);
+
+/// A call queue.
+///
+/// The queue stops distributing its callers when dropped; call [`Queue::destroy`] to also
+/// destroy the holding bridge.
+pub struct Queue {
+    shared: Arc<Shared>,
+    _dispatcher: DropGuard,
+}
+
+impl Queue {
+    /// Creates the holding bridge of a new queue and starts distributing its callers.
+    pub async fn create(client: &AriClient, options: QueueOptions) -> Result<Self> {
+        let bridge_id = BridgeId::new(crate::client::unique_id("queue"));
+        let stop_signal = CancellationToken::new();
+
+        // Subscribe before creating the bridge, so no event can be missed.
+        let subscription = client.subscribe();
+        let bridges = client.bridges();
+        bridges
+            .create_with_id(
+                CreateWithIdRequest::new(&bridge_id)
+                    .with_type(BridgeType::Holding)
+                    .with_name(&options.name),
+            )
+            .await?;
+
+        let mut moh = MohRequest::new(&bridge_id);
+        if let Some(class) = &options.moh_class {
+            moh = moh.with_moh_class(class);
+        }
+        if let Err(e) = bridges.moh(moh).await {
+            if let Err(e) = bridges.delete(&bridge_id).await {
+                debug!("unable to destroy bridge {}: {}", bridge_id, e);
+            }
+            return Err(e);
+        }
+
+        let shared = Arc::new(Shared {
+            client: client.clone(),
+            bridge_id,
+            options,
+            state: Mutex::new(State::default()),
+            events: broadcast::channel(EVENTS_CAPACITY).0,
+        });
+
+        tokio::spawn(dispatch(shared.clone(), subscription, stop_signal.clone()));
+
+        Ok(Queue {
+            shared,
+            _dispatcher: stop_signal.drop_guard(),
+        })
+    }
+
+    /// Id of the holding bridge of the queue.
+    pub fn bridge_id(&self) -> &BridgeId {
+        &self.shared.bridge_id
+    }
+
+    /// Subscribes to the changes of the queue.
+    pub fn subscribe(&self) -> broadcast::Receiver<QueueEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// The agents of the queue.
+    pub fn agents(&self) -> Vec<Agent> {
+        self.shared.state.lock().unwrap().agents.clone()
+    }
+
+    /// The callers waiting in the queue, in arrival order.
+    pub fn callers(&self) -> Vec<Caller> {
+        self.shared.state.lock().unwrap().callers.clone()
+    }
+
+    /// Adds an agent to the queue.
+    ///
+    /// The current state of the endpoint is fetched from Asterisk. Agents whose state is
+    /// unknown are considered available.
+    pub async fn add_agent(
+        &self,
+        endpoint: impl Into<EndpointRef> + Send,
+        skills: Vec<String>,
+    ) -> Result<()> {
+        let endpoint = endpoint.into();
+        let details = self.shared.client.endpoints().get(&endpoint).await?;
+        let state = AgentState::from_endpoint(&details).unwrap_or(AgentState::Available);
+
+        let mut guard = self.shared.state.lock().unwrap();
+        guard.agents.retain(|agent| agent.endpoint != endpoint);
+        guard.agents.push(Agent {
+            endpoint: endpoint.clone(),
+            skills,
+            state,
+            last_call_end: None,
+            calls_taken: 0,
+        });
+        let _ = self.shared.events.send(QueueEvent::AgentStateChanged {
+            agent: endpoint,
+            state,
+        });
+
+        Ok(())
+    }
+
+    /// Removes an agent from the queue. A call in progress with the agent is not affected.
+    pub fn remove_agent(&self, endpoint: impl Into<EndpointRef>) {
+        let endpoint = endpoint.into();
+        self.shared
+            .state
+            .lock()
+            .unwrap()
+            .agents
+            .retain(|agent| agent.endpoint != endpoint);
+    }
+
+    /// Answers a channel of the application and places it in the queue.
+    ///
+    /// `skills` are the skills an agent needs to take the call with the skills-based strategy,
+    /// they are ignored by the other strategies.
+    pub async fn enqueue(
+        &self,
+        channel_id: impl Into<ChannelId> + Send,
+        skills: Vec<String>,
+    ) -> Result<()> {
+        let channel_id = channel_id.into();
+
+        self.shared.client.channels().answer(&channel_id).await?;
+        self.shared
+            .client
+            .bridges()
+            .add_channel(AddChannelRequest::new(&self.shared.bridge_id, &channel_id))
+            .await?;
+
+        self.shared.state.lock().unwrap().callers.push(Caller {
+            channel_id: channel_id.clone(),
+            skills,
+            joined_at: Utc::now(),
+        });
+        let _ = self
+            .shared
+            .events
+            .send(QueueEvent::CallerJoined(channel_id));
+
+        Ok(())
+    }
+
+    /// Stops distributing the callers and destroys the holding bridge.
+    ///
+    /// The callers still waiting stay in the application.
+    pub async fn destroy(self) -> Result<()> {
+        self.shared
+            .client
+            .bridges()
+            .delete(&self.shared.bridge_id)
+            .await
+    }
+}
+
+/// The part of a queue shared with its background tasks.
+struct Shared {
+    client: AriClient,
+    bridge_id: BridgeId,
+    options: QueueOptions,
+    state: Mutex<State>,
+    events: broadcast::Sender<QueueEvent>,
+}
+
+#[derive(Default)]
+struct State {
+    agents: Vec<Agent>,
+    callers: Vec<Caller>,
+    /// Callers being offered to agents.
+    offered: HashSet<ChannelId>,
+    /// Position of the round-robin strategy.
+    cursor: usize,
+    last_announcement: Option<Instant>,
+}
+
+/// Keeps the queue up to date from the events and distributes the waiting callers.
+async fn dispatch(
+    shared: Arc<Shared>,
+    mut subscription: broadcast::Receiver<Event>,
+    stop_signal: CancellationToken,
+) {
+    let mut tick = tokio::time::interval(TICK);
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            event = crate::client::next_event(&mut subscription) => match event {
+                Ok(event) => shared.apply(&event),
+                Err(e) => {
+                    debug!("queue {} stopped: {}", shared.bridge_id, e);
+                    break;
+                }
+            },
+            _ = tick.tick() => shared.tick(),
+        }
+    }
+}
+
+impl Shared {
+    /// Updates the agents and the callers from an event.
+    fn apply(&self, event: &Event) {
+        match event {
+            Event::EndpointStateChange(e) => {
+                let endpoint = &e.data.endpoint;
+                if let Some(state) = AgentState::from_endpoint(endpoint) {
+                    self.observe_agent(&endpoint.reference(), state);
+                }
+            }
+            Event::DeviceStateChanged(e) => {
+                let device = &e.data.device_state;
+                if let Some(state) = AgentState::from_device_state(&device.state) {
+                    self.observe_agent(&EndpointRef::from(device.name.as_str()), state);
+                }
+            }
+            Event::StasisEnd(e) => self.abandon(&e.data.channel.id),
+            Event::ChannelHangupRequest(e) => self.abandon(&e.data.channel.id),
+            Event::ChannelDestroyed(e) => self.abandon(&e.data.channel.id),
+            _ => {}
+        }
+    }
+
+    /// Applies a state reported by Asterisk, unless the agent is being offered a call.
+    fn observe_agent(&self, endpoint: &EndpointRef, state: AgentState) {
+        self.update_agent(endpoint, |agent| {
+            if agent.state != AgentState::Ringing {
+                agent.state = state;
+            }
+        });
+    }
+
+    /// Updates an agent, publishing its new state if it changed.
+    fn update_agent(&self, endpoint: &EndpointRef, update: impl FnOnce(&mut Agent)) {
+        let mut state = self.state.lock().unwrap();
+        let Some(agent) = state.agents.iter_mut().find(|a| &a.endpoint == endpoint) else {
+            return;
+        };
+
+        let previous = agent.state;
+        update(agent);
+        if agent.state != previous {
+            let _ = self.events.send(QueueEvent::AgentStateChanged {
+                agent: agent.endpoint.clone(),
+                state: agent.state,
+            });
+        }
+    }
+
+    /// Makes the agents offered a call available again.
+    fn release_agents<'a>(&self, endpoints: impl Iterator<Item = &'a EndpointRef>) {
+        for endpoint in endpoints {
+            self.update_agent(endpoint, |agent| {
+                if agent.state == AgentState::Ringing {
+                    agent.state = AgentState::Available;
+                }
+            });
+        }
+    }
+
+    /// Removes a caller that hung up while waiting.
+    fn abandon(&self, channel_id: &ChannelId) {
+        let mut state = self.state.lock().unwrap();
+        let before = state.callers.len();
+        state.callers.retain(|c| &c.channel_id != channel_id);
+        if state.callers.len() != before {
+            state.offered.remove(channel_id);
+            let _ = self
+                .events
+                .send(QueueEvent::CallerAbandoned(channel_id.clone()));
+        }
+    }
+
+    /// Hands the callers waiting too long to the overflow action, announces their position to
+    /// the waiting callers and offers them to the agents.
+    fn tick(self: &Arc<Self>) {
+        let mut overflowed = vec![];
+        let mut announcements = vec![];
+        let mut offers = vec![];
+
+        {
+            let mut guard = self.state.lock().unwrap();
+            let state = &mut *guard;
+
+            if let Some(max_wait) = self.options.max_wait {
+                let limit = Utc::now() - chrono::Duration::seconds(max_wait.into());
+                state.callers.retain(|caller| {
+                    let expired =
+                        caller.joined_at <= limit && !state.offered.contains(&caller.channel_id);
+                    if expired {
+                        overflowed.push(caller.channel_id.clone());
+                    }
+                    !expired
+                });
+            }
+
+            if let Some(frequency) = self.options.announce_frequency {
+                match state.last_announcement {
+                    _ if state.callers.is_empty() => state.last_announcement = None,
+                    None => state.last_announcement = Some(Instant::now()),
+                    Some(last) if last.elapsed() >= Duration::from_secs(frequency.into()) => {
+                        state.last_announcement = Some(Instant::now());
+                        // The callers being offered to the agents are about to be connected.
+                        announcements = (state.callers.iter().enumerate())
+                            .filter(|(_, caller)| !state.offered.contains(&caller.channel_id))
+                            .map(|(index, caller)| (caller.channel_id.clone(), index + 1))
+                            .collect();
+                    }
+                    Some(_) => {}
+                }
+            }
+
+            for caller in &state.callers {
+                if state.offered.contains(&caller.channel_id) {
+                    continue;
+                }
+
+                let agents: Vec<EndpointRef> = self
+                    .options
+                    .strategy
+                    .select(&state.agents, &caller.skills, &mut state.cursor)
+                    .into_iter()
+                    .map(|agent| agent.endpoint.clone())
+                    .collect();
+                if agents.is_empty() {
+                    // With skills, an agent may still be found for the callers behind.
+                    if self.options.strategy == Strategy::SkillsBased {
+                        continue;
+                    }
+                    break;
+                }
+
+                for agent in state.agents.iter_mut() {
+                    if agents.contains(&agent.endpoint) {
+                        agent.state = AgentState::Ringing;
+                        let _ = self.events.send(QueueEvent::AgentStateChanged {
+                            agent: agent.endpoint.clone(),
+                            state: agent.state,
+                        });
+                    }
+                }
+                state.offered.insert(caller.channel_id.clone());
+                offers.push((caller.channel_id.clone(), agents));
+            }
+        }
+
+        for caller in overflowed {
+            let _ = self
+                .events
+                .send(QueueEvent::CallerOverflowed(caller.clone()));
+            let shared = self.clone();
+            tokio::spawn(async move {
+                if let Err(e) = shared.overflow(&caller).await {
+                    warn!("unable to overflow caller {}: {}", caller, e);
+                }
+            });
+        }
+
+        for (caller, position) in announcements {
+            let shared = self.clone();
+            tokio::spawn(async move {
+                let media = [
+                    shared.options.announce_prefix.clone(),
+                    MediaUri::number(position as i64),
+                    shared.options.announce_suffix.clone(),
+                ];
+                if let Err(e) = shared
+                    .client
+                    .channels()
+                    .play(PlayRequest::new(&caller, media))
+                    .await
+                {
+                    debug!("unable to announce its position to {}: {}", caller, e);
+                }
+            });
+        }
+
+        for (caller, agents) in offers {
+            let offer = Offer {
+                shared: self.clone(),
+                events: self.client.subscribe(),
+                caller,
+                agents,
+                legs: HashMap::new(),
+            };
+            tokio::spawn(offer.run());
+        }
+    }
+
+    /// Takes a caller out of the holding bridge and applies the overflow action.
+    async fn overflow(&self, caller: &ChannelId) -> Result<()> {
+        self.client
+            .bridges()
+            .remove_channel(&self.bridge_id, caller)
+            .await?;
+
+        let channels = self.client.channels();
+        match &self.options.overflow {
+            Overflow::Hangup => channels.delete(DeleteRequest::new(caller)).await,
+            Overflow::Transfer(endpoint) => channels.redirect(caller, endpoint.as_str()).await,
+            Overflow::Dialplan {
+                context,
+                extension,
+                priority,
+            } => {
+                let mut target = Extension::new(extension.clone());
+                if let Some(context) = context {
+                    target = target.with_context(context);
+                }
+                if let Some(priority) = priority {
+                    target = target.with_priority(priority);
+                }
+                channels
+                    .r#continue(ContinueRequest::new(caller).with_extension(target))
+                    .await
+            }
+            Overflow::Return => Ok(()),
+        }
+    }
+}
+
+/// A caller being offered to some agents.
+struct Offer {
+    shared: Arc<Shared>,
+    events: broadcast::Receiver<Event>,
+    caller: ChannelId,
+    /// Endpoints of the agents offered the call.
+    agents: Vec<EndpointRef>,
+    /// Endpoints of the agents being rung, by the id of the channel ringing them.
+    legs: HashMap<ChannelId, EndpointRef>,
+}
+
+impl Offer {
+    /// Rings the agents and connects the caller to the first one answering.
+    ///
+    /// If no agent answers, the caller is left waiting to be offered again.
+    async fn run(mut self) {
+        let answered = match self.ring().await {
+            Ok(answered) => answered,
+            Err(e) => {
+                warn!("unable to offer caller {}: {}", self.caller, e);
+                None
+            }
+        };
+
+        let winner = answered.and_then(|leg| self.legs.remove_entry(&leg));
+        self.hangup(self.legs.keys()).await;
+        self.shared.release_agents(
+            self.agents
+                .iter()
+                .filter(|agent| winner.as_ref().map(|(_, a)| a) != Some(*agent)),
+        );
+
+        let Some((leg, agent)) = winner else {
+            self.shared
+                .state
+                .lock()
+                .unwrap()
+                .offered
+                .remove(&self.caller);
+            return;
+        };
+
+        let waiting = {
+            let mut state = self.shared.state.lock().unwrap();
+            state.offered.remove(&self.caller);
+            let before = state.callers.len();
+            state.callers.retain(|c| c.channel_id != self.caller);
+            state.callers.len() != before
+        };
+        if !waiting {
+            // The caller hung up while the agent answered.
+            self.shared.release_agents(Some(&agent).into_iter());
+            self.hangup(Some(&leg).into_iter()).await;
+            return;
+        }
+
+        self.shared
+            .update_agent(&agent, |a| a.state = AgentState::Busy);
+        let _ = self.shared.events.send(QueueEvent::CallerConnected {
+            caller: self.caller.clone(),
+            agent: agent.clone(),
+        });
+
+        let bridge = BridgeId::new(crate::client::unique_id("queue"));
+        if let Err(e) = self.connect(&leg, &bridge).await {
+            warn!(
+                "call between caller {} and agent {} failed: {}",
+                self.caller, agent, e
+            );
+        }
+
+        if let Err(e) = self.shared.client.bridges().delete(&bridge).await {
+            debug!("unable to destroy bridge {}: {}", bridge, e);
+        }
+        self.hangup([&self.caller, &leg].into_iter()).await;
+
+        self.shared.update_agent(&agent, |a| {
+            a.state = AgentState::Available;
+            a.last_call_end = Some(Utc::now());
+            a.calls_taken += 1;
+        });
+    }
+
+    /// Dials the agents and waits for the first one to answer.
+    ///
+    /// Returns the channel of the agent that answered, or `None` if nobody answered or the
+    /// caller hung up.
+    async fn ring(&mut self) -> Result<Option<ChannelId>> {
+        let application = self
+            .shared
+            .client
+            .application()
+            .ok_or_else(|| AriError::Internal("the client is not started".to_string()))?;
+        let channels = self.shared.client.channels();
+        let timeout = self.shared.options.ring_timeout;
+
+        for agent in &self.agents {
+            let leg = ChannelId::new(crate::client::unique_id("queue"));
+            let dialed = async {
+                channels
+                    .create(
+                        CreateRequest::new(agent.to_string(), App::new(&application))
+                            .with_channel_id(&leg)
+                            .with_originator(&self.caller),
+                    )
+                    .await?;
+                channels
+                    .dial(
+                        DialRequest::new(&leg)
+                            .with_caller(&self.caller)
+                            .with_timeout(timeout),
+                    )
+                    .await
+            };
+            match dialed.await {
+                Ok(()) => {
+                    self.legs.insert(leg, agent.clone());
+                }
+                Err(e) => {
+                    debug!("unable to ring agent {}: {}", agent, e);
+                    self.hangup(Some(&leg).into_iter()).await;
+                }
+            }
+        }
+
+        // Leave some room to the dial timeout, so the dial status is received before giving up.
+        let deadline = Instant::now() + Duration::from_secs(u64::from(timeout) + 5);
+        while !self.legs.is_empty() {
+            let event = match tokio::time::timeout_at(
+                deadline,
+                crate::client::next_event(&mut self.events),
+            )
+            .await
+            {
+                Ok(event) => event?,
+                Err(_) => return Ok(None),
+            };
+
+            match &event {
+                Event::Dial(e) => {
+                    if let Some(peer) = &e.data.peer {
+                        if e.data.dialstatus == DialStatus::Answer
+                            && self.legs.contains_key(&peer.id)
+                        {
+                            return Ok(Some(peer.id.clone()));
+                        }
+                    }
+                }
+                Event::ChannelStateChange(e)
+                    if e.data.channel.state == ChannelState::Up
+                        && self.legs.contains_key(&e.data.channel.id) =>
+                {
+                    return Ok(Some(e.data.channel.id.clone()))
+                }
+                Event::ChannelDestroyed(e) => {
+                    self.legs.remove(&e.data.channel.id);
+                }
+                _ => {}
+            }
+
+            if crate::calls::hung_up(&event, &self.caller, &self.caller).is_some() {
+                return Ok(None);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Moves the caller from the holding bridge to a new bridge with the agent, and waits for
+    /// either of them to hang up.
+    async fn connect(&mut self, leg: &ChannelId, bridge: &BridgeId) -> Result<()> {
+        let bridges = self.shared.client.bridges();
+        bridges
+            .remove_channel(&self.shared.bridge_id, &self.caller)
+            .await?;
+        bridges
+            .create_with_id(CreateWithIdRequest::new(bridge).with_type(BridgeType::Mixing))
+            .await?;
+        bridges
+            .add_channel(AddChannelRequest::new(
+                bridge,
+                format!("{},{}", self.caller, leg),
+            ))
+            .await?;
+
+        loop {
+            let event = crate::client::next_event(&mut self.events).await?;
+            if crate::calls::hung_up(&event, &self.caller, leg).is_some() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Hangs up channels, logging the errors as the channels may already be gone.
+    async fn hangup<'a>(&self, channels: impl Iterator<Item = &'a ChannelId>) {
+        for channel_id in channels {
+            if let Err(e) = self
+                .shared
+                .client
+                .channels()
+                .delete(DeleteRequest::new(channel_id))
+                .await
+            {
+                debug!("unable to hang up channel {}: {}", channel_id, e);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::models::{AgentState, QueueEvent};
+    use super::params::QueueOptions;
+    use super::Queue;
+    use crate::testing::models::Pickup;
+    use crate::testing::{Caller, MockAriServer};
+    use crate::AriClient;
+    use std::time::Duration;
+
+    /// Waits for a queue event matching a predicate, returning the events seen until then.
+    async fn queue_events_until(
+        events: &mut tokio::sync::broadcast::Receiver<QueueEvent>,
+        last: impl Fn(&QueueEvent) -> bool,
+    ) -> Vec<QueueEvent> {
+        let mut seen = vec![];
+        while seen.last().map_or(true, |event| !last(event)) {
+            let event = tokio::time::timeout(Duration::from_secs(10), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            seen.push(event);
+        }
+        seen
+    }
+
+    #[tokio::test]
+    async fn test_queue_offers_the_callers_again_until_an_agent_answers() {
+        let server = MockAriServer::start().await.unwrap();
+        server.add_endpoint("PJSIP", "1000");
+        server.set_pickup("PJSIP/1000", Pickup::Ring);
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let queue = Queue::create(
+            &client,
+            QueueOptions::new("support").with_ring_timeout(1u32),
+        )
+        .await
+        .unwrap();
+        let mut events = queue.subscribe();
+        queue.add_agent("PJSIP/1000", vec![]).await.unwrap();
+        let caller = server.call("PJSIP/2000", "test");
+        queue.enqueue(caller.id(), vec![]).await.unwrap();
+
+        // The agent does not answer: it is released and the caller keeps waiting.
+        let state_is = |expected: AgentState| move |event: &QueueEvent| matches!(event, QueueEvent::AgentStateChanged { state, .. } if *state == expected);
+        queue_events_until(&mut events, state_is(AgentState::Ringing)).await;
+        queue_events_until(&mut events, state_is(AgentState::Available)).await;
+        assert_eq!(queue.callers().len(), 1);
+        assert!(!caller.is_hung_up());
+        let first_leg = server.requests_to("POST", "/channels/create")[0]
+            .param("channelId")
+            .unwrap()
+            .to_string();
+        assert!(server.channel(&first_leg).is_none());
+
+        // Offered again, the agent answers and is connected to the caller.
+        server.set_pickup("PJSIP/1000", Pickup::Answer);
+        let seen = queue_events_until(&mut events, |event| {
+            matches!(event, QueueEvent::CallerConnected { .. })
+        })
+        .await;
+        assert_eq!(
+            seen.last(),
+            Some(&QueueEvent::CallerConnected {
+                caller: caller.id().into(),
+                agent: "PJSIP/1000".into(),
+            })
+        );
+        assert!(queue.callers().is_empty());
+        let creates = server.requests_to("POST", "/channels/create");
+        assert_eq!(creates.len(), 2);
+        assert!(creates
+            .iter()
+            .all(|create| create.param("originator") == Some(caller.id())));
+        assert!(!server.received("POST", "/channels"));
+        let holding = queue.bridge_id().to_string();
+        let waiting = || server.bridge(&holding).unwrap()["channels"].to_string();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while waiting().contains(caller.id()) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // Once the call is over, the agent is available again.
+        caller.hang_up();
+        queue_events_until(&mut events, state_is(AgentState::Available)).await;
+        let agent = &queue.agents()[0];
+        assert_eq!(agent.calls_taken, 1);
+        assert!(agent.last_call_end.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_queue_announces_their_position_to_the_callers() {
+        let server = MockAriServer::start().await.unwrap();
+        let mut client = AriClient::with_config(server.config());
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let queue = Queue::create(
+            &client,
+            QueueOptions::new("support").with_announce_frequency(1u32),
+        )
+        .await
+        .unwrap();
+        let first = server.call("PJSIP/1000", "test");
+        let second = server.call("PJSIP/1001", "test");
+        queue.enqueue(first.id(), vec![]).await.unwrap();
+        queue.enqueue(second.id(), vec![]).await.unwrap();
+
+        let announced = |caller: &Caller| {
+            server
+                .requests_to("POST", &format!("/channels/{}/play", caller.id()))
+                .first()
+                .and_then(|request| request.param("media").map(str::to_string))
+        };
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while announced(&first).is_none() || announced(&second).is_none() {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            announced(&first).unwrap(),
+            "sound:queue-thereare,number:1,sound:queue-callswaiting"
+        );
+        assert_eq!(
+            announced(&second).unwrap(),
+            "sound:queue-thereare,number:2,sound:queue-callswaiting"
+        );
+        let holding_bridge = format!("/bridges/{}/play", queue.bridge_id());
+        assert!(!server.received("POST", &holding_bridge));
+    }
+}
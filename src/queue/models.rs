@@ -0,0 +1,244 @@
+use crate::apis::device_stats::models::DeviceStateState;
+use crate::apis::endpoints::models::{Endpoint, State};
+use crate::apis::ids::{ChannelId, EndpointRef};
+use chrono::{DateTime, Utc};
+
+/// Agent : An endpoint answering the calls of a queue.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Agent {
+    /// Endpoint of the agent.
+    pub endpoint: EndpointRef,
+    /// Skills of the agent, used by the skills-based strategy.
+    pub skills: Vec<String>,
+    /// Current state of the agent.
+    pub state: AgentState,
+    /// When the agent ended its last call of the queue.
+    pub last_call_end: Option<DateTime<Utc>>,
+    /// Number of calls of the queue answered by the agent.
+    pub calls_taken: u32,
+}
+
+/// Caller : A channel waiting in a queue.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Caller {
+    /// Id of the caller channel.
+    pub channel_id: ChannelId,
+    /// Skills an agent needs to take the call, used by the skills-based strategy.
+    pub skills: Vec<String>,
+    /// When the caller entered the queue.
+    pub joined_at: DateTime<Utc>,
+}
+
+/// State of an agent.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AgentState {
+    /// Ready to take a call.
+    Available,
+    /// Being offered a call of the queue.
+    Ringing,
+    /// On a call, of the queue or not.
+    Busy,
+    /// Not reachable.
+    Offline,
+}
+
+impl AgentState {
+    /// Maps the state of the device of an agent. Returns `None` if the state is unknown.
+    pub(crate) fn from_device_state(state: &DeviceStateState) -> Option<Self> {
+        match state {
+            DeviceStateState::NotInuse => Some(AgentState::Available),
+            DeviceStateState::Inuse
+            | DeviceStateState::Busy
+            | DeviceStateState::Ringing
+            | DeviceStateState::Ringinuse
+            | DeviceStateState::Onhold => Some(AgentState::Busy),
+            DeviceStateState::Unavailable | DeviceStateState::Invalid => Some(AgentState::Offline),
+            DeviceStateState::Unknown => None,
+        }
+    }
+
+    /// Maps the state of the endpoint of an agent. Returns `None` if the state is unknown.
+    pub(crate) fn from_endpoint(endpoint: &Endpoint) -> Option<Self> {
+        match endpoint.state {
+            Some(State::Offline) => Some(AgentState::Offline),
+            Some(State::Online) if endpoint.channel_ids.is_empty() => Some(AgentState::Available),
+            Some(State::Online) => Some(AgentState::Busy),
+            Some(State::Unknown) | None => None,
+        }
+    }
+}
+
+/// Strategy : How the agents to ring are chosen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Ring all the available agents, the first one answering takes the call.
+    #[default]
+    RingAll,
+    /// Ring the available agents one after the other, in turn.
+    RoundRobin,
+    /// Ring the available agent idle for the longest time.
+    LongestIdle,
+    /// Ring the available agent having all the skills required by the caller,
+    /// idle for the longest time.
+    SkillsBased,
+}
+
+impl Strategy {
+    /// Chooses the agents to ring for a caller requiring the given skills.
+    ///
+    /// `cursor` keeps the position of the round-robin strategy between calls.
+    pub(crate) fn select<'a>(
+        &self,
+        agents: &'a [Agent],
+        skills: &[String],
+        cursor: &mut usize,
+    ) -> Vec<&'a Agent> {
+        let available = agents.iter().filter(|a| a.state == AgentState::Available);
+        let longest_idle =
+            |agents: &mut dyn Iterator<Item = &'a Agent>| agents.min_by_key(|a| a.last_call_end);
+
+        match self {
+            Strategy::RingAll => available.collect(),
+            Strategy::RoundRobin => {
+                let len = agents.len();
+                let next = (0..len)
+                    .map(|offset| (*cursor + offset) % len)
+                    .find(|&i| agents[i].state == AgentState::Available);
+                match next {
+                    Some(i) => {
+                        *cursor = (i + 1) % len;
+                        vec![&agents[i]]
+                    }
+                    None => vec![],
+                }
+            }
+            Strategy::LongestIdle => longest_idle(&mut available.into_iter())
+                .into_iter()
+                .collect(),
+            Strategy::SkillsBased => {
+                let mut skilled =
+                    available.filter(|a| skills.iter().all(|skill| a.skills.contains(skill)));
+                longest_idle(&mut skilled).into_iter().collect()
+            }
+        }
+    }
+}
+
+/// QueueEvent : Changes of a queue.
+#[derive(Clone, Debug, PartialEq)]
+pub enum QueueEvent {
+    /// A caller entered the queue.
+    CallerJoined(ChannelId),
+    /// A caller hung up while waiting.
+    CallerAbandoned(ChannelId),
+    /// A caller was connected to an agent.
+    CallerConnected {
+        /// Id of the caller channel.
+        caller: ChannelId,
+        /// Endpoint of the agent.
+        agent: EndpointRef,
+    },
+    /// A caller waited too long and was handed to the overflow action.
+    CallerOverflowed(ChannelId),
+    /// The state of an agent changed.
+    AgentStateChanged {
+        /// Endpoint of the agent.
+        agent: EndpointRef,
+        /// New state of the agent.
+        state: AgentState,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(endpoint: &str, state: AgentState, idle_since: Option<i64>, skills: &[&str]) -> Agent {
+        Agent {
+            endpoint: endpoint.into(),
+            skills: skills.iter().map(|s| s.to_string()).collect(),
+            state,
+            last_call_end: idle_since.map(|ts| DateTime::from_timestamp(ts, 0).unwrap()),
+            calls_taken: 0,
+        }
+    }
+
+    fn endpoints(agents: Vec<&Agent>) -> Vec<String> {
+        agents.iter().map(|a| a.endpoint.to_string()).collect()
+    }
+
+    fn agents() -> Vec<Agent> {
+        vec![
+            agent("PJSIP/1000", AgentState::Available, Some(300), &["sales"]),
+            agent("PJSIP/1001", AgentState::Busy, None, &["sales", "italian"]),
+            agent("PJSIP/1002", AgentState::Available, Some(100), &["support"]),
+            agent(
+                "PJSIP/1003",
+                AgentState::Available,
+                Some(200),
+                &["sales", "italian"],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_strategy_ring_all() {
+        let agents = agents();
+        let selected = Strategy::RingAll.select(&agents, &[], &mut 0);
+        assert_eq!(
+            endpoints(selected),
+            vec!["PJSIP/1000", "PJSIP/1002", "PJSIP/1003"]
+        );
+    }
+
+    #[test]
+    fn test_strategy_round_robin() {
+        let agents = agents();
+        let mut cursor = 0;
+        let mut ring = || endpoints(Strategy::RoundRobin.select(&agents, &[], &mut cursor));
+        assert_eq!(ring(), vec!["PJSIP/1000"]);
+        assert_eq!(ring(), vec!["PJSIP/1002"]);
+        assert_eq!(ring(), vec!["PJSIP/1003"]);
+        assert_eq!(ring(), vec!["PJSIP/1000"]);
+    }
+
+    #[test]
+    fn test_strategy_longest_idle() {
+        let agents = agents();
+        let selected = Strategy::LongestIdle.select(&agents, &[], &mut 0);
+        assert_eq!(endpoints(selected), vec!["PJSIP/1002"]);
+    }
+
+    #[test]
+    fn test_strategy_skills_based() {
+        let agents = agents();
+        let skills = vec!["sales".to_string(), "italian".to_string()];
+        let selected = Strategy::SkillsBased.select(&agents, &skills, &mut 0);
+        assert_eq!(endpoints(selected), vec!["PJSIP/1003"]);
+
+        let skills = vec!["german".to_string()];
+        assert!(Strategy::SkillsBased
+            .select(&agents, &skills, &mut 0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_agent_state_from_device_state() {
+        assert_eq!(
+            AgentState::from_device_state(&DeviceStateState::NotInuse),
+            Some(AgentState::Available)
+        );
+        assert_eq!(
+            AgentState::from_device_state(&DeviceStateState::Ringinuse),
+            Some(AgentState::Busy)
+        );
+        assert_eq!(
+            AgentState::from_device_state(&DeviceStateState::Unavailable),
+            Some(AgentState::Offline)
+        );
+        assert_eq!(
+            AgentState::from_device_state(&DeviceStateState::Unknown),
+            None
+        );
+    }
+}
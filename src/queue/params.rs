@@ -0,0 +1,71 @@
+use crate::apis::media::MediaUri;
+use crate::queue::models::Strategy;
+use derive_new::new;
+use derive_setters::Setters;
+
+#[derive(Clone, Debug, PartialEq, new, Setters)]
+#[setters(prefix = "with_")]
+#[setters(into, strip_option)]
+pub struct QueueOptions {
+    /// Name to give to the holding bridge of the queue.
+    #[setters(skip)]
+    #[new(into)]
+    pub(crate) name: String,
+
+    /// How the agents to ring are chosen.
+    #[new(default)]
+    pub(crate) strategy: Strategy,
+
+    /// Music on hold class played to the waiting callers. If omitted, the default class is used.
+    #[new(default)]
+    pub(crate) moh_class: Option<String>,
+
+    /// Seconds to ring the agents before offering the call again.
+    #[new(value = "15")]
+    pub(crate) ring_timeout: u32,
+
+    /// Seconds a caller can wait before being handed to the overflow action.
+    ///
+    /// If omitted, callers wait until an agent answers or they hang up.
+    #[new(default)]
+    pub(crate) max_wait: Option<u32>,
+
+    /// What to do with the callers waiting longer than `max_wait`.
+    #[new(default)]
+    pub(crate) overflow: Overflow,
+
+    /// Seconds between the announcements of their position to the waiting callers.
+    ///
+    /// If omitted, nothing is announced.
+    #[new(default)]
+    pub(crate) announce_frequency: Option<u32>,
+
+    /// Media played before the position of the caller, "you are currently caller number".
+    #[new(value = r#"MediaUri::sound("queue-thereare")"#)]
+    pub(crate) announce_prefix: MediaUri,
+
+    /// Media played after the position of the caller, "waiting to speak to a representative".
+    #[new(value = r#"MediaUri::sound("queue-callswaiting")"#)]
+    pub(crate) announce_suffix: MediaUri,
+}
+
+/// What to do with a caller that waited too long.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum Overflow {
+    /// Hang up the caller.
+    #[default]
+    Hangup,
+    /// Redirect the caller to an endpoint (e.g. `PJSIP/1000`).
+    Transfer(String),
+    /// Exit the application and continue in the dialplan.
+    Dialplan {
+        /// The context to continue to.
+        context: Option<String>,
+        /// The extension to continue to.
+        extension: String,
+        /// The priority to continue to.
+        priority: Option<String>,
+    },
+    /// Leave the caller in the application, to be handled on `QueueEvent::CallerOverflowed`.
+    Return,
+}
@@ -0,0 +1,550 @@
+//! Call sessions grouping the channels of a logical call.
+//!
+//! A [`SessionTracker`] follows the events and groups the channels that belong together: a
+//! session starts with a channel entering the application, and grows with the channels it
+//! dials, the channels snooping on it, the other half of its local channels, the channels
+//! replacing it and the channels brought in by transfers. Every event touching a channel of
+//! the session is kept in its timeline, and a [`models::CallCompleted`] summary is published
+//! once the last channel is destroyed.
+
+pub mod models;
+
+use crate::apis::channels::models::{Channel, HangupCause};
+use crate::apis::ids::ChannelId;
+use crate::ws::models::Event;
+use crate::AriClient;
+use chrono::Utc;
+use models::{CallCompleted, CallSession, ChannelRole, SessionChannel};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::debug;
+
+/// Number of completed calls buffered for each subscriber.
+const COMPLETED_CAPACITY: usize = 256;
+
+/// Number of events kept for a channel that does not belong to a session yet.
+const PENDING_CAPACITY: usize = 64;
+
+/// Tracks the calls of the application.
+///
+/// The tracker stops following the events when dropped.
+pub struct SessionTracker {
+    sessions: Arc<Mutex<Sessions>>,
+    completed: broadcast::Sender<CallCompleted>,
+    _watcher: DropGuard,
+}
+
+impl SessionTracker {
+    /// Starts tracking the calls from the events received by the client.
+    pub fn start(client: &AriClient) -> Self {
+        let sessions = Arc::new(Mutex::new(Sessions::default()));
+        let completed = broadcast::channel(COMPLETED_CAPACITY).0;
+        let stop_signal = CancellationToken::new();
+
+        tokio::spawn(watch(
+            sessions.clone(),
+            completed.clone(),
+            client.subscribe(),
+            stop_signal.clone(),
+        ));
+
+        SessionTracker {
+            sessions,
+            completed,
+            _watcher: stop_signal.drop_guard(),
+        }
+    }
+
+    /// Subscribes to the summaries of the completed calls.
+    pub fn subscribe(&self) -> broadcast::Receiver<CallCompleted> {
+        self.completed.subscribe()
+    }
+
+    /// The calls in progress.
+    pub fn sessions(&self) -> Vec<CallSession> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .sessions
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// A call in progress, by session id.
+    pub fn session(&self, session_id: &str) -> Option<CallSession> {
+        self.sessions
+            .lock()
+            .unwrap()
+            .sessions
+            .get(session_id)
+            .cloned()
+    }
+
+    /// The call in progress a channel belongs to.
+    pub fn session_of(&self, channel_id: &str) -> Option<CallSession> {
+        let sessions = self.sessions.lock().unwrap();
+        let session_id = sessions.by_channel.get(channel_id)?;
+        sessions.sessions.get(session_id).cloned()
+    }
+}
+
+async fn watch(
+    sessions: Arc<Mutex<Sessions>>,
+    completed: broadcast::Sender<CallCompleted>,
+    mut events: broadcast::Receiver<Event>,
+    stop_signal: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            event = crate::client::next_event(&mut events) => match event {
+                Ok(event) => {
+                    if let Some(call) = sessions.lock().unwrap().apply(&event) {
+                        let _ = completed.send(call);
+                    }
+                }
+                Err(e) => {
+                    debug!("session tracker stopped: {}", e);
+                    break;
+                }
+            },
+        }
+    }
+}
+
+/// The calls in progress, updated from the events.
+#[derive(Debug, Default)]
+struct Sessions {
+    sessions: HashMap<ChannelId, CallSession>,
+    /// Session id, by channel id.
+    by_channel: HashMap<ChannelId, ChannelId>,
+    /// Events of the channels that do not belong to a session yet, as they may join one later.
+    pending: HashMap<ChannelId, Vec<Event>>,
+}
+
+impl Sessions {
+    /// Updates the sessions from an event, returning the call it completed if any.
+    fn apply(&mut self, event: &Event) -> Option<CallCompleted> {
+        for (channel, role, related) in links(event) {
+            self.link(channel, role, &related.id);
+        }
+
+        for channel in channels(event) {
+            if self.by_channel.contains_key(&channel.id) {
+                continue;
+            }
+            if let Some((role, related)) = self.relative(channel) {
+                self.link(channel, role, &related);
+            } else if matches!(event, Event::StasisStart(e) if e.data.channel.id == channel.id) {
+                self.open(channel, event);
+            }
+        }
+
+        self.record(event);
+
+        match event {
+            Event::ChannelDestroyed(e) => self.destroy(&e.data.channel.id, e.data.cause, event),
+            _ => None,
+        }
+    }
+
+    /// Starts a session with the channel it is named after.
+    fn open(&mut self, channel: &Channel, event: &Event) {
+        let session = CallSession {
+            id: channel.id.clone(),
+            channels: vec![],
+            started_at: event.timestamp().unwrap_or_else(Utc::now),
+            timeline: vec![],
+        };
+        self.sessions.insert(channel.id.clone(), session);
+        self.join(&channel.id, channel, ChannelRole::Inbound);
+    }
+
+    /// Adds a channel to the session of a related channel, merging their sessions if both
+    /// already have one.
+    fn link(&mut self, channel: &Channel, role: ChannelRole, related: &str) {
+        let Some(target) = self.by_channel.get(related).cloned() else {
+            return;
+        };
+
+        match self.by_channel.get(&channel.id).cloned() {
+            None => self.join(&target, channel, role),
+            Some(current) if current != target => self.merge(&current, &target),
+            Some(_) => {}
+        }
+    }
+
+    fn join(&mut self, session_id: &ChannelId, channel: &Channel, role: ChannelRole) {
+        let Some(session) = self.sessions.get_mut(session_id) else {
+            return;
+        };
+
+        session.channels.push(SessionChannel {
+            id: channel.id.clone(),
+            name: channel.name.clone(),
+            role,
+            cause: None,
+        });
+        if let Some(events) = self.pending.remove(&channel.id) {
+            session.timeline.extend(events);
+            session.timeline.sort_by_key(Event::timestamp);
+        }
+        self.by_channel
+            .insert(channel.id.clone(), session_id.clone());
+    }
+
+    /// Moves the channels and the timeline of a session into another one.
+    fn merge(&mut self, from: &ChannelId, into: &ChannelId) {
+        let Some(merged) = self.sessions.remove(from) else {
+            return;
+        };
+        let Some(session) = self.sessions.get_mut(into) else {
+            return;
+        };
+
+        for channel in &merged.channels {
+            self.by_channel.insert(channel.id.clone(), into.clone());
+        }
+        session.channels.extend(merged.channels);
+        session.timeline.extend(merged.timeline);
+        session.timeline.sort_by_key(Event::timestamp);
+        session.started_at = session.started_at.min(merged.started_at);
+    }
+
+    /// Finds the channel of a session a new channel is related to by its name: the channel a
+    /// snoop channel spies on, or the other half of a local channel.
+    fn relative(&self, channel: &Channel) -> Option<(ChannelRole, ChannelId)> {
+        if let Some(snooped) = channel
+            .name
+            .strip_prefix("Snoop/")
+            .and_then(|name| name.rsplit_once('-'))
+            .map(|(snooped, _)| snooped)
+        {
+            if self.by_channel.contains_key(snooped) {
+                return Some((ChannelRole::Snoop, snooped.into()));
+            }
+        }
+
+        let (base, half) = channel
+            .name
+            .strip_prefix("Local/")
+            .and_then(|name| name.rsplit_once(';'))?;
+        let other = format!("Local/{base};{}", if half == "1" { "2" } else { "1" });
+        self.sessions
+            .values()
+            .flat_map(|session| &session.channels)
+            .find(|c| c.name == other)
+            .map(|c| (ChannelRole::Local, c.id.clone()))
+    }
+
+    /// Adds the event to the timeline of the sessions it touches.
+    fn record(&mut self, event: &Event) {
+        let mut sessions = vec![];
+        for channel_id in touched(event) {
+            match self.by_channel.get(&channel_id) {
+                Some(session_id) => {
+                    if !sessions.contains(session_id) {
+                        sessions.push(session_id.clone());
+                    }
+                }
+                None => {
+                    let pending = self.pending.entry(channel_id).or_default();
+                    if pending.len() == PENDING_CAPACITY {
+                        pending.remove(0);
+                    }
+                    pending.push(event.clone());
+                }
+            }
+        }
+
+        for session_id in sessions {
+            if let Some(session) = self.sessions.get_mut(&session_id) {
+                session.timeline.push(event.clone());
+            }
+        }
+    }
+
+    /// Marks a channel as destroyed, and completes its session if it was the last one.
+    fn destroy(
+        &mut self,
+        channel_id: &str,
+        cause: HangupCause,
+        event: &Event,
+    ) -> Option<CallCompleted> {
+        self.pending.remove(channel_id);
+
+        let session_id = self.by_channel.get(channel_id)?.clone();
+        let session = self.sessions.get_mut(&session_id)?;
+        if let Some(channel) = session.channels.iter_mut().find(|c| c.id == channel_id) {
+            channel.cause = Some(cause);
+        }
+        if session.channels.iter().any(|c| c.cause.is_none()) {
+            return None;
+        }
+
+        let session = self.sessions.remove(&session_id)?;
+        for channel in &session.channels {
+            self.by_channel.remove(&channel.id);
+        }
+        Some(CallCompleted {
+            session,
+            ended_at: event.timestamp().unwrap_or_else(Utc::now),
+        })
+    }
+}
+
+/// The channels an event tells to belong to the call of another channel, with the role they
+/// play in it.
+fn links(event: &Event) -> Vec<(&Channel, ChannelRole, &Channel)> {
+    let mut links = vec![];
+    match event {
+        Event::StasisStart(e) => {
+            if let Some(replaced) = &e.data.replace_channel {
+                links.push((&e.data.channel, ChannelRole::Replacement, replaced));
+            }
+        }
+        Event::Dial(e) => {
+            if let (Some(caller), Some(peer)) = (&e.data.caller, &e.data.peer) {
+                links.push((peer, ChannelRole::Outbound, caller));
+            }
+        }
+        Event::BridgeBlindTransfer(e) => {
+            let transferer = &e.data.channel;
+            if let Some(transferee) = &e.data.transferee {
+                links.push((transferee, ChannelRole::Transfer, transferer));
+            }
+            if let Some(replacement) = &e.data.replace_channel {
+                links.push((replacement, ChannelRole::Replacement, transferer));
+            }
+        }
+        Event::BridgeAttendedTransfer(e) => {
+            let transferer = &e.data.transferer_first_leg;
+            links.push((
+                &e.data.transferer_second_leg,
+                ChannelRole::Transfer,
+                transferer,
+            ));
+            for channel in [&e.data.transferee, &e.data.transfer_target]
+                .into_iter()
+                .flatten()
+            {
+                links.push((channel, ChannelRole::Transfer, transferer));
+            }
+            if let Some(replacement) = &e.data.replace_channel {
+                links.push((replacement, ChannelRole::Replacement, transferer));
+            }
+        }
+        _ => {}
+    }
+    links
+}
+
+/// The channels carried by an event.
+fn channels(event: &Event) -> Vec<&Channel> {
+    match event {
+        Event::ApplicationMoveFailed(e) => vec![&e.data.channel],
+        Event::BridgeAttendedTransfer(e) => [
+            Some(&e.data.transferer_first_leg),
+            Some(&e.data.transferer_second_leg),
+            e.data.replace_channel.as_ref(),
+            e.data.transferee.as_ref(),
+            e.data.transfer_target.as_ref(),
+            e.data.destination_link_first_leg.as_ref(),
+            e.data.destination_link_second_leg.as_ref(),
+            e.data.destination_threeway_channel.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        Event::BridgeBlindTransfer(e) => [
+            Some(&e.data.channel),
+            e.data.replace_channel.as_ref(),
+            e.data.transferee.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        Event::ChannelCallerId(e) => vec![&e.data.channel],
+        Event::ChannelConnectedLine(e) => vec![&e.data.channel],
+        Event::ChannelCreated(e) => vec![&e.data.channel],
+        Event::ChannelDestroyed(e) => vec![&e.data.channel],
+        Event::ChannelDialplan(e) => vec![&e.data.channel],
+        Event::ChannelDtmfReceived(e) => vec![&e.data.channel],
+        Event::ChannelEnteredBridge(e) => e.data.channel.iter().collect(),
+        Event::ChannelHangupRequest(e) => vec![&e.data.channel],
+        Event::ChannelHold(e) => vec![&e.data.channel],
+        Event::ChannelLeftBridge(e) => vec![&e.data.channel],
+        Event::ChannelStateChange(e) => vec![&e.data.channel],
+        Event::ChannelTalkingFinished(e) => vec![&e.data.channel],
+        Event::ChannelTalkingStarted(e) => vec![&e.data.channel],
+        Event::ChannelToneDetected(e) => vec![&e.data.channel],
+        Event::ChannelUnhold(e) => vec![&e.data.channel],
+        Event::ChannelUserEvent(e) => e.data.channel.iter().collect(),
+        Event::ChannelVarSet(e) => e.data.channel.iter().collect(),
+        Event::Dial(e) => [e.data.caller.as_ref(), e.data.peer.as_ref()]
+            .into_iter()
+            .flatten()
+            .collect(),
+        Event::StasisEnd(e) => vec![&e.data.channel],
+        Event::StasisStart(e) => [Some(&e.data.channel), e.data.replace_channel.as_ref()]
+            .into_iter()
+            .flatten()
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// The ids of the channels an event touches, including the targets of playbacks and recordings.
+fn touched(event: &Event) -> Vec<ChannelId> {
+    let target = match event {
+        Event::PlaybackStarted(e) => e.data.playback.target_uri.as_deref(),
+        Event::PlaybackContinuing(e) => e.data.playback.target_uri.as_deref(),
+        Event::PlaybackFinished(e) => e.data.playback.target_uri.as_deref(),
+        Event::RecordingStarted(e) => Some(e.data.recording.target_uri.as_str()),
+        Event::RecordingFinished(e) => Some(e.data.recording.target_uri.as_str()),
+        Event::RecordingFailed(e) => Some(e.data.recording.target_uri.as_str()),
+        _ => None,
+    };
+
+    let mut ids: Vec<ChannelId> = channels(event).iter().map(|c| c.id.clone()).collect();
+    if let Some(channel_id) = target.and_then(|uri| uri.strip_prefix("channel:")) {
+        ids.push(channel_id.into());
+    }
+    ids.dedup();
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(json: serde_json::Value, at: &str) -> Event {
+        let mut json = json;
+        json["application"] = "test".into();
+        json["timestamp"] = format!("2025-01-07T22:12:{at}+0100").into();
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn channel(id: &str, name: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": name,
+            "state": "Up",
+            "caller": { "name": "", "number": "" },
+            "connected": { "name": "", "number": "" },
+            "accountcode": "",
+            "dialplan": { "context": "default", "exten": "100", "priority": 1, "app_name": "", "app_data": "" },
+            "creationtime": "2025-01-07T22:12:29.369+0100",
+            "language": "en"
+        })
+    }
+
+    fn destroyed(id: &str, name: &str, at: &str) -> Event {
+        event(
+            serde_json::json!({
+                "type": "ChannelDestroyed",
+                "cause": 16,
+                "cause_txt": "Normal Clearing",
+                "channel": channel(id, name),
+            }),
+            at,
+        )
+    }
+
+    #[test]
+    fn test_session_groups_call_legs() {
+        let mut sessions = Sessions::default();
+        let inbound = channel("in", "PJSIP/1000-00000001");
+        let outbound = channel("out", "PJSIP/2000-00000002");
+        let snoop = channel("snoop", "Snoop/in-00000003");
+
+        sessions.apply(&event(
+            serde_json::json!({ "type": "StasisStart", "args": [], "channel": inbound }),
+            "00.000",
+        ));
+        // The outbound channel is created before it is known to belong to the call.
+        sessions.apply(&event(
+            serde_json::json!({ "type": "ChannelCreated", "channel": outbound }),
+            "01.000",
+        ));
+        sessions.apply(&event(
+            serde_json::json!({
+                "type": "Dial",
+                "caller": inbound,
+                "peer": outbound,
+                "dialstatus": "ANSWER",
+            }),
+            "02.000",
+        ));
+        sessions.apply(&event(
+            serde_json::json!({ "type": "StasisStart", "args": [], "channel": snoop }),
+            "03.000",
+        ));
+
+        let session = &sessions.sessions["in"];
+        let roles: Vec<_> = session.channels.iter().map(|c| c.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                ChannelRole::Inbound,
+                ChannelRole::Outbound,
+                ChannelRole::Snoop
+            ]
+        );
+        let timeline: Vec<_> = session.timeline.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            timeline,
+            vec!["StasisStart", "ChannelCreated", "Dial", "StasisStart"]
+        );
+
+        assert!(sessions
+            .apply(&destroyed("out", "PJSIP/2000-00000002", "10.000"))
+            .is_none());
+        assert!(sessions
+            .apply(&destroyed("snoop", "Snoop/in-00000003", "11.000"))
+            .is_none());
+        let completed = sessions
+            .apply(&destroyed("in", "PJSIP/1000-00000001", "12.000"))
+            .unwrap();
+
+        assert_eq!(completed.session.id, "in");
+        assert_eq!(completed.session.timeline.len(), 7);
+        assert_eq!(completed.duration(), chrono::Duration::seconds(12));
+        assert!(sessions.sessions.is_empty());
+        assert!(sessions.by_channel.is_empty());
+    }
+
+    #[test]
+    fn test_session_merged_by_transfer() {
+        let mut sessions = Sessions::default();
+        let first = channel("a", "PJSIP/1000-00000001");
+        let second = channel("b", "PJSIP/1000-00000002");
+
+        for (leg, at) in [(&first, "00.000"), (&second, "01.000")] {
+            sessions.apply(&event(
+                serde_json::json!({ "type": "StasisStart", "args": [], "channel": leg }),
+                at,
+            ));
+        }
+        assert_eq!(sessions.sessions.len(), 2);
+
+        sessions.apply(&event(
+            serde_json::json!({
+                "type": "BridgeAttendedTransfer",
+                "transferer_first_leg": first,
+                "transferer_second_leg": second,
+                "result": "Success",
+                "is_external": false,
+                "destination_type": "bridge",
+            }),
+            "02.000",
+        ));
+
+        assert_eq!(sessions.sessions.len(), 1);
+        assert_eq!(sessions.sessions["a"].channels.len(), 2);
+        assert_eq!(sessions.by_channel["b"], "a");
+    }
+}
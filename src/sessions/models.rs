@@ -0,0 +1,70 @@
+use crate::apis::channels::models::HangupCause;
+use crate::apis::ids::ChannelId;
+use crate::ws::models::Event;
+use chrono::{DateTime, Utc};
+
+/// CallSession : All the channels of one logical call, with everything that happened to them.
+#[derive(Clone, Debug)]
+pub struct CallSession {
+    /// Id of the session, which is the id of the channel the call started with.
+    pub id: ChannelId,
+    /// Channels of the call, in order of appearance.
+    pub channels: Vec<SessionChannel>,
+    /// When the first channel of the call appeared.
+    pub started_at: DateTime<Utc>,
+    /// Events touching any channel of the call, in the order they were raised.
+    pub timeline: Vec<Event>,
+}
+
+impl CallSession {
+    /// Whether the session has a channel.
+    pub fn contains(&self, channel_id: &str) -> bool {
+        self.channels.iter().any(|c| c.id == channel_id)
+    }
+}
+
+/// SessionChannel : A channel of a call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionChannel {
+    /// Id of the channel.
+    pub id: ChannelId,
+    /// Name of the channel (e.g. `PJSIP/1000-00000001`).
+    pub name: String,
+    /// How the channel joined the call.
+    pub role: ChannelRole,
+    /// Hangup cause, once the channel is destroyed.
+    pub cause: Option<HangupCause>,
+}
+
+/// How a channel joined a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelRole {
+    /// The channel the call started with.
+    Inbound,
+    /// A channel dialed from another channel of the call.
+    Outbound,
+    /// A channel snooping on another channel of the call.
+    Snoop,
+    /// The other half of a local channel of the call.
+    Local,
+    /// A channel replacing another channel of the call (e.g. after a masquerade).
+    Replacement,
+    /// A channel brought in by a transfer.
+    Transfer,
+}
+
+/// CallCompleted : Summary of a call whose channels are all destroyed.
+#[derive(Clone, Debug)]
+pub struct CallCompleted {
+    /// The session of the call.
+    pub session: CallSession,
+    /// When the last channel of the call was destroyed.
+    pub ended_at: DateTime<Utc>,
+}
+
+impl CallCompleted {
+    /// Time between the first channel appearing and the last one being destroyed.
+    pub fn duration(&self) -> chrono::Duration {
+        self.ended_at - self.session.started_at
+    }
+}
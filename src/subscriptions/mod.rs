@@ -0,0 +1,155 @@
+//! Event sources and event filter of the application, applied again after each reconnection.
+//!
+//! Asterisk forgets the subscriptions of an application when it restarts, while the WebSocket
+//! reconnects on its own. The [`AriClient`](crate::AriClient) remembers what the application
+//! asked for, and reconciles it with `Applications::get` once connected.
+
+use crate::apis::applications::models::{Application, EventSource, FilteredEvent};
+use crate::apis::applications::params::{Filter, FilterEventsRequest, SubscribeRequest};
+use crate::apis::client::Client;
+use crate::errors::Result;
+use crate::ws::models::{ConnectionState, EventKind};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+use tokio_util::sync::{CancellationToken, DropGuard};
+use tracing::{debug, warn};
+
+/// What the application subscribed to.
+#[derive(Clone, Debug, Default)]
+struct Desired {
+    /// Event sources, in the order they were subscribed to.
+    sources: Vec<EventSource>,
+    /// Kinds of events allowed, when the events are filtered.
+    allowed: Option<Vec<EventKind>>,
+}
+
+/// The subscriptions of the application, shared by the clones of the client.
+#[derive(Clone, Default)]
+pub(crate) struct Subscriptions {
+    desired: Arc<Mutex<Desired>>,
+    /// Stops reconciling after the reconnections when dropped.
+    watcher: Arc<Mutex<Option<DropGuard>>>,
+}
+
+impl Subscriptions {
+    /// Remembers an event source.
+    pub(crate) fn add(&self, source: EventSource) {
+        let mut desired = self.desired.lock().unwrap();
+        if !desired.sources.contains(&source) {
+            desired.sources.push(source);
+        }
+    }
+
+    /// Forgets an event source.
+    pub(crate) fn remove(&self, source: &EventSource) {
+        let mut desired = self.desired.lock().unwrap();
+        desired.sources.retain(|existing| existing != source);
+    }
+
+    /// The event sources remembered.
+    pub(crate) fn sources(&self) -> Vec<EventSource> {
+        self.desired.lock().unwrap().sources.clone()
+    }
+
+    /// Sets the kinds of events allowed, or `None` not to filter the events.
+    pub(crate) fn set_allowed(&self, allowed: Option<Vec<EventKind>>) {
+        self.desired.lock().unwrap().allowed = allowed;
+    }
+
+    /// Reconciles the application now, then again after every reconnection, until `stop`.
+    pub(crate) async fn watch(
+        &self,
+        client: Arc<Client>,
+        application: String,
+        states: broadcast::Receiver<ConnectionState>,
+    ) -> Result<()> {
+        let desired = self.desired.lock().unwrap().clone();
+        reconcile(&client, &application, &desired).await?;
+
+        let stop_signal = CancellationToken::new();
+        tokio::spawn(follow(
+            client,
+            application,
+            self.desired.clone(),
+            states,
+            stop_signal.clone(),
+        ));
+        *self.watcher.lock().unwrap() = Some(stop_signal.drop_guard());
+        Ok(())
+    }
+
+    /// Stops reconciling after the reconnections.
+    pub(crate) fn stop(&self) {
+        self.watcher.lock().unwrap().take();
+    }
+}
+
+/// Reconciles the application each time the WebSocket reconnects. The features of the
+/// server are read again too, as Asterisk may have been upgraded while restarting.
+async fn follow(
+    client: Arc<Client>,
+    application: String,
+    desired: Arc<Mutex<Desired>>,
+    mut states: broadcast::Receiver<ConnectionState>,
+    stop_signal: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            state = states.recv() => match state {
+                Ok(ConnectionState::Reconnected) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                    client.forget_capabilities();
+                    let desired = desired.lock().unwrap().clone();
+                    if let Err(e) = reconcile(&client, &application, &desired).await {
+                        warn!("unable to restore the subscriptions of {}: {}", application, e);
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        }
+    }
+}
+
+/// Subscribes the application to the sources it lost, and pushes the filter again if it
+/// changed.
+async fn reconcile(client: &Client, application: &str, desired: &Desired) -> Result<()> {
+    if desired.sources.is_empty() && desired.allowed.is_none() {
+        return Ok(());
+    }
+    let current: Application = client.applications().get(application).await?;
+
+    let missing: Vec<String> = desired
+        .sources
+        .iter()
+        .filter(|source| !current.is_subscribed(source))
+        .map(EventSource::to_string)
+        .collect();
+    if !missing.is_empty() {
+        debug!("subscribing {} to {:?}", application, missing);
+        client
+            .applications()
+            .subscribe(SubscribeRequest::new(application.into(), missing))
+            .await?;
+    }
+
+    if let Some(allowed) = &desired.allowed {
+        let mut current_allowed: Vec<EventKind> = current
+            .events_allowed
+            .iter()
+            .filter_map(FilteredEvent::kind)
+            .collect();
+        current_allowed.sort();
+        if &current_allowed != allowed {
+            client
+                .applications()
+                .filter_events(
+                    FilterEventsRequest::new(application)
+                        .with_filter(Filter::allow(allowed.iter().copied())),
+                )
+                .await?;
+        }
+    }
+
+    Ok(())
+}
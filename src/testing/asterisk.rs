@@ -0,0 +1,1034 @@
+use crate::apis::channels::models::HangupCause;
+use crate::testing::engine::{reason_cause, Action, Timings};
+use crate::testing::models::{Pickup, RecordedRequest, Response};
+use chrono::Utc;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+/// Formats a timestamp the way Asterisk does.
+pub(crate) fn timestamp() -> String {
+    Utc::now().format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string()
+}
+
+/// A random identifier shaped like the UUIDs generated by Asterisk.
+fn uuid() -> String {
+    let bits = rand::random::<u128>();
+    format!(
+        "{:08x}-{:04x}-4{:03x}-8{:03x}-{:012x}",
+        (bits >> 96) as u32,
+        (bits >> 80) as u16,
+        (bits >> 68) as u16 & 0xfff,
+        (bits >> 56) as u16 & 0xfff,
+        bits as u64 & 0xffff_ffff_ffff
+    )
+}
+
+/// A channel of the simulation.
+#[derive(Clone, Debug)]
+pub(crate) struct SimChannel {
+    /// The channel, as sent by Asterisk.
+    pub(crate) json: Value,
+    /// Variables set on the channel.
+    pub(crate) variables: HashMap<String, String>,
+    /// Stasis application the channel is for.
+    pub(crate) app: Option<String>,
+    /// Whether the channel is in its Stasis application.
+    pub(crate) in_stasis: bool,
+    /// Whether the channel is dialing, i.e. it was originated or dialed.
+    pub(crate) dialed: bool,
+}
+
+/// A playback of the simulation.
+#[derive(Clone, Debug)]
+pub(crate) struct SimPlayback {
+    /// The playback, as sent by Asterisk.
+    pub(crate) json: Value,
+    /// Media to play after the current one.
+    pub(crate) remaining: Vec<String>,
+}
+
+/// The resources of the simulated Asterisk, and the REST routes acting on them.
+///
+/// Changes queue the events Asterisk would raise into the outbox, and the actions taking
+/// time (e.g. the end of a playback) into the schedule.
+#[derive(Debug, Default)]
+pub(crate) struct Asterisk {
+    pub(crate) channels: BTreeMap<String, SimChannel>,
+    pub(crate) bridges: BTreeMap<String, Value>,
+    pub(crate) playbacks: BTreeMap<String, SimPlayback>,
+    pub(crate) live_recordings: BTreeMap<String, Value>,
+    pub(crate) stored_recordings: BTreeMap<String, Value>,
+    pub(crate) endpoints: BTreeMap<(String, String), Value>,
+    pub(crate) device_states: BTreeMap<String, Value>,
+    pub(crate) mailboxes: BTreeMap<String, Value>,
+    pub(crate) applications: BTreeMap<String, Value>,
+    pub(crate) globals: HashMap<String, String>,
+    /// Variables sent along with the channels, as set by `channelvars` in `ari.conf`.
+    pub(crate) channel_vars: Vec<String>,
+    /// How the endpoints (e.g. `PJSIP/1000`) answer, when not right away.
+    pub(crate) pickups: HashMap<String, Pickup>,
+    /// Version reported by `/asterisk/info`, `mock` by default.
+    pub(crate) version: Option<String>,
+    pub(crate) timings: Timings,
+    /// Number of playbacks finished on each channel.
+    pub(crate) finished_playbacks: HashMap<String, usize>,
+    /// Events to send.
+    pub(crate) outbox: Vec<Value>,
+    /// Actions to run later.
+    pub(crate) scheduled: Vec<(Duration, Action)>,
+    /// Number of channels created, used to name them.
+    sequence: u64,
+}
+
+impl Asterisk {
+    /// Registers an application when a WebSocket connects for it.
+    pub(crate) fn register_application(&mut self, name: &str) {
+        self.applications
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                json!({
+                    "name": name,
+                    "channel_ids": [],
+                    "bridge_ids": [],
+                    "endpoint_ids": [],
+                    "device_names": [],
+                    "events_allowed": [],
+                    "events_disallowed": [],
+                })
+            });
+    }
+
+    /// Loses everything but the configuration (endpoints, stored recordings, mailboxes...), as
+    /// when Asterisk restarts.
+    pub(crate) fn restart(&mut self) {
+        let endpoints = std::mem::take(&mut self.endpoints);
+        *self = Asterisk {
+            endpoints: endpoints
+                .into_iter()
+                .map(|(key, mut endpoint)| {
+                    endpoint["channel_ids"] = json!([]);
+                    (key, endpoint)
+                })
+                .collect(),
+            stored_recordings: std::mem::take(&mut self.stored_recordings),
+            device_states: std::mem::take(&mut self.device_states),
+            mailboxes: std::mem::take(&mut self.mailboxes),
+            channel_vars: std::mem::take(&mut self.channel_vars),
+            pickups: std::mem::take(&mut self.pickups),
+            version: self.version.take(),
+            timings: self.timings,
+            ..Asterisk::default()
+        };
+    }
+
+    /// Adds an endpoint that channels can be originated to.
+    pub(crate) fn add_endpoint(&mut self, technology: &str, resource: &str) {
+        self.endpoints.insert(
+            (technology.to_string(), resource.to_string()),
+            json!({
+                "technology": technology,
+                "resource": resource,
+                "state": "online",
+                "channel_ids": [],
+            }),
+        );
+    }
+
+    /// Creates a channel on an endpoint (e.g. `PJSIP/1000`), raising `ChannelCreated`.
+    ///
+    /// The channel enters its Stasis application with [`Asterisk::enter_stasis`].
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new_channel(
+        &mut self,
+        id: Option<&str>,
+        endpoint: &str,
+        state: &str,
+        caller: (&str, &str),
+        dialplan: (&str, &str, i64),
+        app: Option<(&str, &str)>,
+    ) -> std::result::Result<Value, Response> {
+        let id = match id {
+            Some(id) if self.channels.contains_key(id) => {
+                return Err(Response::error(
+                    409,
+                    "Channel with given unique ID already exists",
+                ))
+            }
+            Some(id) => id.to_string(),
+            None => {
+                self.sequence += 1;
+                format!("{}.{}", Utc::now().timestamp(), self.sequence)
+            }
+        };
+        self.sequence += 1;
+
+        let name = match endpoint.strip_prefix("Local/") {
+            Some(local) => format!("Local/{}-{:08x};1", local, self.sequence),
+            None => format!("{}-{:08x}", endpoint, self.sequence),
+        };
+        let (app_name, app_data) = match app {
+            Some((app, "")) => ("Stasis".to_string(), app.to_string()),
+            Some((app, args)) => ("Stasis".to_string(), format!("{app},{args}")),
+            None => (String::new(), String::new()),
+        };
+
+        let channel = json!({
+            "id": id,
+            "name": name,
+            "state": state,
+            "caller": { "name": caller.0, "number": caller.1 },
+            "connected": { "name": "", "number": "" },
+            "accountcode": "",
+            "dialplan": {
+                "context": dialplan.0,
+                "exten": dialplan.1,
+                "priority": dialplan.2,
+                "app_name": app_name,
+                "app_data": app_data,
+            },
+            "creationtime": timestamp(),
+            "language": "en",
+        });
+        self.channels.insert(
+            id.clone(),
+            SimChannel {
+                json: channel.clone(),
+                variables: HashMap::new(),
+                app: app.map(|(app, _)| app.to_string()),
+                in_stasis: false,
+                dialed: false,
+            },
+        );
+        self.refresh_channel_vars(&id);
+        self.emit_channel("ChannelCreated", &id, json!({}));
+
+        Ok(self.channels[&id].json.clone())
+    }
+
+    /// Updates the `channelvars` of a channel from its variables.
+    pub(crate) fn refresh_channel_vars(&mut self, channel_id: &str) {
+        let Some(channel) = self.channels.get_mut(channel_id) else {
+            return;
+        };
+        let Some(json) = channel.json.as_object_mut() else {
+            return;
+        };
+        if self.channel_vars.is_empty() {
+            json.remove("channelvars");
+            return;
+        }
+        // Asterisk sends the variables which are not set as empty.
+        let vars: serde_json::Map<String, Value> = self
+            .channel_vars
+            .iter()
+            .map(|var| {
+                let value = channel.variables.get(var).cloned().unwrap_or_default();
+                (var.clone(), value.into())
+            })
+            .collect();
+        json.insert("channelvars".to_string(), vars.into());
+    }
+
+    /// Answers a REST call.
+    pub(crate) fn route(&mut self, request: &RecordedRequest) -> Response {
+        let segments: Vec<&str> = request.path.trim_start_matches('/').split('/').collect();
+        let method = request.method.to_ascii_uppercase();
+
+        match (method.as_str(), segments.as_slice()) {
+            ("GET", ["asterisk", "info"]) => Response::ok(json!({
+                "system": {
+                    "version": self.version.as_deref().unwrap_or("mock"),
+                    "entity_id": "00:00:00:00:00:00",
+                },
+            })),
+            ("GET", ["asterisk", "ping"]) => Response::ok(json!({
+                "asterisk_id": "00:00:00:00:00:00",
+                "ping": "pong",
+                "timestamp": timestamp(),
+            })),
+            ("GET", ["asterisk", "variable"]) => {
+                let Some(variable) = request.param("variable") else {
+                    return Response::error(400, "Variable name is required");
+                };
+                match self.globals.get(variable) {
+                    Some(value) => Response::ok(json!({ "value": value })),
+                    None => Response::error(404, "Variable not found"),
+                }
+            }
+            ("POST", ["asterisk", "variable"]) => {
+                let Some(variable) = request.param("variable") else {
+                    return Response::error(400, "Variable name is required");
+                };
+                self.globals.insert(
+                    variable.to_string(),
+                    request.param("value").unwrap_or_default().to_string(),
+                );
+                Response::no_content()
+            }
+            ("GET", ["asterisk", "modules" | "logging"]) => Response::ok(json!([])),
+            ("GET", ["asterisk", "modules", _]) => Response::error(404, "Module not found"),
+            ("GET", ["asterisk", "config", "dynamic", ..]) => {
+                Response::error(404, "Object not found")
+            }
+            ("PUT", ["asterisk", "config", "dynamic", ..]) => Response::ok(
+                request
+                    .body
+                    .as_ref()
+                    .and_then(|body| body.get("fields"))
+                    .cloned()
+                    .unwrap_or_else(|| json!([])),
+            ),
+            (_, ["asterisk", ..]) => Response::no_content(),
+
+            ("GET", ["applications"]) => {
+                Response::ok(Value::Array(self.applications.values().cloned().collect()))
+            }
+            ("GET", ["applications", name]) => self.application(name),
+            ("POST" | "DELETE", ["applications", name, "subscription"]) => {
+                let sources: Vec<String> = request
+                    .query
+                    .iter()
+                    .filter(|(key, _)| key == "eventSource")
+                    .flat_map(|(_, value)| value.split(','))
+                    .map(str::to_string)
+                    .collect();
+                self.subscription(name, &sources, method == "POST")
+            }
+            ("PUT", ["applications", name, "eventFilter"]) => {
+                let Some(application) = self.applications.get_mut(*name) else {
+                    return Response::error(404, "Application not found");
+                };
+                // An empty body resets both lists, otherwise only the given lists are set.
+                let filter = request.body.clone().unwrap_or_else(|| json!({}));
+                let reset = filter.as_object().map_or(true, |filter| filter.is_empty());
+                for key in ["allowed", "disallowed"] {
+                    match filter.get(key) {
+                        Some(list) => application[format!("events_{key}")] = list.clone(),
+                        None if reset => application[format!("events_{key}")] = json!([]),
+                        None => {}
+                    }
+                }
+                Response::ok(application.clone())
+            }
+
+            ("GET", ["channels"]) => Response::ok(Value::Array(
+                self.channels.values().map(|c| c.json.clone()).collect(),
+            )),
+            ("POST", ["channels"]) => self.originate(request, None, true),
+            ("POST", ["channels", "create"]) => self.originate(request, None, false),
+            ("POST", ["channels", "externalMedia"]) => {
+                let host = request.param("external_host").unwrap_or_default();
+                let app = request.param("app").unwrap_or_default();
+                match self.new_channel(
+                    request.param("channelId").or(request.param("channel_id")),
+                    &format!("UnicastRTP/{host}"),
+                    "Up",
+                    ("", ""),
+                    ("default", "s", 1),
+                    Some((app, "")),
+                ) {
+                    Ok(channel) => {
+                        self.enter_stasis(channel["id"].as_str().unwrap(), &[]);
+                        Response::ok(channel)
+                    }
+                    Err(response) => response,
+                }
+            }
+            ("GET", ["channels", id]) => match self.channels.get(*id) {
+                Some(channel) => Response::ok(channel.json.clone()),
+                None => Response::error(404, "Channel not found"),
+            },
+            ("POST", ["channels", id]) => self.originate(request, Some(id), true),
+            ("DELETE", ["channels", id]) => {
+                let cause = match request.param("reason_code") {
+                    Some(code) => code
+                        .parse::<u32>()
+                        .map_or(HangupCause::NormalClearing, HangupCause::from),
+                    None => reason_cause(request.param("reason").unwrap_or("normal")),
+                };
+                match self.hangup(id, cause) {
+                    true => Response::no_content(),
+                    false => Response::error(404, "Channel not found"),
+                }
+            }
+            ("POST", ["channels", id, "answer"]) => self.set_channel_state(id, "Up"),
+            ("POST", ["channels", id, "ring"]) => self.set_channel_state(id, "Ringing"),
+            ("POST", ["channels", id, "dial"]) => {
+                let Some(channel) = self.channels.get_mut(*id) else {
+                    return Response::error(404, "Channel not found");
+                };
+                // Only the channels created without dialing can be dialed, once.
+                if channel.dialed {
+                    return Response::error(409, "Channel is already dialed");
+                }
+                channel.dialed = true;
+                let caller_id = request.param("caller").map(str::to_string);
+                if let Some(caller_id) = &caller_id {
+                    self.dial_status(caller_id, id, "");
+                }
+                self.change_state(id, "Ringing");
+                let timeout = request.param("timeout").and_then(|t| t.parse().ok());
+                self.ring(id, caller_id, vec![], timeout);
+                Response::no_content()
+            }
+            ("POST" | "DELETE", ["channels", id, "hold"]) => {
+                if !self.channels.contains_key(*id) {
+                    return Response::error(404, "Channel not found");
+                }
+                let kind = if method == "POST" {
+                    "ChannelHold"
+                } else {
+                    "ChannelUnhold"
+                };
+                self.emit_channel(kind, id, json!({}));
+                Response::no_content()
+            }
+            ("POST", ["channels", id, "continue"]) => {
+                if !self.channels.contains_key(*id) {
+                    return Response::error(404, "Channel not found");
+                }
+                self.leave_stasis(id);
+                Response::no_content()
+            }
+            ("POST", ["channels", id, "move"]) => {
+                let Some(channel) = self.channels.get(*id) else {
+                    return Response::error(404, "Channel not found");
+                };
+                let Some(app) = request.param("app") else {
+                    return Response::error(400, "Application is required");
+                };
+                let args = Self::list_param(request, "appArgs");
+                if !self.applications.contains_key(app) {
+                    let application = channel.app.clone();
+                    let event = json!({
+                        "type": "ApplicationMoveFailed",
+                        "channel": channel.json,
+                        "destination": app,
+                        "args": args,
+                    });
+                    self.emit(application, event);
+                    return Response::no_content();
+                }
+                self.leave_stasis(id);
+                self.channels.get_mut(*id).unwrap().app = Some(app.to_string());
+                self.enter_stasis(id, &args);
+                Response::no_content()
+            }
+            ("GET", ["channels", id, "variable"]) => {
+                let Some(channel) = self.channels.get(*id) else {
+                    return Response::error(404, "Channel not found");
+                };
+                let Some(variable) = request.param("variable") else {
+                    return Response::error(400, "Variable name is required");
+                };
+                match channel.variables.get(variable) {
+                    Some(value) => Response::ok(json!({ "value": value })),
+                    None => Response::error(404, "Provided variable was not found"),
+                }
+            }
+            ("POST", ["channels", id, "variable"]) => {
+                let Some(channel) = self.channels.get_mut(*id) else {
+                    return Response::error(404, "Channel not found");
+                };
+                let Some(variable) = request.param("variable") else {
+                    return Response::error(400, "Variable name is required");
+                };
+                let value = request.param("value").unwrap_or_default();
+                channel
+                    .variables
+                    .insert(variable.to_string(), value.to_string());
+                self.refresh_channel_vars(id);
+                self.emit_channel(
+                    "ChannelVarSet",
+                    id,
+                    json!({ "variable": variable, "value": value }),
+                );
+                Response::no_content()
+            }
+            ("POST", ["channels", id, "play"]) => self.play(
+                &format!("channel:{id}"),
+                self.channels.contains_key(*id),
+                request,
+                None,
+            ),
+            ("POST", ["channels", id, "play", playback_id]) => self.play(
+                &format!("channel:{id}"),
+                self.channels.contains_key(*id),
+                request,
+                Some(playback_id),
+            ),
+            ("POST", ["channels", id, "record"]) => self.record(
+                &format!("channel:{id}"),
+                self.channels.contains_key(*id),
+                request,
+            ),
+            ("POST", ["channels", id, "snoop"]) => self.snoop(id, request, None),
+            ("POST", ["channels", id, "snoop", snoop_id]) => {
+                self.snoop(id, request, Some(snoop_id))
+            }
+            ("GET", ["channels", id, "rtp_statistics"]) => match self.channels.get(*id) {
+                Some(_) => Response::ok(json!({
+                    "txcount": 0, "rxcount": 0, "txploss": 0, "rxploss": 0,
+                    "local_ssrc": 0, "remote_ssrc": 0, "txoctetcount": 0, "rxoctetcount": 0,
+                    "channel_uniqueid": id,
+                })),
+                None => Response::error(404, "Channel not found"),
+            },
+            // DTMF, music on hold, silence, mute, redirect...
+            (_, ["channels", id, _]) => match self.channels.contains_key(*id) {
+                true => Response::no_content(),
+                false => Response::error(404, "Channel not found"),
+            },
+
+            ("GET", ["bridges"]) => {
+                Response::ok(Value::Array(self.bridges.values().cloned().collect()))
+            }
+            ("POST", ["bridges"]) => self.create_bridge(request, None),
+            ("POST", ["bridges", id]) => self.create_bridge(request, Some(id)),
+            ("GET", ["bridges", id]) => match self.bridges.get(*id) {
+                Some(bridge) => Response::ok(bridge.clone()),
+                None => Response::error(404, "Bridge not found"),
+            },
+            ("DELETE", ["bridges", id]) => match self.destroy_bridge(id) {
+                true => Response::no_content(),
+                false => Response::error(404, "Bridge not found"),
+            },
+            ("POST", ["bridges", id, "addChannel"]) => {
+                let channels = Self::list_param(request, "channel");
+                self.add_to_bridge(id, &channels)
+            }
+            ("POST", ["bridges", id, "removeChannel"]) => {
+                let channels = Self::list_param(request, "channel");
+                self.remove_from_bridge(id, &channels)
+            }
+            ("POST", ["bridges", id, "play"]) => self.play(
+                &format!("bridge:{id}"),
+                self.bridges.contains_key(*id),
+                request,
+                None,
+            ),
+            ("POST", ["bridges", id, "play", playback_id]) => self.play(
+                &format!("bridge:{id}"),
+                self.bridges.contains_key(*id),
+                request,
+                Some(playback_id),
+            ),
+            ("POST", ["bridges", id, "record"]) => self.record(
+                &format!("bridge:{id}"),
+                self.bridges.contains_key(*id),
+                request,
+            ),
+            (_, ["bridges", id, _]) => match self.bridges.contains_key(*id) {
+                true => Response::no_content(),
+                false => Response::error(404, "Bridge not found"),
+            },
+
+            // `/playback` is accepted along with the documented `/playbacks`.
+            ("GET", ["playbacks" | "playback", id]) => match self.playbacks.get(*id) {
+                Some(playback) => Response::ok(playback.json.clone()),
+                None => Response::error(404, "Playback not found"),
+            },
+            ("DELETE", ["playbacks" | "playback", id]) => match self.finish_playback(id) {
+                true => Response::no_content(),
+                false => Response::error(404, "Playback not found"),
+            },
+            // The operations do not change how long the playback lasts.
+            ("POST", ["playbacks" | "playback", id, "control"]) => {
+                if !self.playbacks.contains_key(*id) {
+                    return Response::error(404, "Playback not found");
+                }
+                match request.param("operation") {
+                    Some(_) => Response::no_content(),
+                    None => Response::error(400, "Operation is required"),
+                }
+            }
+
+            ("GET", ["recordings", "live", name]) => match self.live_recordings.get(*name) {
+                Some(recording) => Response::ok(recording.clone()),
+                None => Response::error(404, "Recording not found"),
+            },
+            ("DELETE", ["recordings", "live", name]) => match self.live_recordings.remove(*name) {
+                Some(_) => Response::no_content(),
+                None => Response::error(404, "Recording not found"),
+            },
+            ("POST", ["recordings", "live", name, "stop"]) => {
+                match self.live_recordings.remove(*name) {
+                    Some(mut recording) => {
+                        self.stored_recordings.insert(
+                            name.to_string(),
+                            json!({ "name": name, "format": recording["format"] }),
+                        );
+                        recording["state"] = "done".into();
+                        self.emit(
+                            None,
+                            json!({ "type": "RecordingFinished", "recording": recording }),
+                        );
+                        Response::no_content()
+                    }
+                    None => Response::error(404, "Recording not found"),
+                }
+            }
+            (method, ["recordings", "live", name, operation]) => {
+                let Some(recording) = self.live_recordings.get_mut(*name) else {
+                    return Response::error(404, "Recording not found");
+                };
+                match (method, *operation) {
+                    ("POST", "pause") => recording["state"] = "paused".into(),
+                    ("DELETE", "pause") => recording["state"] = "recording".into(),
+                    _ => {}
+                }
+                Response::no_content()
+            }
+            ("GET", ["recordings", "stored"]) => Response::ok(Value::Array(
+                self.stored_recordings.values().cloned().collect(),
+            )),
+            ("GET", ["recordings", "stored", name]) => match self.stored_recordings.get(*name) {
+                Some(recording) => Response::ok(recording.clone()),
+                None => Response::error(404, "Recording not found"),
+            },
+            ("DELETE", ["recordings", "stored", name]) => {
+                match self.stored_recordings.remove(*name) {
+                    Some(_) => Response::no_content(),
+                    None => Response::error(404, "Recording not found"),
+                }
+            }
+            ("POST", ["recordings", "stored", name, "copy"]) => {
+                let Some(recording) = self.stored_recordings.get(*name).cloned() else {
+                    return Response::error(404, "Recording not found");
+                };
+                let Some(destination) = request.param("destinationRecordingName") else {
+                    return Response::error(400, "Destination recording name is required");
+                };
+                if self.stored_recordings.contains_key(destination) {
+                    return Response::error(409, "A recording with the same name already exists");
+                }
+                let copy = json!({ "name": destination, "format": recording["format"] });
+                self.stored_recordings
+                    .insert(destination.to_string(), copy.clone());
+                Response::ok(copy)
+            }
+
+            ("GET", ["endpoints"]) => {
+                Response::ok(Value::Array(self.endpoints.values().cloned().collect()))
+            }
+            ("PUT", ["endpoints", "sendMessage"]) | ("POST", ["endpoints", "refer"]) => Response {
+                status: 202,
+                body: None,
+            },
+            ("GET", ["endpoints", technology]) => {
+                let endpoints: Vec<Value> = self
+                    .endpoints
+                    .iter()
+                    .filter(|((tech, _), _)| tech == technology)
+                    .map(|(_, endpoint)| endpoint.clone())
+                    .collect();
+                match endpoints.is_empty() {
+                    true => Response::error(404, "Endpoints not found"),
+                    false => Response::ok(Value::Array(endpoints)),
+                }
+            }
+            ("GET", ["endpoints", technology, resource]) => {
+                match self
+                    .endpoints
+                    .get(&(technology.to_string(), resource.to_string()))
+                {
+                    Some(endpoint) => Response::ok(endpoint.clone()),
+                    None => Response::error(404, "Endpoint not found"),
+                }
+            }
+            ("PUT", ["endpoints", technology, resource, "sendMessage"])
+            | ("POST", ["endpoints", technology, resource, "refer"]) => {
+                match self
+                    .endpoints
+                    .contains_key(&(technology.to_string(), resource.to_string()))
+                {
+                    true => Response {
+                        status: 202,
+                        body: None,
+                    },
+                    false => Response::error(404, "Endpoint not found"),
+                }
+            }
+
+            ("GET", ["deviceStates"]) => {
+                Response::ok(Value::Array(self.device_states.values().cloned().collect()))
+            }
+            ("GET", ["deviceStates", name]) => match self.device_states.get(*name) {
+                Some(state) => Response::ok(state.clone()),
+                None => Response::error(404, "Device state not found"),
+            },
+            ("PUT", ["deviceStates", name]) => {
+                let Some(state) = request.param("deviceState") else {
+                    return Response::error(400, "Device state is required");
+                };
+                let device_state = json!({ "name": name, "state": state });
+                self.device_states
+                    .insert(name.to_string(), device_state.clone());
+                self.emit(
+                    None,
+                    json!({ "type": "DeviceStateChanged", "device_state": device_state }),
+                );
+                Response::no_content()
+            }
+            ("DELETE", ["deviceStates", name]) => match self.device_states.remove(*name) {
+                Some(_) => Response::no_content(),
+                None => Response::error(404, "Device state not found"),
+            },
+
+            ("GET", ["mailboxes"]) => {
+                Response::ok(Value::Array(self.mailboxes.values().cloned().collect()))
+            }
+            ("GET", ["mailboxes", name]) => match self.mailboxes.get(*name) {
+                Some(mailbox) => Response::ok(mailbox.clone()),
+                None => Response::error(404, "Mailbox not found"),
+            },
+            ("PUT", ["mailboxes", name]) => {
+                let count = |key| {
+                    request
+                        .param(key)
+                        .and_then(|count| count.parse::<u32>().ok())
+                        .unwrap_or_default()
+                };
+                self.mailboxes.insert(
+                    name.to_string(),
+                    json!({
+                        "name": name,
+                        "old_messages": count("oldMessages"),
+                        "new_messages": count("newMessages"),
+                    }),
+                );
+                Response::no_content()
+            }
+            ("DELETE", ["mailboxes", name]) => match self.mailboxes.remove(*name) {
+                Some(_) => Response::no_content(),
+                None => Response::error(404, "Mailbox not found"),
+            },
+
+            ("GET", ["sounds"]) => Response::ok(json!([])),
+            ("GET", ["sounds", _]) => Response::error(404, "Sound not found"),
+
+            ("POST", ["events", "user", name]) => {
+                let Some(application) = request.param("application") else {
+                    return Response::error(400, "Application is required");
+                };
+                let mut event = json!({
+                    "type": "ChannelUserEvent",
+                    "eventname": name,
+                    "userevent": request
+                        .body
+                        .as_ref()
+                        .map(|body| body.get("variables").unwrap_or(body).clone())
+                        .unwrap_or_else(|| json!({})),
+                });
+                for source in Self::list_param(request, "source") {
+                    match source.split_once(':') {
+                        Some(("channel", id)) => {
+                            event["channel"] = self.channels.get(id).map(|c| c.json.clone()).into()
+                        }
+                        Some(("bridge", id)) => {
+                            event["bridge"] = self.bridges.get(id).cloned().into()
+                        }
+                        _ => {}
+                    }
+                }
+                self.emit(Some(application.to_string()), event);
+                Response::no_content()
+            }
+
+            _ => Response::error(404, "Resource not found"),
+        }
+    }
+
+    /// Values of a parameter sent either repeated or comma separated.
+    fn list_param(request: &RecordedRequest, name: &str) -> Vec<String> {
+        request
+            .query
+            .iter()
+            .filter(|(key, _)| key == name)
+            .flat_map(|(_, value)| value.split(','))
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    fn application(&self, name: &str) -> Response {
+        match self.applications.get(name) {
+            Some(application) => Response::ok(application.clone()),
+            None => Response::error(404, "Application not found"),
+        }
+    }
+
+    fn subscription(&mut self, name: &str, sources: &[String], subscribe: bool) -> Response {
+        let Some(application) = self.applications.get_mut(name) else {
+            return Response::error(404, "Application not found");
+        };
+        if sources.is_empty() {
+            return Response::error(400, "Event source is required");
+        }
+
+        for source in sources {
+            let (list, id) = match source.split_once(':') {
+                Some(("channel", id)) => ("channel_ids", id),
+                Some(("bridge", id)) => ("bridge_ids", id),
+                Some(("endpoint", id)) => ("endpoint_ids", id),
+                Some(("deviceState", id)) => ("device_names", id),
+                _ => return Response::error(400, "Invalid event source"),
+            };
+            let ids = application[list].as_array_mut().unwrap();
+            ids.retain(|existing| existing != id);
+            if subscribe {
+                ids.push(id.into());
+            }
+        }
+
+        Response::ok(application.clone())
+    }
+
+    /// Creates a channel, which answers after a while when `dial` is set, like the real
+    /// originate does.
+    fn originate(&mut self, request: &RecordedRequest, id: Option<&str>, dial: bool) -> Response {
+        let Some(endpoint) = request.param("endpoint") else {
+            return Response::error(400, "Endpoint is required");
+        };
+        let caller = match request.param("callerId") {
+            Some(caller_id) => match caller_id.split_once('<') {
+                Some((name, number)) => (name.trim(), number.trim_end_matches('>')),
+                None => ("", caller_id),
+            },
+            None => ("", ""),
+        };
+        let dialplan = (
+            request.param("context").unwrap_or("default"),
+            request.param("extension").unwrap_or("s"),
+            request
+                .param("priority")
+                .and_then(|priority| priority.parse().ok())
+                .unwrap_or(1),
+        );
+        let app = request
+            .param("app")
+            .map(|app| (app, request.param("appArgs").unwrap_or_default()));
+
+        let channel = match self.new_channel(
+            id.or(request.param("channelId")),
+            endpoint,
+            "Down",
+            caller,
+            dialplan,
+            app,
+        ) {
+            Ok(channel) => channel,
+            Err(response) => return response,
+        };
+
+        // Variables are sent under a `variables` key, or as the body itself.
+        let variables = request
+            .body
+            .as_ref()
+            .map(|body| body.get("variables").unwrap_or(body))
+            .and_then(Value::as_object);
+        if let Some(variables) = variables {
+            let sim = self
+                .channels
+                .get_mut(channel["id"].as_str().unwrap())
+                .unwrap();
+            for (key, value) in variables {
+                let value = value
+                    .as_str()
+                    .map_or_else(|| value.to_string(), str::to_string);
+                sim.variables.insert(key.clone(), value);
+            }
+            self.refresh_channel_vars(channel["id"].as_str().unwrap());
+        }
+
+        if dial {
+            let id = channel["id"].as_str().unwrap();
+            self.channels.get_mut(id).unwrap().dialed = true;
+            // Originated channels ring for 30 seconds unless told otherwise.
+            let timeout = request
+                .param("timeout")
+                .map_or(Some(30), |t| t.parse().ok());
+            self.ring(id, None, Self::list_param(request, "appArgs"), timeout);
+        }
+
+        let id = channel["id"].as_str().unwrap();
+        Response::ok(self.channels[id].json.clone())
+    }
+
+    fn set_channel_state(&mut self, channel_id: &str, state: &str) -> Response {
+        match self.change_state(channel_id, state) {
+            true => Response::no_content(),
+            false => Response::error(404, "Channel not found"),
+        }
+    }
+
+    fn snoop(&mut self, channel_id: &str, request: &RecordedRequest, id: Option<&str>) -> Response {
+        if !self.channels.contains_key(channel_id) {
+            return Response::error(404, "Channel not found");
+        }
+        let app = request.param("app").unwrap_or_default();
+        let id = id.or(request.param("snoopId"));
+        match self.new_channel(
+            id,
+            &format!("Snoop/{channel_id}"),
+            "Up",
+            ("", ""),
+            ("default", "s", 1),
+            Some((app, request.param("appArgs").unwrap_or_default())),
+        ) {
+            Ok(channel) => {
+                let args = Self::list_param(request, "appArgs");
+                self.enter_stasis(channel["id"].as_str().unwrap(), &args);
+                Response::ok(channel)
+            }
+            Err(response) => response,
+        }
+    }
+
+    fn play(
+        &mut self,
+        target_uri: &str,
+        target_exists: bool,
+        request: &RecordedRequest,
+        id: Option<&str>,
+    ) -> Response {
+        if !target_exists {
+            return Response::error(404, "Target not found");
+        }
+        let media = Self::list_param(request, "media");
+        if media.is_empty() {
+            return Response::error(400, "Media is required");
+        }
+        let id = id
+            .or(request.param("playbackId"))
+            .map_or_else(uuid, str::to_string);
+
+        let mut playback = json!({
+            "id": id,
+            "media_uri": media[0],
+            "target_uri": target_uri,
+            "language": request.param("lang").unwrap_or("en"),
+            "state": "queued",
+        });
+        if let Some(next) = media.get(1) {
+            playback["next_media_uri"] = next.as_str().into();
+        }
+        self.playbacks.insert(
+            id.clone(),
+            SimPlayback {
+                json: playback.clone(),
+                remaining: media[1..].to_vec(),
+            },
+        );
+        self.start_playback(&id);
+
+        Response {
+            status: 201,
+            body: Some(playback),
+        }
+    }
+
+    fn record(
+        &mut self,
+        target_uri: &str,
+        target_exists: bool,
+        request: &RecordedRequest,
+    ) -> Response {
+        if !target_exists {
+            return Response::error(404, "Target not found");
+        }
+        let (Some(name), Some(format)) = (request.param("name"), request.param("format")) else {
+            return Response::error(400, "Name and format are required");
+        };
+        let exists =
+            self.live_recordings.contains_key(name) || self.stored_recordings.contains_key(name);
+        if exists && request.param("ifExists").unwrap_or("fail") == "fail" {
+            return Response::error(409, "Recording with the same name already exists");
+        }
+
+        let recording = json!({
+            "name": name,
+            "format": format,
+            "target_uri": target_uri,
+            "state": "queued",
+        });
+        let mut started = recording.clone();
+        started["state"] = "recording".into();
+        self.live_recordings
+            .insert(name.to_string(), started.clone());
+        self.emit(
+            None,
+            json!({ "type": "RecordingStarted", "recording": started }),
+        );
+
+        Response {
+            status: 201,
+            body: Some(recording),
+        }
+    }
+
+    fn create_bridge(&mut self, request: &RecordedRequest, id: Option<&str>) -> Response {
+        let id = id
+            .or(request.param("bridgeId"))
+            .map_or_else(uuid, str::to_string);
+        // Creating an existing bridge updates it.
+        if let Some(bridge) = self.bridges.get(&id) {
+            return Response::ok(bridge.clone());
+        }
+
+        let bridge_type = match request.param("type") {
+            Some(types) if types.contains("holding") => "holding",
+            _ => "mixing",
+        };
+        let bridge = json!({
+            "id": id,
+            "technology": if bridge_type == "holding" { "holding_bridge" } else { "simple_bridge" },
+            "bridge_type": bridge_type,
+            "bridge_class": "stasis",
+            "creator": "Stasis",
+            "name": request.param("name").unwrap_or_default(),
+            "channels": [],
+            "creationtime": timestamp(),
+            "video_mode": "talker",
+        });
+        self.bridges.insert(id.clone(), bridge.clone());
+        self.emit(None, json!({ "type": "BridgeCreated", "bridge": bridge }));
+
+        Response::ok(bridge)
+    }
+
+    fn add_to_bridge(&mut self, bridge_id: &str, channels: &[String]) -> Response {
+        if !self.bridges.contains_key(bridge_id) {
+            return Response::error(404, "Bridge not found");
+        }
+        if channels.is_empty() {
+            return Response::error(400, "Channel is required");
+        }
+        if channels.iter().any(|id| !self.channels.contains_key(id)) {
+            return Response::error(400, "Channel not found");
+        }
+
+        self.enter_bridge(bridge_id, channels);
+
+        Response::no_content()
+    }
+
+    fn remove_from_bridge(&mut self, bridge_id: &str, channels: &[String]) -> Response {
+        let Some(bridge) = self.bridges.get(bridge_id) else {
+            return Response::error(404, "Bridge not found");
+        };
+        let members = bridge["channels"].as_array().unwrap();
+        if channels
+            .iter()
+            .any(|id| !members.iter().any(|member| member == id.as_str()))
+        {
+            return Response::error(422, "Channel not in this bridge");
+        }
+        for channel_id in channels {
+            self.leave_bridge(bridge_id, channel_id);
+        }
+
+        Response::no_content()
+    }
+}
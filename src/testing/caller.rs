@@ -0,0 +1,222 @@
+use crate::apis::channels::models::{Channel, HangupCause};
+use crate::testing::Shared;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A simulated party calling into a Stasis application.
+///
+/// Clones act on the same call.
+#[derive(Clone)]
+pub struct Caller {
+    channel_id: String,
+    shared: Arc<Shared>,
+    /// Number of playbacks on the channel already waited for.
+    playbacks_seen: Arc<AtomicUsize>,
+}
+
+impl Caller {
+    pub(crate) fn new(channel_id: String, shared: Arc<Shared>) -> Self {
+        Caller {
+            channel_id,
+            shared,
+            playbacks_seen: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Id of the channel of the caller.
+    pub fn id(&self) -> &str {
+        &self.channel_id
+    }
+
+    /// The channel of the caller, until it is hung up.
+    pub fn channel(&self) -> Option<Channel> {
+        let asterisk = self.shared.asterisk.lock().unwrap();
+        let channel = asterisk.channels.get(&self.channel_id)?;
+        serde_json::from_value(channel.json.clone()).ok()
+    }
+
+    /// Whether the call was answered.
+    pub fn is_answered(&self) -> bool {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .is_answered(&self.channel_id)
+    }
+
+    /// Whether the call is over, hung up by either side.
+    pub fn is_hung_up(&self) -> bool {
+        !self
+            .shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .channels
+            .contains_key(&self.channel_id)
+    }
+
+    /// Presses keys on the phone, raising a `ChannelDtmfReceived` event for each digit.
+    pub fn send_dtmf(&self, digits: &str) {
+        self.shared.update(|asterisk| {
+            for digit in digits.chars() {
+                asterisk.emit_channel(
+                    "ChannelDtmfReceived",
+                    &self.channel_id,
+                    json!({ "digit": digit.to_string(), "duration_ms": 100 }),
+                );
+            }
+        });
+    }
+
+    /// Hangs up the phone, with a normal clearing cause.
+    pub fn hang_up(&self) {
+        self.hang_up_with_cause(HangupCause::NormalClearing);
+    }
+
+    /// Hangs up the phone with a cause (e.g. `UserBusy`).
+    pub fn hang_up_with_cause(&self, cause: HangupCause) {
+        self.shared
+            .update(|asterisk| asterisk.hangup(&self.channel_id, cause));
+    }
+
+    /// Waits until the call is answered, returning `false` when it was hung up before.
+    pub async fn wait_answered(&self) -> bool {
+        self.shared
+            .wait_until(|asterisk| {
+                asterisk.is_answered(&self.channel_id)
+                    || !asterisk.channels.contains_key(&self.channel_id)
+            })
+            .await;
+        self.is_answered()
+    }
+
+    /// Waits until a playback started on the channel since the last wait has finished, and
+    /// nothing else is playing. Returns `false` when the call was hung up before.
+    pub async fn wait_playback_finished(&self) -> bool {
+        let seen = self.playbacks_seen.load(Ordering::SeqCst);
+        let target_uri = format!("channel:{}", self.channel_id);
+        self.shared
+            .wait_until(|asterisk| {
+                let finished = asterisk.finished_playbacks.get(&self.channel_id).copied();
+                let playing = asterisk
+                    .playbacks
+                    .values()
+                    .any(|playback| playback.json["target_uri"] == target_uri.as_str());
+                (finished.unwrap_or_default() > seen && !playing)
+                    || !asterisk.channels.contains_key(&self.channel_id)
+            })
+            .await;
+
+        let asterisk = self.shared.asterisk.lock().unwrap();
+        let finished = asterisk.finished_playbacks.get(&self.channel_id).copied();
+        self.playbacks_seen
+            .store(finished.unwrap_or_default(), Ordering::SeqCst);
+        asterisk.channels.contains_key(&self.channel_id)
+    }
+
+    /// Waits until the call is over.
+    pub async fn wait_hung_up(&self) {
+        self.shared
+            .wait_until(|asterisk| !asterisk.channels.contains_key(&self.channel_id))
+            .await;
+    }
+
+    /// Plays a script, stopping early when the call is hung up by the application.
+    pub async fn run(self, script: CallerScript) {
+        for step in script.steps {
+            if self.is_hung_up() {
+                return;
+            }
+            match step {
+                Step::Wait(duration) => tokio::time::sleep(duration).await,
+                Step::WaitAnswered => {
+                    self.wait_answered().await;
+                }
+                Step::WaitPlaybackFinished => {
+                    self.wait_playback_finished().await;
+                }
+                Step::Dtmf(digits) => self.send_dtmf(&digits),
+                Step::HangUp(cause) => self.hang_up_with_cause(cause),
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Caller {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Caller")
+            .field("channel_id", &self.channel_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// What a caller does, step by step.
+///
+/// ```
+/// use asterisk_ari::testing::CallerScript;
+/// use std::time::Duration;
+///
+/// let script = CallerScript::new()
+///     .wait_answered()
+///     .wait_playback_finished()
+///     .dtmf("1234#")
+///     .wait(Duration::from_secs(1))
+///     .hang_up();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CallerScript {
+    steps: Vec<Step>,
+}
+
+#[derive(Clone, Debug)]
+enum Step {
+    Wait(Duration),
+    WaitAnswered,
+    WaitPlaybackFinished,
+    Dtmf(String),
+    HangUp(HangupCause),
+}
+
+impl CallerScript {
+    /// An empty script.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Does nothing for a while.
+    pub fn wait(mut self, duration: Duration) -> Self {
+        self.steps.push(Step::Wait(duration));
+        self
+    }
+
+    /// Waits until the call is answered.
+    pub fn wait_answered(mut self) -> Self {
+        self.steps.push(Step::WaitAnswered);
+        self
+    }
+
+    /// Waits until what is played on the channel has finished.
+    pub fn wait_playback_finished(mut self) -> Self {
+        self.steps.push(Step::WaitPlaybackFinished);
+        self
+    }
+
+    /// Presses keys.
+    pub fn dtmf(mut self, digits: impl Into<String>) -> Self {
+        self.steps.push(Step::Dtmf(digits.into()));
+        self
+    }
+
+    /// Hangs up.
+    pub fn hang_up(self) -> Self {
+        self.hang_up_with_cause(HangupCause::NormalClearing)
+    }
+
+    /// Hangs up with a cause.
+    pub fn hang_up_with_cause(mut self, cause: HangupCause) -> Self {
+        self.steps.push(Step::HangUp(cause));
+        self
+    }
+}
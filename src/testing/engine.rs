@@ -0,0 +1,476 @@
+//! Events raised by the simulated Asterisk, as the real one would raise them.
+
+use crate::apis::channels::models::HangupCause;
+use crate::testing::asterisk::{timestamp, Asterisk};
+use crate::testing::models::Pickup;
+use serde_json::{json, Value};
+use std::time::Duration;
+
+/// How long the simulated actions take.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Timings {
+    /// Duration of every media played.
+    pub(crate) playback: Duration,
+    /// Time for the far end of a dialed or originated channel to answer.
+    pub(crate) answer: Duration,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Timings {
+            playback: Duration::from_millis(200),
+            answer: Duration::from_millis(50),
+        }
+    }
+}
+
+/// Something happening later in the simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Action {
+    /// The current media of a playback ends.
+    AdvancePlayback(String),
+    /// The far end of a channel answers, the channel entering its Stasis application.
+    Answer {
+        channel_id: String,
+        /// Channel that dialed it, if any.
+        caller_id: Option<String>,
+        args: Vec<String>,
+    },
+    /// The far end of a channel does not answer, the channel being hung up.
+    Reject {
+        channel_id: String,
+        /// Channel that dialed it, if any.
+        caller_id: Option<String>,
+        /// Final status of the dial, e.g. `BUSY`.
+        status: &'static str,
+        cause: HangupCause,
+    },
+}
+
+/// Hangup cause of a reason given to `DELETE /channels/{channelId}`.
+pub(crate) fn reason_cause(reason: &str) -> HangupCause {
+    match reason {
+        "unallocated" => HangupCause::Unallocated,
+        "busy" => HangupCause::UserBusy,
+        "timeout" => HangupCause::NoUserResponse,
+        "no_answer" => HangupCause::NoAnswer,
+        "rejected" => HangupCause::CallRejected,
+        "answered_elsewhere" => HangupCause::AnsweredElsewhere,
+        "number_incomplete" => HangupCause::InvalidNumberFormat,
+        "normal_unspecified" => HangupCause::NormalUnspecified,
+        "congestion" => HangupCause::NormalCircuitCongestion,
+        "failure" => HangupCause::NetworkOutOfOrder,
+        "codec_mismatch" => HangupCause::BearerCapabilityNotAvail,
+        "interworking" => HangupCause::Interworking,
+        _ => HangupCause::NormalClearing,
+    }
+}
+
+impl Asterisk {
+    /// Queues an event for an application, or for every application when `None`.
+    pub(crate) fn emit(&mut self, application: Option<String>, event: Value) {
+        let mut event = event;
+        event["timestamp"] = timestamp().into();
+
+        let applications = match application {
+            Some(application) => vec![application],
+            None => self.applications.keys().cloned().collect(),
+        };
+        for application in applications {
+            if !self.allows(&application, &event["type"]) {
+                continue;
+            }
+            let mut event = event.clone();
+            event["application"] = application.into();
+            self.outbox.push(event);
+        }
+    }
+
+    /// Whether the event filter of an application lets a type of event through.
+    fn allows(&self, application: &str, kind: &Value) -> bool {
+        let Some(application) = self.applications.get(application) else {
+            return true;
+        };
+        let listed = |key: &str| {
+            application[key]
+                .as_array()
+                .map(|list| list.iter().any(|entry| &entry["type"] == kind))
+        };
+        let allowed = application["events_allowed"]
+            .as_array()
+            .map_or(true, Vec::is_empty)
+            || listed("events_allowed") == Some(true);
+        allowed && listed("events_disallowed") != Some(true)
+    }
+
+    /// Queues an event about a channel, for the application the channel is in.
+    pub(crate) fn emit_channel(&mut self, kind: &str, channel_id: &str, fields: Value) {
+        let Some(channel) = self.channels.get(channel_id) else {
+            return;
+        };
+        let applications = self.channel_applications(channel_id);
+        let mut event = json!({ "type": kind, "channel": channel.json });
+        if let (Some(event), Value::Object(fields)) = (event.as_object_mut(), fields) {
+            event.extend(fields);
+        }
+        for application in applications {
+            self.emit(Some(application), event.clone());
+        }
+    }
+
+    /// The applications receiving the events of a channel: its Stasis application, or the
+    /// applications subscribed to it.
+    fn channel_applications(&self, channel_id: &str) -> Vec<String> {
+        if let Some(app) = self.channels.get(channel_id).and_then(|c| c.app.clone()) {
+            return vec![app];
+        }
+        self.applications
+            .iter()
+            .filter(|(_, application)| {
+                application["channel_ids"]
+                    .as_array()
+                    .map_or(false, |ids| ids.iter().any(|id| id == channel_id))
+            })
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// Queues an event about a bridge.
+    fn emit_bridge(&mut self, kind: &str, bridge_id: &str, fields: Value) {
+        let Some(bridge) = self.bridges.get(bridge_id) else {
+            return;
+        };
+        let mut event = json!({ "type": kind, "bridge": bridge });
+        if let (Some(event), Value::Object(fields)) = (event.as_object_mut(), fields) {
+            event.extend(fields);
+        }
+        self.emit(None, event);
+    }
+
+    /// Runs an action later.
+    pub(crate) fn schedule(&mut self, after: Duration, action: Action) {
+        self.scheduled.push((after, action));
+    }
+
+    /// Whether a channel is answered.
+    pub(crate) fn is_answered(&self, channel_id: &str) -> bool {
+        self.channels
+            .get(channel_id)
+            .map_or(false, |channel| channel.json["state"] == "Up")
+    }
+
+    /// Changes the state of a channel, raising `ChannelStateChange`.
+    pub(crate) fn change_state(&mut self, channel_id: &str, state: &str) -> bool {
+        let Some(channel) = self.channels.get_mut(channel_id) else {
+            return false;
+        };
+        if channel.json["state"] != state {
+            channel.json["state"] = state.into();
+            self.emit_channel("ChannelStateChange", channel_id, json!({}));
+        }
+        true
+    }
+
+    /// Puts a channel in its Stasis application, raising `StasisStart`.
+    pub(crate) fn enter_stasis(&mut self, channel_id: &str, args: &[String]) {
+        let Some(channel) = self.channels.get_mut(channel_id) else {
+            return;
+        };
+        if channel.in_stasis || channel.app.is_none() {
+            return;
+        }
+        channel.in_stasis = true;
+        self.emit_channel("StasisStart", channel_id, json!({ "args": args }));
+    }
+
+    /// Takes a channel out of its Stasis application, raising `StasisEnd`.
+    pub(crate) fn leave_stasis(&mut self, channel_id: &str) {
+        let Some(channel) = self.channels.get_mut(channel_id) else {
+            return;
+        };
+        if !channel.in_stasis {
+            return;
+        }
+        channel.in_stasis = false;
+        self.emit_channel("StasisEnd", channel_id, json!({}));
+    }
+
+    /// Adds channels to a bridge, taking them out of the bridge they were in.
+    pub(crate) fn enter_bridge(&mut self, bridge_id: &str, channel_ids: &[String]) {
+        for channel_id in channel_ids {
+            self.leave_bridges(channel_id);
+            if let Some(members) = self
+                .bridges
+                .get_mut(bridge_id)
+                .and_then(|bridge| bridge["channels"].as_array_mut())
+            {
+                members.push(channel_id.as_str().into());
+            }
+            let channel = self.channels.get(channel_id).map(|c| c.json.clone());
+            self.emit_bridge(
+                "ChannelEnteredBridge",
+                bridge_id,
+                json!({ "channel": channel }),
+            );
+        }
+    }
+
+    /// Removes a channel from a bridge, raising `ChannelLeftBridge`.
+    pub(crate) fn leave_bridge(&mut self, bridge_id: &str, channel_id: &str) {
+        let Some(members) = self
+            .bridges
+            .get_mut(bridge_id)
+            .and_then(|bridge| bridge["channels"].as_array_mut())
+        else {
+            return;
+        };
+        let count = members.len();
+        members.retain(|member| member != channel_id);
+        if members.len() == count {
+            return;
+        }
+
+        let channel = self.channels.get(channel_id).map(|c| c.json.clone());
+        self.emit_bridge(
+            "ChannelLeftBridge",
+            bridge_id,
+            json!({ "channel": channel }),
+        );
+    }
+
+    /// Removes a channel from any bridge it is in.
+    fn leave_bridges(&mut self, channel_id: &str) {
+        let bridge_ids: Vec<String> = self.bridges.keys().cloned().collect();
+        for bridge_id in bridge_ids {
+            self.leave_bridge(&bridge_id, channel_id);
+        }
+    }
+
+    /// Destroys a bridge, raising `ChannelLeftBridge` for its channels and `BridgeDestroyed`.
+    pub(crate) fn destroy_bridge(&mut self, bridge_id: &str) -> bool {
+        let Some(bridge) = self.bridges.get(bridge_id) else {
+            return false;
+        };
+        let members: Vec<String> = bridge["channels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|id| id.as_str().map(str::to_string))
+            .collect();
+        for channel_id in members {
+            self.leave_bridge(bridge_id, &channel_id);
+        }
+        self.emit_bridge("BridgeDestroyed", bridge_id, json!({}));
+        self.bridges.remove(bridge_id);
+        true
+    }
+
+    /// Hangs up a channel, raising the events Asterisk raises up to `ChannelDestroyed`.
+    pub(crate) fn hangup(&mut self, channel_id: &str, cause: HangupCause) -> bool {
+        if !self.channels.contains_key(channel_id) {
+            return false;
+        }
+
+        self.emit_channel(
+            "ChannelHangupRequest",
+            channel_id,
+            json!({ "cause": cause.code() }),
+        );
+        let target_uri = format!("channel:{channel_id}");
+        let playbacks: Vec<String> = self
+            .playbacks
+            .iter()
+            .filter(|(_, playback)| playback.json["target_uri"] == target_uri.as_str())
+            .map(|(id, _)| id.clone())
+            .collect();
+        for playback_id in playbacks {
+            self.finish_playback(&playback_id);
+        }
+        self.leave_bridges(channel_id);
+        self.leave_stasis(channel_id);
+        self.emit_channel(
+            "ChannelDestroyed",
+            channel_id,
+            json!({ "cause": cause.code(), "cause_txt": cause.text() }),
+        );
+        self.channels.remove(channel_id);
+        true
+    }
+
+    /// Starts a playback, raising `PlaybackStarted` and scheduling the end of its media.
+    pub(crate) fn start_playback(&mut self, playback_id: &str) {
+        let Some(playback) = self.playbacks.get_mut(playback_id) else {
+            return;
+        };
+        playback.json["state"] = "playing".into();
+        let event = json!({ "type": "PlaybackStarted", "playback": playback.json });
+        let application = self.target_application(playback_id);
+        self.emit(application, event);
+        self.schedule(
+            self.timings.playback,
+            Action::AdvancePlayback(playback_id.to_string()),
+        );
+    }
+
+    /// Stops a playback, raising `PlaybackFinished`.
+    pub(crate) fn finish_playback(&mut self, playback_id: &str) -> bool {
+        let application = self.target_application(playback_id);
+        let Some(mut playback) = self.playbacks.remove(playback_id) else {
+            return false;
+        };
+        playback.json["state"] = "done".into();
+        if let Some(channel_id) = playback.json["target_uri"]
+            .as_str()
+            .and_then(|target_uri| target_uri.strip_prefix("channel:"))
+        {
+            *self
+                .finished_playbacks
+                .entry(channel_id.to_string())
+                .or_default() += 1;
+        }
+        self.emit(
+            application,
+            json!({ "type": "PlaybackFinished", "playback": playback.json }),
+        );
+        true
+    }
+
+    /// The application of the channel a playback is for.
+    fn target_application(&self, playback_id: &str) -> Option<String> {
+        let target_uri = self.playbacks.get(playback_id)?.json["target_uri"].as_str()?;
+        let channel_id = target_uri.strip_prefix("channel:")?;
+        self.channels.get(channel_id)?.app.clone()
+    }
+
+    /// Runs a scheduled action.
+    pub(crate) fn perform(&mut self, action: Action) {
+        match action {
+            Action::AdvancePlayback(playback_id) => {
+                let application = self.target_application(&playback_id);
+                let Some(playback) = self.playbacks.get_mut(&playback_id) else {
+                    return;
+                };
+                if playback.remaining.is_empty() {
+                    self.finish_playback(&playback_id);
+                    return;
+                }
+
+                let media = playback.remaining.remove(0);
+                playback.json["media_uri"] = media.into();
+                playback.json["state"] = "continuing".into();
+                match playback.remaining.first() {
+                    Some(next) => playback.json["next_media_uri"] = next.as_str().into(),
+                    None => {
+                        if let Some(json) = playback.json.as_object_mut() {
+                            json.remove("next_media_uri");
+                        }
+                    }
+                }
+                let event = json!({ "type": "PlaybackContinuing", "playback": playback.json });
+                playback.json["state"] = "playing".into();
+                self.emit(application, event);
+                self.schedule(self.timings.playback, Action::AdvancePlayback(playback_id));
+            }
+            Action::Answer {
+                channel_id,
+                caller_id,
+                args,
+            } => {
+                if !self.change_state(&channel_id, "Up") {
+                    return;
+                }
+                if let Some(caller_id) = caller_id {
+                    self.dial_status(&caller_id, &channel_id, "ANSWER");
+                }
+                self.enter_stasis(&channel_id, &args);
+            }
+            Action::Reject {
+                channel_id,
+                caller_id,
+                status,
+                cause,
+            } => {
+                if let Some(caller_id) = caller_id {
+                    self.dial_status(&caller_id, &channel_id, status);
+                }
+                self.hangup(&channel_id, cause);
+            }
+        }
+    }
+
+    /// Rings the far end of a dialed or originated channel, which picks up as set for its
+    /// endpoint. `timeout` is the number of seconds to ring, if limited.
+    pub(crate) fn ring(
+        &mut self,
+        channel_id: &str,
+        caller_id: Option<String>,
+        args: Vec<String>,
+        timeout: Option<u64>,
+    ) {
+        let Some(channel) = self.channels.get(channel_id) else {
+            return;
+        };
+        let endpoint = channel.json["name"]
+            .as_str()
+            .unwrap_or_default()
+            .rsplit_once('-')
+            .map_or("", |(endpoint, _)| endpoint);
+        let channel_id = channel_id.to_string();
+
+        match self.pickups.get(endpoint).copied().unwrap_or_default() {
+            Pickup::Answer => self.schedule(
+                self.timings.answer,
+                Action::Answer {
+                    channel_id,
+                    caller_id,
+                    args,
+                },
+            ),
+            Pickup::Busy => self.schedule(
+                self.timings.answer,
+                Action::Reject {
+                    channel_id,
+                    caller_id,
+                    status: "BUSY",
+                    cause: HangupCause::UserBusy,
+                },
+            ),
+            Pickup::Ring => {
+                if let Some(timeout) = timeout.filter(|timeout| *timeout > 0) {
+                    self.schedule(
+                        Duration::from_secs(timeout),
+                        Action::Reject {
+                            channel_id,
+                            caller_id,
+                            status: "NOANSWER",
+                            cause: HangupCause::NoAnswer,
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    /// Raises a `Dial` event between two channels.
+    pub(crate) fn dial_status(&mut self, caller_id: &str, peer_id: &str, status: &str) {
+        let Some(peer) = self.channels.get(peer_id) else {
+            return;
+        };
+        let caller = self.channels.get(caller_id).map(|c| c.json.clone());
+        let dialstring = peer.json["name"]
+            .as_str()
+            .unwrap_or_default()
+            .rsplit_once('-')
+            .map_or_else(String::new, |(endpoint, _)| endpoint.to_string());
+        let event = json!({
+            "type": "Dial",
+            "caller": caller,
+            "peer": peer.json,
+            "dialstring": dialstring,
+            "dialstatus": status,
+        });
+        for application in self.channel_applications(peer_id) {
+            self.emit(Some(application), event.clone());
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::testing::models::{RecordedRequest, Response};
+use crate::testing::Shared;
+use futures_util::{SinkExt, StreamExt};
+use std::io;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::WebSocketStream;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+/// Accepts connections until stopped.
+pub(crate) async fn serve(
+    listener: TcpListener,
+    shared: Arc<Shared>,
+    stop_signal: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = stop_signal.cancelled() => break,
+            accepted = listener.accept() => match accepted {
+                Ok((stream, _)) => {
+                    tokio::spawn(connection(stream, shared.clone(), stop_signal.child_token()));
+                }
+                Err(e) => warn!("mock server unable to accept a connection: {}", e),
+            },
+        }
+    }
+}
+
+/// A request read from a connection.
+struct Request {
+    method: String,
+    target: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Splits the target into a path without the `/ari` prefix and the query parameters.
+    fn into_recorded(self) -> Option<RecordedRequest> {
+        let (path, query) = self
+            .target
+            .split_once('?')
+            .unwrap_or((self.target.as_str(), ""));
+        let path = path.strip_prefix("/ari")?.to_string();
+        let body = serde_json::from_slice(&self.body)
+            .ok()
+            .filter(|body: &serde_json::Value| !body.is_null());
+
+        Some(RecordedRequest {
+            method: self.method,
+            path,
+            query: url::form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+            body,
+        })
+    }
+}
+
+/// Serves the requests of a keep-alive connection, until it is closed or upgraded to a WebSocket.
+async fn connection(mut stream: TcpStream, shared: Arc<Shared>, stop_signal: CancellationToken) {
+    let mut buffer = vec![];
+    loop {
+        let request = tokio::select! {
+            _ = stop_signal.cancelled() => return,
+            request = read_request(&mut stream, &mut buffer) => request,
+        };
+        let request = match request {
+            Ok(Some(request)) => request,
+            Ok(None) => return,
+            Err(e) => {
+                debug!("mock server closing a connection: {}", e);
+                return;
+            }
+        };
+
+        if request
+            .header("upgrade")
+            .map_or(false, |upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        {
+            return events(stream, request, shared, stop_signal).await;
+        }
+
+        let response = match request.into_recorded() {
+            Some(request) => shared.handle(request),
+            None => Response::error(404, "Not found"),
+        };
+        if let Err(e) = write_response(&mut stream, &response).await {
+            debug!("mock server unable to respond: {}", e);
+            return;
+        }
+    }
+}
+
+/// Reads the next request, returning `None` when the connection is closed.
+async fn read_request(stream: &mut TcpStream, buffer: &mut Vec<u8>) -> io::Result<Option<Request>> {
+    loop {
+        let mut headers = [httparse::EMPTY_HEADER; 64];
+        let mut parsed = httparse::Request::new(&mut headers);
+        let status = parsed
+            .parse(buffer)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if let httparse::Status::Complete(head) = status {
+            let headers: Vec<(String, String)> = parsed
+                .headers
+                .iter()
+                .map(|h| {
+                    (
+                        h.name.to_string(),
+                        String::from_utf8_lossy(h.value).to_string(),
+                    )
+                })
+                .collect();
+            let mut request = Request {
+                method: parsed.method.unwrap_or_default().to_string(),
+                target: parsed.path.unwrap_or_default().to_string(),
+                headers,
+                body: vec![],
+            };
+            let length = request
+                .header("content-length")
+                .and_then(|length| length.trim().parse::<usize>().ok())
+                .unwrap_or_default();
+
+            if buffer.len() >= head + length {
+                request.body = buffer[head..head + length].to_vec();
+                buffer.drain(..head + length);
+                return Ok(Some(request));
+            }
+        }
+
+        let mut chunk = [0; 4096];
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+}
+
+async fn write_response(stream: &mut TcpStream, response: &Response) -> io::Result<()> {
+    let reason = match response.status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        500 => "Internal Server Error",
+        _ => "",
+    };
+
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason);
+    let body = match (&response.body, response.status) {
+        (_, 204) => vec![],
+        (Some(body), _) => serde_json::to_vec(body)?,
+        (None, _) => vec![],
+    };
+    if response.status != 204 {
+        head.push_str("Content-Type: application/json\r\n");
+        head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    head.push_str("\r\n");
+
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(&body).await?;
+    stream.flush().await
+}
+
+/// Upgrades a connection to the `/ari/events` WebSocket, and forwards the events of its application.
+async fn events(
+    mut stream: TcpStream,
+    request: Request,
+    shared: Arc<Shared>,
+    stop_signal: CancellationToken,
+) {
+    let Some(request_key) = request.header("sec-websocket-key").map(str::to_string) else {
+        let _ = write_response(&mut stream, &Response::error(400, "Missing key")).await;
+        return;
+    };
+    let Some(recorded) = request.into_recorded() else {
+        let _ = write_response(&mut stream, &Response::error(404, "Not found")).await;
+        return;
+    };
+    let app = match recorded.param("app") {
+        Some(app) if recorded.path == "/events" => app.to_string(),
+        _ => {
+            let _ = write_response(&mut stream, &Response::error(400, "Missing app")).await;
+            return;
+        }
+    };
+
+    // Subscribe before answering, so the events injected once the client is connected reach it.
+    let mut frames = shared.frames.subscribe();
+    let kicked = shared.kicked();
+    shared.connected(&app);
+
+    let answer = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(request_key.as_bytes())
+    );
+    if stream.write_all(answer.as_bytes()).await.is_ok() {
+        let socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+        let (mut sender, mut receiver) = socket.split();
+
+        loop {
+            tokio::select! {
+                _ = stop_signal.cancelled() => break,
+                _ = kicked.cancelled() => {
+                    let _ = sender.send(Message::Close(None)).await;
+                    break;
+                }
+                frame = frames.recv() => match frame {
+                    Ok((target, frame)) if target == app => {
+                        if sender.send(Message::Text(frame.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                message = receiver.next() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+    }
+
+    shared.disconnected(&app);
+}
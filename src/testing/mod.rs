@@ -0,0 +1,835 @@
+//! In-process mock of the ARI server, to test applications without Asterisk.
+//!
+//! A [`MockAriServer`] listens on localhost and serves the REST routes called by this crate
+//! from an in-memory model of Asterisk (channels, bridges, playbacks, recordings...), along
+//! with the `/ari/events` WebSocket. Tests inject the events Asterisk would send, and assert
+//! on the REST calls the application made.
+//!
+//! The model raises the events the real Asterisk raises: answering a channel raises
+//! `ChannelStateChange`, playing a media raises `PlaybackStarted` and later `PlaybackFinished`,
+//! hanging up raises `ChannelHangupRequest`, `StasisEnd` and `ChannelDestroyed`... Calls into
+//! the application are placed with [`MockAriServer::call`], the [`Caller`] then pressing keys
+//! or hanging up, possibly following a [`CallerScript`].
+//!
+//! ```no_run
+//! use asterisk_ari::testing::MockAriServer;
+//! use asterisk_ari::AriClient;
+//!
+//! # async fn example() -> asterisk_ari::Result<()> {
+//! let server = MockAriServer::start().await?;
+//!
+//! let mut client = AriClient::with_config(server.config());
+//! client.on_stasis_start(|client, event| async move {
+//!     client.channels().answer(&event.data.channel.id).await
+//! });
+//! client.start("my-application").await?;
+//! server.wait_connected().await;
+//!
+//! let caller = server.call("PJSIP/1000", "my-application");
+//! assert!(caller.wait_answered().await);
+//! assert!(server.received("POST", &format!("/channels/{}/answer", caller.id())));
+//! # Ok(())
+//! # }
+//! ```
+
+mod asterisk;
+mod caller;
+mod engine;
+mod http;
+pub mod models;
+
+pub use caller::{Caller, CallerScript};
+
+use crate::errors::Result;
+use crate::Config;
+use asterisk::Asterisk;
+use models::{ChannelVars, Pickup, RecordedRequest, Response, Stub};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, watch};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+/// Username and password accepted by the mock server.
+const CREDENTIALS: &str = "asterisk";
+
+/// A mock ARI server listening on localhost.
+///
+/// The server stops when dropped.
+pub struct MockAriServer {
+    address: SocketAddr,
+    shared: Arc<Shared>,
+    _server: DropGuard,
+}
+
+impl MockAriServer {
+    /// Starts a server on a free port of localhost.
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            asterisk: Mutex::new(Asterisk::default()),
+            requests: Mutex::new(vec![]),
+            stubs: Mutex::new(vec![]),
+            frames: broadcast::channel(1024).0,
+            connections: watch::channel(0).0,
+            changes: watch::channel(()).0,
+            kick: Mutex::new(CancellationToken::new()),
+        });
+        let stop_signal = CancellationToken::new();
+
+        tokio::spawn(http::serve(listener, shared.clone(), stop_signal.clone()));
+
+        Ok(MockAriServer {
+            address,
+            shared,
+            _server: stop_signal.drop_guard(),
+        })
+    }
+
+    /// Configuration of a client connecting to the server.
+    pub fn config(&self) -> Config {
+        Config::new(format!("http://{}", self.address), CREDENTIALS, CREDENTIALS)
+    }
+
+    /// Address the server listens on.
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Sends an event to the WebSocket clients of its application.
+    ///
+    /// The `application` and `timestamp` fields are filled in when missing; the application
+    /// defaults to the one of the first client connected.
+    pub fn inject(&self, event: serde_json::Value) {
+        let mut event = event;
+        if event.get("application").is_none() {
+            let application = self
+                .shared
+                .asterisk
+                .lock()
+                .unwrap()
+                .applications
+                .keys()
+                .next()
+                .cloned()
+                .unwrap_or_default();
+            event["application"] = application.into();
+        }
+        self.shared.send(event);
+    }
+
+    /// Waits until a client is connected to the events WebSocket.
+    pub async fn wait_connected(&self) {
+        let mut connections = self.shared.connections.subscribe();
+        let _ = connections.wait_for(|count| *count > 0).await;
+    }
+
+    /// Closes the WebSocket connections, as when Asterisk restarts. Clients can connect again.
+    pub fn disconnect_clients(&self) {
+        let mut kick = self.shared.kick.lock().unwrap();
+        kick.cancel();
+        *kick = CancellationToken::new();
+    }
+
+    /// Restarts the simulated Asterisk: the channels, bridges and subscriptions of the
+    /// applications are lost, and the WebSocket connections are closed.
+    pub fn restart(&self) {
+        self.shared.update(Asterisk::restart);
+        self.disconnect_clients();
+    }
+
+    /// The REST calls received so far, in order.
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.shared.requests.lock().unwrap().clone()
+    }
+
+    /// The REST calls received with a method on a path.
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.is(method, path))
+            .collect()
+    }
+
+    /// Whether a REST call was received with a method on a path.
+    pub fn received(&self, method: &str, path: &str) -> bool {
+        !self.requests_to(method, path).is_empty()
+    }
+
+    /// Forgets the REST calls received so far.
+    pub fn clear_requests(&self) {
+        self.shared.requests.lock().unwrap().clear();
+    }
+
+    /// Answers the calls with a method on a path with a canned response, instead of the
+    /// simulated one (e.g. to make a call fail).
+    pub fn stub(
+        &self,
+        method: impl Into<String>,
+        path: impl Into<String>,
+        status: u16,
+        body: Option<serde_json::Value>,
+    ) {
+        self.shared.stubs.lock().unwrap().push(Stub {
+            method: method.into(),
+            path: path.into(),
+            response: Response { status, body },
+        });
+    }
+
+    /// Adds an endpoint (e.g. `PJSIP`, `1000`) to the simulation.
+    pub fn add_endpoint(&self, technology: &str, resource: &str) {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .add_endpoint(technology, resource);
+    }
+
+    /// Sets how an endpoint (e.g. `PJSIP/1000`) answers the channels dialed or originated to
+    /// it; endpoints answer by default.
+    pub fn set_pickup(&self, endpoint: &str, pickup: Pickup) {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .pickups
+            .insert(endpoint.to_string(), pickup);
+    }
+
+    /// Reports a version of Asterisk, e.g. `18.20.0`, to test the features it lacks.
+    pub fn set_version(&self, version: impl Into<String>) {
+        let version = version.into();
+        self.shared
+            .update(|asterisk| asterisk.version = Some(version));
+    }
+
+    /// Sends variables along with the channels, as the `channelvars` option of `ari.conf`.
+    pub fn set_channel_vars(&self, vars: impl Into<ChannelVars>) {
+        self.shared.update(|asterisk| {
+            asterisk.channel_vars = vars.into().0;
+            let channel_ids: Vec<String> = asterisk.channels.keys().cloned().collect();
+            for channel_id in channel_ids {
+                asterisk.refresh_channel_vars(&channel_id);
+            }
+        });
+    }
+
+    /// Sets how long every media played lasts (200ms by default).
+    pub fn set_playback_duration(&self, duration: Duration) {
+        self.shared.asterisk.lock().unwrap().timings.playback = duration;
+    }
+
+    /// Sets how long dialed and originated channels take to answer (50ms by default).
+    pub fn set_answer_delay(&self, delay: Duration) {
+        self.shared.asterisk.lock().unwrap().timings.answer = delay;
+    }
+
+    /// Places a call from an endpoint (e.g. `PJSIP/1000`) into a Stasis application, raising
+    /// `ChannelCreated` and `StasisStart`.
+    pub fn call(&self, endpoint: &str, app: &str) -> Caller {
+        let number = endpoint.rsplit('/').next().unwrap_or_default();
+        let channel_id = self.shared.update(|asterisk| {
+            let channel = asterisk
+                .new_channel(
+                    None,
+                    endpoint,
+                    "Ring",
+                    ("", number),
+                    ("default", "s", 1),
+                    Some((app, "")),
+                )
+                .expect("generated channel ids are unique");
+            let channel_id = channel["id"].as_str().unwrap_or_default().to_string();
+            asterisk.enter_stasis(&channel_id, &[]);
+            channel_id
+        });
+
+        Caller::new(channel_id, self.shared.clone())
+    }
+
+    /// Adds a channel to the simulation, as if it was already answered in the Stasis
+    /// application, and returns it as sent by Asterisk. No event is sent.
+    pub fn add_channel(&self, id: &str, endpoint: &str, app: &str) -> Result<serde_json::Value> {
+        let mut asterisk = self.shared.asterisk.lock().unwrap();
+        let channel = asterisk
+            .new_channel(
+                Some(id),
+                endpoint,
+                "Up",
+                ("", ""),
+                ("default", "s", 1),
+                Some((app, "")),
+            )
+            .map_err(|_| {
+                crate::errors::AriError::Internal(format!("channel {id} already exists"))
+            })?;
+        asterisk.outbox.clear();
+        if let Some(channel) = asterisk.channels.get_mut(id) {
+            channel.in_stasis = true;
+        }
+        Ok(channel)
+    }
+
+    /// A channel of the simulation, as sent by Asterisk.
+    pub fn channel(&self, id: &str) -> Option<serde_json::Value> {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .channels
+            .get(id)
+            .map(|channel| channel.json.clone())
+    }
+
+    /// A bridge of the simulation, as sent by Asterisk.
+    pub fn bridge(&self, id: &str) -> Option<serde_json::Value> {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .bridges
+            .get(id)
+            .cloned()
+    }
+
+    /// A playback of the simulation, as sent by Asterisk.
+    pub fn playback(&self, id: &str) -> Option<serde_json::Value> {
+        self.shared
+            .asterisk
+            .lock()
+            .unwrap()
+            .playbacks
+            .get(id)
+            .map(|playback| playback.json.clone())
+    }
+}
+
+/// State shared by the server and its connections.
+pub(crate) struct Shared {
+    asterisk: Mutex<Asterisk>,
+    requests: Mutex<Vec<RecordedRequest>>,
+    stubs: Mutex<Vec<Stub>>,
+    /// Frames to send, along with the application they are for.
+    frames: broadcast::Sender<(String, String)>,
+    /// Number of WebSocket clients connected.
+    connections: watch::Sender<usize>,
+    /// Cancelled to close the WebSocket connections.
+    kick: Mutex<CancellationToken>,
+    /// Notified whenever the simulation changes.
+    changes: watch::Sender<()>,
+}
+
+impl Shared {
+    /// Records a REST call and answers it.
+    pub(crate) fn handle(self: &Arc<Self>, request: RecordedRequest) -> Response {
+        self.requests.lock().unwrap().push(request.clone());
+
+        let stub = self
+            .stubs
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|stub| request.is(&stub.method, &stub.path))
+            .map(|stub| stub.response.clone());
+        if let Some(response) = stub {
+            return response;
+        }
+
+        self.update(|asterisk| asterisk.route(&request))
+    }
+
+    /// Changes the simulation, then sends the events raised and schedules the actions.
+    pub(crate) fn update<R>(self: &Arc<Self>, change: impl FnOnce(&mut Asterisk) -> R) -> R {
+        let mut asterisk = self.asterisk.lock().unwrap();
+        let result = change(&mut asterisk);
+        self.flush(asterisk);
+        result
+    }
+
+    fn flush(self: &Arc<Self>, mut asterisk: MutexGuard<Asterisk>) {
+        // Sent under the lock, so the events keep the order they were raised in.
+        for event in std::mem::take(&mut asterisk.outbox) {
+            self.send(event);
+        }
+        for (after, action) in std::mem::take(&mut asterisk.scheduled) {
+            let shared = self.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(after).await;
+                let mut asterisk = shared.asterisk.lock().unwrap();
+                asterisk.perform(action);
+                shared.flush(asterisk);
+            });
+        }
+        drop(asterisk);
+
+        self.changes.send_replace(());
+    }
+
+    /// Waits until the simulation satisfies a condition.
+    pub(crate) async fn wait_until(&self, condition: impl Fn(&Asterisk) -> bool) {
+        let mut changes = self.changes.subscribe();
+        loop {
+            if condition(&self.asterisk.lock().unwrap()) {
+                return;
+            }
+            if changes.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Sends an event to the clients of the application it names.
+    pub(crate) fn send(&self, event: serde_json::Value) {
+        let mut event = event;
+        if event.get("timestamp").is_none() {
+            event["timestamp"] = asterisk::timestamp().into();
+        }
+        let application = event["application"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        // Sending only fails when no client is connected, and the event is lost as it would be.
+        let _ = self.frames.send((application, event.to_string()));
+    }
+
+    pub(crate) fn kicked(&self) -> CancellationToken {
+        self.kick.lock().unwrap().clone()
+    }
+
+    pub(crate) fn connected(&self, application: &str) {
+        self.asterisk
+            .lock()
+            .unwrap()
+            .register_application(application);
+        self.connections.send_modify(|count| *count += 1);
+    }
+
+    pub(crate) fn disconnected(&self, _application: &str) {
+        self.connections.send_modify(|count| *count -= 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apis::applications::models::EventSource;
+    use crate::apis::bridges::params::AddChannelRequest;
+    use crate::apis::capabilities::{AsteriskVersion, Capability};
+    use crate::apis::channels::models::HangupCause;
+    use crate::apis::channels::params::{
+        App, CreateRequest, DeleteRequest, ExternalMediaRequest, OriginateRequest, PlayRequest,
+    };
+    use crate::apis::endpoints::params::ReferRequest;
+    use crate::ws::models::{ConnectionState, Event, EventKind};
+    use crate::{AriClient, AriError};
+
+    #[tokio::test]
+    async fn test_rest_calls_are_simulated_and_recorded() {
+        let server = MockAriServer::start().await.unwrap();
+        let client = AriClient::with_config(server.config());
+
+        let channel =
+            client
+                .channels()
+                .originate(OriginateRequest::new("PJSIP/1000").with_app(
+                    crate::apis::channels::params::App::new("test").with_args("outbound"),
+                ))
+                .await
+                .unwrap();
+        assert_eq!(client.channels().list().await.unwrap().len(), 1);
+
+        client.channels().answer(&channel.id).await.unwrap();
+        let bridge = client.bridges().create(Default::default()).await.unwrap();
+        client
+            .bridges()
+            .add_channel(AddChannelRequest::new(&bridge.id, &channel.id))
+            .await
+            .unwrap();
+        let playback = client
+            .channels()
+            .play(PlayRequest::new(&channel.id, "sound:hello-world"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            client.bridges().get(&bridge.id).await.unwrap().channels,
+            vec![channel.id.clone()]
+        );
+        assert_eq!(playback.media_uri.as_deref(), Some("sound:hello-world"));
+        assert!(server.received("POST", &format!("/channels/{}/answer", channel.id)));
+        let originate = &server.requests_to("POST", "/channels")[0];
+        assert_eq!(originate.param("endpoint"), Some("PJSIP/1000"));
+        assert_eq!(originate.param("appArgs"), Some("outbound"));
+
+        assert!(client.channels().get("missing").await.is_err());
+
+        server.stub("GET", "/channels", 500, None);
+        assert!(client.channels().list().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_injected_events_reach_the_client() {
+        let server = MockAriServer::start().await.unwrap();
+        let channel = server.add_channel("1", "PJSIP/1000", "test").unwrap();
+        let mut client = AriClient::with_config(server.config());
+        let mut events = client.subscribe();
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        server.inject(serde_json::json!({
+            "type": "ChannelDtmfReceived",
+            "digit": "5",
+            "duration_ms": 100,
+            "channel": channel,
+        }));
+
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            Event::ChannelDtmfReceived(e) => {
+                assert_eq!(e.data.digit, '5');
+                assert_eq!(e.application, "test");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+
+    /// Names of the events received until one named `last`.
+    async fn events_until(
+        events: &mut tokio::sync::broadcast::Receiver<Event>,
+        last: &str,
+    ) -> Vec<String> {
+        let mut names = vec![];
+        while names.last().map_or(true, |name| name != last) {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(!matches!(event, Event::Unknown(_)), "unknown event");
+            names.push(event.to_string());
+        }
+        names
+    }
+
+    #[tokio::test]
+    async fn test_rest_calls_raise_the_events_of_asterisk() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_playback_duration(Duration::from_millis(10));
+        let mut client = AriClient::with_config(server.config());
+        let mut events = client.subscribe();
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let caller = server.call("PJSIP/1000", "test");
+        assert_eq!(
+            events_until(&mut events, "StasisStart").await,
+            ["ChannelCreated", "StasisStart"]
+        );
+
+        client.channels().answer(caller.id()).await.unwrap();
+        assert_eq!(
+            events_until(&mut events, "ChannelStateChange").await,
+            ["ChannelStateChange"]
+        );
+        assert!(caller.is_answered());
+
+        client
+            .channels()
+            .play(PlayRequest::new(caller.id(), "sound:hello-world"))
+            .await
+            .unwrap();
+        assert_eq!(
+            events_until(&mut events, "PlaybackFinished").await,
+            ["PlaybackStarted", "PlaybackFinished"]
+        );
+
+        let bridge = client.bridges().create(Default::default()).await.unwrap();
+        client
+            .bridges()
+            .add_channel(AddChannelRequest::new(&bridge.id, caller.id()))
+            .await
+            .unwrap();
+        assert_eq!(
+            events_until(&mut events, "ChannelEnteredBridge").await,
+            ["BridgeCreated", "ChannelEnteredBridge"]
+        );
+
+        client
+            .channels()
+            .delete(DeleteRequest::new(caller.id()))
+            .await
+            .unwrap();
+        assert_eq!(
+            events_until(&mut events, "ChannelDestroyed").await,
+            [
+                "ChannelHangupRequest",
+                "ChannelLeftBridge",
+                "StasisEnd",
+                "ChannelDestroyed"
+            ]
+        );
+        assert!(caller.is_hung_up());
+    }
+
+    #[tokio::test]
+    async fn test_events_are_filtered_by_handler() {
+        let server = MockAriServer::start().await.unwrap();
+        let mut client =
+            AriClient::with_config(server.config()).with_event_filter([EventKind::StasisEnd]);
+        client.on_stasis_start(|_, _| async { Ok(()) });
+        let mut events = client.subscribe();
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let application = client.applications().get("test").await.unwrap();
+        let allowed: Vec<_> = application
+            .events_allowed
+            .iter()
+            .filter_map(|event| event.kind())
+            .collect();
+        assert_eq!(allowed, [EventKind::StasisEnd, EventKind::StasisStart]);
+
+        let caller = server.call("PJSIP/1000", "test");
+        caller.hang_up();
+        assert_eq!(
+            events_until(&mut events, "StasisEnd").await,
+            ["StasisStart", "StasisEnd"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_survive_a_restart() {
+        let server = MockAriServer::start().await.unwrap();
+        let mut client = AriClient::with_config(server.config()).with_event_filter([]);
+        client.on_stasis_start(|_, _| async { Ok(()) });
+        let endpoint = EventSource::endpoint("PJSIP/1000");
+        client.subscribe_to(endpoint.clone()).await.unwrap();
+        let mut states = client.connection_states();
+        client.start("test").await.unwrap();
+        client
+            .subscribe_to(EventSource::device_state("Custom:agent"))
+            .await
+            .unwrap();
+        assert_eq!(client.subscriptions().len(), 2);
+
+        let application = client.applications().get("test").await.unwrap();
+        assert!(application.is_subscribed(&endpoint));
+        assert_eq!(application.device_names, ["Custom:agent"]);
+
+        server.restart();
+        while states.recv().await.unwrap() != ConnectionState::Reconnected {}
+        let restored = async {
+            loop {
+                let application = client.applications().get("test").await.unwrap();
+                if application.is_subscribed(&endpoint) && !application.events_allowed.is_empty() {
+                    return application;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        let application = tokio::time::timeout(Duration::from_secs(5), restored)
+            .await
+            .unwrap();
+        assert_eq!(application.device_names, ["Custom:agent"]);
+        assert_eq!(
+            application.events_allowed[0].kind(),
+            Some(EventKind::StasisStart)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_channel_vars_are_sent_along_with_the_channels() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_channel_vars("channelvars = queue_position, CALLERID(name)");
+        let client = AriClient::with_config(server.config());
+
+        let channel = client
+            .channels()
+            .originate(OriginateRequest::new("PJSIP/1000"))
+            .await
+            .unwrap();
+        assert_eq!(channel.var("queue_position"), Some(""));
+        assert_eq!(channel.var("unlisted"), None);
+
+        client
+            .channels()
+            .set_variable(&channel.id, "queue_position", "3")
+            .await
+            .unwrap();
+        let channel = client.channels().get(&channel.id).await.unwrap();
+        assert_eq!(channel.var_as::<u32>("queue_position"), Some(3));
+        assert_eq!(channel.channel_vars.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_requests_follow_the_ari_definitions() {
+        let server = MockAriServer::start().await.unwrap();
+        let client = AriClient::with_config(server.config());
+
+        let channel = client
+            .channels()
+            .originate(
+                OriginateRequest::new("PJSIP/1000")
+                    .with_variables(serde_json::json!({ "CALLERID(name)": "Alice" })),
+            )
+            .await
+            .unwrap();
+        let originate = &server.requests_to("POST", "/channels")[0];
+        assert_eq!(
+            originate.body,
+            Some(serde_json::json!({ "variables": { "CALLERID(name)": "Alice" } }))
+        );
+
+        let variable = client
+            .channels()
+            .get_variable(&channel.id, "CALLERID(name)")
+            .await
+            .unwrap();
+        assert_eq!(variable.value, "Alice");
+        assert!(server.received("GET", &format!("/channels/{}/variable", channel.id)));
+
+        let playback = client
+            .channels()
+            .play(PlayRequest::new(&channel.id, "sound:hello-world"))
+            .await
+            .unwrap();
+        let playback_id = playback.id.unwrap();
+        let fetched = client.playbacks().get(&playback_id).await.unwrap();
+        assert_eq!(fetched.id, Some(playback_id.clone()));
+        assert!(server.received("GET", &format!("/playbacks/{playback_id}")));
+
+        let created = client
+            .channels()
+            .create(CreateRequest::new("PJSIP/1001", App::new("test")))
+            .await
+            .unwrap();
+        assert!(server.received("POST", "/channels/create"));
+        assert_eq!(server.requests_to("POST", "/channels").len(), 1);
+        assert!(client.channels().get(&created.id).await.is_ok());
+
+        client
+            .endpoints()
+            .refer(ReferRequest::new(
+                "pjsip:1000",
+                "pjsip:asterisk",
+                "pjsip:1001",
+            ))
+            .await
+            .unwrap();
+        assert!(server.received("POST", "/endpoints/refer"));
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_features_fail_fast() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_version("16.2.0");
+        let client = AriClient::with_config(server.config());
+
+        let request = ExternalMediaRequest::new("test", "127.0.0.1:4000", "ulaw");
+        match client.channels().external_media(request).await {
+            Err(AriError::Unsupported { required, actual }) => {
+                assert_eq!(required, "16.6.0");
+                assert_eq!(actual, "16.2.0");
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(!server.received("POST", "/channels/externalMedia"));
+
+        client.bridges().create(Default::default()).await.unwrap();
+        let capabilities = client.capabilities().await;
+        assert_eq!(capabilities.version(), Some(AsteriskVersion::new(16, 2, 0)));
+        assert!(capabilities.supports(Capability::VideoSfuBridges));
+        assert_eq!(server.requests_to("GET", "/asterisk/info").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_are_read_again_after_a_reconnection() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_version("16.2.0");
+        let mut client = AriClient::with_config(server.config());
+        let mut states = client.connection_states();
+        client.start("test").await.unwrap();
+        let version = client.capabilities().await.version();
+        assert_eq!(version, Some(AsteriskVersion::new(16, 2, 0)));
+
+        server.set_version("20.9.0");
+        server.restart();
+        while states.recv().await.unwrap() != ConnectionState::Reconnected {}
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while client.capabilities().await.version() != Some(AsteriskVersion::new(20, 9, 0)) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(client
+            .capabilities()
+            .await
+            .supports(Capability::ChannelTenantId));
+    }
+
+    #[tokio::test]
+    async fn test_capabilities_are_assumed_when_the_version_is_unavailable() {
+        let server = MockAriServer::start().await.unwrap();
+        server.stub("GET", "/asterisk/info", 500, None);
+        let client = AriClient::with_config(server.config());
+
+        let capabilities = client.capabilities().await;
+        assert_eq!(capabilities.version(), None);
+        client
+            .channels()
+            .external_media(ExternalMediaRequest::new("test", "127.0.0.1:4000", "ulaw"))
+            .await
+            .unwrap();
+        assert!(server.received("POST", "/channels/externalMedia"));
+        assert_eq!(server.requests_to("GET", "/asterisk/info").len(), 1);
+
+        client.forget_capabilities();
+        client.capabilities().await;
+        assert_eq!(server.requests_to("GET", "/asterisk/info").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_callers_follow_their_script() {
+        let server = MockAriServer::start().await.unwrap();
+        server.set_playback_duration(Duration::from_millis(10));
+        let mut client = AriClient::with_config(server.config());
+        client.on_stasis_start(|client, event| async move {
+            let channel_id = &event.data.channel.id;
+            client.channels().answer(channel_id).await?;
+            client
+                .channels()
+                .play(PlayRequest::new(channel_id, "sound:welcome"))
+                .await
+                .map(drop)
+        });
+        let mut events = client.subscribe();
+        client.start("test").await.unwrap();
+        server.wait_connected().await;
+
+        let caller = server.call("PJSIP/1000", "test");
+        let script = CallerScript::new()
+            .wait_answered()
+            .wait_playback_finished()
+            .dtmf("12")
+            .hang_up_with_cause(HangupCause::UserBusy);
+        tokio::time::timeout(Duration::from_secs(5), caller.clone().run(script))
+            .await
+            .unwrap();
+
+        assert!(caller.is_hung_up());
+        let names = events_until(&mut events, "ChannelDestroyed").await;
+        let dtmf = names
+            .iter()
+            .filter(|name| *name == "ChannelDtmfReceived")
+            .count();
+        assert_eq!(dtmf, 2);
+        assert!(names.contains(&"PlaybackFinished".to_string()));
+    }
+}
@@ -0,0 +1,115 @@
+/// RecordedRequest : A REST call received by the mock server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedRequest {
+    /// HTTP method (e.g. `POST`).
+    pub method: String,
+    /// Path, without the `/ari` prefix (e.g. `/channels/1736284349.1/answer`).
+    pub path: String,
+    /// Query parameters, in the order they were sent.
+    pub query: Vec<(String, String)>,
+    /// JSON body, when one was sent.
+    pub body: Option<serde_json::Value>,
+}
+
+impl RecordedRequest {
+    /// The first value of a query parameter.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.query
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the request was made with a method on a path.
+    pub fn is(&self, method: &str, path: &str) -> bool {
+        self.method.eq_ignore_ascii_case(method) && self.path == path
+    }
+}
+
+/// Pickup : How a simulated endpoint answers the channels dialed or originated to it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Pickup {
+    /// The endpoint answers after the answer delay.
+    #[default]
+    Answer,
+    /// The endpoint is busy: the channel is hung up after the answer delay.
+    Busy,
+    /// The endpoint rings until the timeout of the dial, if any.
+    Ring,
+}
+
+/// A canned response, returned instead of the simulated one.
+#[derive(Clone, Debug)]
+pub(crate) struct Stub {
+    pub(crate) method: String,
+    pub(crate) path: String,
+    pub(crate) response: Response,
+}
+
+/// A response of the mock server.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Response {
+    pub(crate) status: u16,
+    pub(crate) body: Option<serde_json::Value>,
+}
+
+impl Response {
+    pub(crate) fn ok(body: serde_json::Value) -> Self {
+        Response {
+            status: 200,
+            body: Some(body),
+        }
+    }
+
+    pub(crate) fn no_content() -> Self {
+        Response {
+            status: 204,
+            body: None,
+        }
+    }
+
+    /// An error, with the body Asterisk sends along.
+    pub(crate) fn error(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: Some(serde_json::json!({ "message": message })),
+        }
+    }
+}
+
+/// ChannelVars : Channel variables the mock sends along with the channels, as the
+/// `channelvars` option in the `[general]` section of `ari.conf`.
+///
+/// Parses from a comma separated list, e.g. `CALLERID(name),queue_position`, with or without
+/// the `channelvars =` of `ari.conf`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ChannelVars(pub Vec<String>);
+
+impl ChannelVars {
+    /// Lists variables.
+    pub fn new(vars: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        ChannelVars(vars.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<&str> for ChannelVars {
+    fn from(vars: &str) -> Self {
+        let vars = match vars.split_once('=') {
+            Some((option, vars)) if option.trim() == "channelvars" => vars,
+            _ => vars,
+        };
+        ChannelVars::new(vars.split(',').map(str::trim).filter(|var| !var.is_empty()))
+    }
+}
+
+impl<T: Into<String>, const N: usize> From<[T; N]> for ChannelVars {
+    fn from(vars: [T; N]) -> Self {
+        ChannelVars::new(vars)
+    }
+}
+
+impl<T: Into<String>> From<Vec<T>> for ChannelVars {
+    fn from(vars: Vec<T>) -> Self {
+        ChannelVars::new(vars)
+    }
+}
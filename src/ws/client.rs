@@ -1,20 +1,29 @@
 use crate::config::Config;
 use crate::errors::AriError;
+use crate::ws::journal::Journal;
+use crate::ws::models::ConnectionState;
 use crate::ws::{models, params};
 use futures_util::{SinkExt, StreamExt as _};
 use rand::random;
 use std::time::Duration;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::Stream;
-use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{
+    connect_async_tls_with_config, Connector, MaybeTlsStream, WebSocketStream,
+};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, trace, warn};
 use url::Url;
+
+type WsStream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
 #[derive(Debug)]
 pub struct Client {
     config: Config,
     stop_signal: CancellationToken,
+    states: tokio::sync::broadcast::Sender<ConnectionState>,
+    journal: Option<Journal>,
     _ws_join_handle: Option<tokio::task::JoinHandle<Result<(), AriError>>>,
 }
 
@@ -29,10 +38,22 @@ impl Client {
         Self {
             config,
             stop_signal: CancellationToken::new(),
+            states: tokio::sync::broadcast::channel(16).0,
+            journal: None,
             _ws_join_handle: None,
         }
     }
 
+    /// Records the frames received on the next connections into a journal.
+    pub fn set_journal(&mut self, journal: Option<Journal>) {
+        self.journal = journal;
+    }
+
+    /// Sender publishing the changes of the connection state.
+    pub(crate) fn states(&self) -> tokio::sync::broadcast::Sender<ConnectionState> {
+        self.states.clone()
+    }
+
     /// Disconnects the WebSocket client and waits for the join handler to finish.
     pub async fn disconnect(&mut self) -> Result<(), AriError> {
         self.stop_signal.cancel();
@@ -52,41 +73,28 @@ impl Client {
         &mut self,
         request: params::ListenRequest,
     ) -> Result<impl Stream<Item = models::Event>, AriError> {
-        let mut url = Url::parse(self.config.api_base.as_str())?;
-
-        url.set_scheme(if url.scheme().starts_with("https") {
-            "wss"
-        } else {
-            "ws"
-        })
-        .unwrap();
-
-        url.set_path("/ari/events");
-
-        url.query_pairs_mut()
-            .append_pair(
-                "api_key",
-                &format!("{}:{}", self.config.username, self.config.password),
-            )
-            .append_pair("app", request.app.as_str())
-            .append_pair(
-                "subscribeAll",
-                request.subscribe_all.unwrap_or(true).to_string().as_str(),
-            );
-
+        let url = events_url(&self.config, &request)?;
         debug!("connecting to ws_url: {}", url);
 
-        let ws_stream = match connect_async(url.to_string()).await {
-            Ok((ws_stream, _)) => ws_stream,
+        let connector = tls_connector(&self.config)?;
+        let connect_timeout = self.config.connect_timeout;
+        let ws_stream = match connect(&url, connector.clone(), connect_timeout).await {
+            Ok(ws_stream) => ws_stream,
             Err(e) => {
                 warn!("error when connecting to the websocket: {:#?}", e);
-                return Err(e.into());
+                return Err(e);
             }
         };
         debug!("websocket connected");
+        let states = self.states.clone();
+        let journal = self.journal.clone();
+        let _ = states.send(ConnectionState::Connected);
 
         let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-        let mut refresh_interval = tokio::time::interval(Duration::from_millis(5000));
+        let mut refresh_interval = tokio::time::interval(self.config.ping_interval);
+        let reconnect_delay = self.config.reconnect_delay;
+        let max_reconnect_delay = self.config.max_reconnect_delay;
+        let max_reconnect_attempts = self.config.max_reconnect_attempts;
         let cancel_token = self.stop_signal.child_token();
         let (tx, rx) = tokio::sync::mpsc::channel(100);
 
@@ -124,6 +132,9 @@ impl Client {
                                     }
                                     Ok(Message::Text(string_msg)) => {
                                         trace!("WS Ari Event: {:#?}", string_msg);
+                                        if let Some(journal) = &journal {
+                                            journal.record(&string_msg);
+                                        }
                                         match serde_json::from_str::<models::Event>(&string_msg) {
                                             Ok(event) => {
                                                 if tx.send(event).await.is_err() {
@@ -152,6 +163,7 @@ impl Client {
                     }
                 }
 
+                let _ = states.send(ConnectionState::Disconnected);
                 let mut i = 0;
                 loop {
                     i += 1;
@@ -159,12 +171,17 @@ impl Client {
                         debug!("Cancellation detected during reconnection attempts");
                         break 'outer;
                     }
+                    if max_reconnect_attempts.map_or(false, |max| i > max) {
+                        error!("Giving up reconnecting after {} attempts", i - 1);
+                        break 'outer;
+                    }
                     info!("Attempting to reconnect ({i})");
 
-                    match connect_async(url.to_string()).await {
-                        Ok((ws_stream, _)) => {
+                    match connect(&url, connector.clone(), connect_timeout).await {
+                        Ok(ws_stream) => {
                             info!("Reconnected successfully");
                             connected = true;
+                            let _ = states.send(ConnectionState::Reconnected);
                             let (new_ws_sender, new_ws_receiver) = ws_stream.split();
                             ws_sender = new_ws_sender;
                             ws_receiver = new_ws_receiver;
@@ -175,8 +192,8 @@ impl Client {
                         }
                     }
                     tokio::time::sleep(std::cmp::min(
-                        Duration::from_millis(500 * i),
-                        Duration::from_secs(90),
+                        reconnect_delay.saturating_mul(i),
+                        max_reconnect_delay,
                     ))
                     .await;
                 }
@@ -188,3 +205,82 @@ impl Client {
         Ok(ReceiverStream::new(rx))
     }
 }
+
+/// URL of the event WebSocket, below the path prefix of the configured base URL if any.
+fn events_url(config: &Config, request: &params::ListenRequest) -> Result<Url, AriError> {
+    let mut url = Url::parse(config.api_base.as_str())?;
+
+    url.set_scheme(if url.scheme().starts_with("https") {
+        "wss"
+    } else {
+        "ws"
+    })
+    .map_err(|_| AriError::InvalidConfig(format!("invalid URL {}", config.api_base)))?;
+
+    let path = format!("{}/ari/events", url.path().trim_end_matches('/'));
+    url.set_path(&path);
+
+    url.query_pairs_mut()
+        .append_pair(
+            "api_key",
+            &format!("{}:{}", config.username, config.password),
+        )
+        .append_pair("app", request.app.as_str())
+        .append_pair(
+            "subscribeAll",
+            request.subscribe_all.unwrap_or(true).to_string().as_str(),
+        );
+    Ok(url)
+}
+
+/// Connects the WebSocket, failing after the connect timeout.
+async fn connect(
+    url: &Url,
+    connector: Option<Connector>,
+    timeout: Duration,
+) -> Result<WsStream, AriError> {
+    let connecting = connect_async_tls_with_config(url.as_str(), None, false, connector);
+    match tokio::time::timeout(timeout, connecting).await {
+        Ok(connected) => Ok(connected?.0),
+        Err(_) => Err(AriError::Io(std::io::ErrorKind::TimedOut.into())),
+    }
+}
+
+/// Connector applying the TLS options of the configuration, `None` for the defaults.
+pub(crate) fn tls_connector(config: &Config) -> Result<Option<Connector>, AriError> {
+    if !config.accept_invalid_certs && config.root_certificate.is_none() {
+        return Ok(None);
+    }
+    let mut builder = native_tls::TlsConnector::builder();
+    builder.danger_accept_invalid_certs(config.accept_invalid_certs);
+    if let Some(pem) = &config.root_certificate {
+        let certificate = native_tls::Certificate::from_pem(pem)
+            .map_err(|e| AriError::InvalidConfig(e.to_string()))?;
+        builder.add_root_certificate(certificate);
+    }
+    let connector = builder
+        .build()
+        .map_err(|e| AriError::InvalidConfig(e.to_string()))?;
+    Ok(Some(Connector::NativeTls(connector)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_url_keeps_the_path_prefix() {
+        let request = params::ListenRequest::new("test");
+
+        let config = Config::new("https://pbx.example.com:8089", "user", "secret");
+        assert_eq!(
+            events_url(&config, &request).unwrap().as_str(),
+            "wss://pbx.example.com:8089/ari/events?api_key=user%3Asecret&app=test&subscribeAll=true"
+        );
+
+        let config = Config::from_url("http://user:secret@[::1]:8088/proxy/ari").unwrap();
+        let url = events_url(&config, &request).unwrap();
+        assert_eq!(url.scheme(), "ws");
+        assert_eq!(url.path(), "/proxy/ari/events");
+    }
+}
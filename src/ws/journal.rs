@@ -0,0 +1,252 @@
+use crate::errors::{AriError, Result};
+use crate::ws::models::Event;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use tokio_stream::Stream;
+use tracing::warn;
+
+/// JournalEntry : A raw WebSocket frame, as received from Asterisk.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the frame was received.
+    pub received_at: DateTime<Utc>,
+    /// Text of the frame.
+    pub frame: String,
+}
+
+/// Records the frames received from Asterisk into a JSON Lines file, one entry per line.
+///
+/// The entries are written by a dedicated thread, so that recording never blocks the event
+/// loop. Clones write to the same file.
+#[derive(Clone)]
+pub struct Journal {
+    entries: mpsc::Sender<JournalEntry>,
+}
+
+impl Journal {
+    /// Opens a journal file, appending to it if it already exists.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::with_writer(file))
+    }
+
+    /// Creates a journal writing to any destination.
+    pub fn with_writer(mut writer: impl Write + Send + 'static) -> Self {
+        let (entries, received) = mpsc::channel::<JournalEntry>();
+        std::thread::spawn(move || {
+            for entry in received {
+                let written = serde_json::to_writer(&mut writer, &entry)
+                    .map_err(std::io::Error::from)
+                    .and_then(|_| writer.write_all(b"\n"))
+                    .and_then(|_| writer.flush());
+                if let Err(e) = written {
+                    warn!("unable to write to the event journal: {}", e);
+                }
+            }
+        });
+
+        Self { entries }
+    }
+
+    /// Appends a frame to the journal.
+    pub(crate) fn record(&self, frame: &str) {
+        let entry = JournalEntry {
+            received_at: Utc::now(),
+            frame: frame.to_string(),
+        };
+
+        if self.entries.send(entry).is_err() {
+            warn!("unable to write to the event journal: the writer stopped");
+        }
+    }
+}
+
+impl std::fmt::Debug for Journal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Journal").finish_non_exhaustive()
+    }
+}
+
+/// How fast a journal is replayed.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ReplaySpeed {
+    /// Keep the delays between the frames as they were received.
+    #[default]
+    RealTime,
+    /// Divide the delays between the frames by a factor, built by [`ReplaySpeed::accelerated`].
+    Accelerated(Acceleration),
+    /// Replay the frames without any delay.
+    Instant,
+}
+
+impl ReplaySpeed {
+    /// Divides the delays between the frames by `factor`, which must be finite and positive.
+    pub fn accelerated(factor: f64) -> Result<Self> {
+        if !factor.is_finite() || factor <= 0.0 {
+            return Err(AriError::InvalidConfig(format!(
+                "replay speed factor must be finite and positive, got {factor}"
+            )));
+        }
+        Ok(ReplaySpeed::Accelerated(Acceleration(factor)))
+    }
+}
+
+/// The factor of a [`ReplaySpeed::Accelerated`], always finite and positive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Acceleration(f64);
+
+impl Acceleration {
+    /// The factor the delays are divided by.
+    pub fn factor(&self) -> f64 {
+        self.0
+    }
+}
+
+/// A journal to feed back to an `AriClient`, as if the frames came from Asterisk.
+#[derive(Clone, Debug)]
+pub struct ReplaySource {
+    entries: Vec<JournalEntry>,
+    speed: ReplaySpeed,
+}
+
+impl ReplaySource {
+    /// Reads a journal file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads a journal from any source. Empty lines are skipped.
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut entries = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                entries.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(Self {
+            entries,
+            speed: ReplaySpeed::default(),
+        })
+    }
+
+    /// Sets how fast the journal is replayed.
+    pub fn with_speed(mut self, speed: ReplaySpeed) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// The entries of the journal.
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Streams the events of the journal, waiting between them according to the speed.
+    ///
+    /// Frames that are not valid events are skipped, as they are when received from Asterisk.
+    pub(crate) fn into_stream(self) -> impl Stream<Item = Event> + Send + 'static {
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+
+        tokio::spawn(async move {
+            let mut previous: Option<DateTime<Utc>> = None;
+            for entry in self.entries {
+                if let Some(previous) = previous {
+                    let delay = (entry.received_at - previous).to_std().unwrap_or_default();
+                    let delay = match self.speed {
+                        ReplaySpeed::RealTime => Some(delay),
+                        ReplaySpeed::Accelerated(acceleration) => {
+                            Some(delay.div_f64(acceleration.factor()))
+                        }
+                        ReplaySpeed::Instant => None,
+                    };
+                    if let Some(delay) = delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                }
+                previous = Some(entry.received_at);
+
+                match serde_json::from_str::<Event>(&entry.frame) {
+                    Ok(event) => {
+                        if tx.send(event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(e) => warn!(
+                        "error when deserializing journal frame: {:#?}. Frame: {:#?}",
+                        e, entry.frame
+                    ),
+                }
+            }
+        });
+
+        tokio_stream::wrappers::ReceiverStream::new(rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio_stream::StreamExt;
+
+    /// A writer whose output can be read after it was moved into a journal.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    const FRAME: &str = r#"{"type":"ChannelDtmfReceived","timestamp":"2025-01-07T22:12:29.571+0100","application":"test","digit":"1","duration_ms":100,"channel":{"id":"1","name":"PJSIP/1000-00000001","state":"Up","caller":{"name":"","number":""},"connected":{"name":"","number":""},"accountcode":"","dialplan":{"context":"default","exten":"100","priority":1,"app_name":"","app_data":""},"creationtime":"2025-01-07T22:12:29.369+0100","language":"en"}}"#;
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let output = Shared::default();
+        let journal = Journal::with_writer(output.clone());
+        journal.record(FRAME);
+        journal.record("not an event");
+        journal.record(FRAME);
+
+        let written = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let written = output.0.lock().unwrap().clone();
+                if written.iter().filter(|byte| **byte == b'\n').count() == 3 {
+                    break written;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let source = ReplaySource::from_reader(written.as_slice())
+            .unwrap()
+            .with_speed(ReplaySpeed::Instant);
+        assert_eq!(source.entries().len(), 3);
+        assert_eq!(source.entries()[0].frame, FRAME);
+
+        let events: Vec<_> = source.into_stream().collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], Event::ChannelDtmfReceived(_)));
+    }
+
+    #[test]
+    fn test_accelerated_speed_is_validated() {
+        let speed = ReplaySpeed::accelerated(4.0).unwrap();
+        assert!(matches!(speed, ReplaySpeed::Accelerated(a) if a.factor() == 4.0));
+        for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(ReplaySpeed::accelerated(factor).is_err());
+        }
+    }
+}
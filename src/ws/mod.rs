@@ -1,6 +1,9 @@
 /// WebSocket client module.
 pub mod client;
 
+/// Journal of the raw WebSocket frames, and their replay.
+pub mod journal;
+
 /// Models for WebSocket events and data structures.
 pub mod models;
 
@@ -1,15 +1,16 @@
 use crate::apis::bridges::models::Bridge;
-use crate::apis::channels::models::Channel;
+use crate::apis::channels::models::{Channel, DialStatus, HangupCause, RTPStatistics};
 use crate::apis::device_stats::models::DeviceState;
+use crate::apis::dtmf::DtmfDigit;
 use crate::apis::endpoints::models::{Endpoint, TextMessage};
 use crate::apis::playbacks::models::Playback;
 use crate::apis::recordings::models::LiveRecording;
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// BaseEvent
-#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BaseEvent<T> {
     /// The unique ID for the Asterisk instance that raised this event.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,6 +20,7 @@ pub struct BaseEvent<T> {
     pub application: String,
 
     /// Time at which this event was created. E.g. 2020-11-22T20:12:51.214+0000
+    #[serde(serialize_with = "crate::apis::serialize_timestamp")]
     pub timestamp: DateTime<Utc>,
 
     /// The event data.
@@ -28,7 +30,7 @@ pub struct BaseEvent<T> {
 
 /// ApplicationMoveFailed
 /// Notification that trying to move a channel to another Stasis application failed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ApplicationMoveFailed {
     #[serde(rename = "channel")]
     pub channel: Channel,
@@ -40,12 +42,22 @@ pub struct ApplicationMoveFailed {
 
 /// ApplicationMoveFailed
 /// Notification that another WebSocket has taken over for an application.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ApplicationReplaced {}
 
+/// ApplicationRegistered
+/// Notification that a Stasis application has been registered.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationRegistered {}
+
+/// ApplicationUnregistered
+/// Notification that a Stasis application has been unregistered.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ApplicationUnregistered {}
+
 /// BridgeAttendedTransfer
 /// Notification that an attended transfer has occurred.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeAttendedTransfer {
     /// First leg of the transferer
     #[serde(rename = "transferer_first_leg")]
@@ -55,15 +67,15 @@ pub struct BridgeAttendedTransfer {
     pub transferer_second_leg: Channel,
 
     /// The channel that is replacing transferer_first_leg in the swap
-    #[serde(rename = "replace_channel")]
+    #[serde(rename = "replace_channel", skip_serializing_if = "Option::is_none")]
     pub replace_channel: Option<Channel>,
 
     /// The channel that is being transferred
-    #[serde(rename = "transferee")]
+    #[serde(rename = "transferee", skip_serializing_if = "Option::is_none")]
     pub transferee: Option<Channel>,
 
     /// The channel that is being transferred to
-    #[serde(rename = "transfer_target")]
+    #[serde(rename = "transfer_target", skip_serializing_if = "Option::is_none")]
     pub transfer_target: Option<Channel>,
 
     /// The result of the transfer attempt
@@ -75,11 +87,17 @@ pub struct BridgeAttendedTransfer {
     pub is_external: bool,
 
     /// Bridge the transferer first leg is in
-    #[serde(rename = "transferer_first_leg_bridge")]
+    #[serde(
+        rename = "transferer_first_leg_bridge",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub transferer_first_leg_bridge: Option<Bridge>,
 
     /// Bridge the transferer second leg is in
-    #[serde(rename = "transferer_second_leg_bridge")]
+    #[serde(
+        rename = "transferer_second_leg_bridge",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub transferer_second_leg_bridge: Option<Bridge>,
 
     /// How the transfer was accomplished
@@ -87,44 +105,59 @@ pub struct BridgeAttendedTransfer {
     pub destination_type: String,
 
     /// Bridge that survived the merge result
-    #[serde(rename = "destination_bridge")]
+    #[serde(rename = "destination_bridge", skip_serializing_if = "Option::is_none")]
     pub destination_bridge: Option<String>,
 
     /// Application that has been transferred into
-    #[serde(rename = "destination_application")]
+    #[serde(
+        rename = "destination_application",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub destination_application: Option<String>,
 
     /// First leg of a link transfer result
-    #[serde(rename = "destination_link_first_leg")]
+    #[serde(
+        rename = "destination_link_first_leg",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub destination_link_first_leg: Option<Channel>,
 
     /// Second leg of a link transfer result
-    #[serde(rename = "destination_link_second_leg")]
+    #[serde(
+        rename = "destination_link_second_leg",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub destination_link_second_leg: Option<Channel>,
 
     /// Transferer channel that survived the threeway result
-    #[serde(rename = "destination_threeway_channel")]
+    #[serde(
+        rename = "destination_threeway_channel",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub destination_threeway_channel: Option<Channel>,
 
     /// Bridge that survived the threeway result
-    #[serde(rename = "destination_threeway_bridge")]
+    #[serde(
+        rename = "destination_threeway_bridge",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub destination_threeway_bridge: Option<Bridge>,
 }
 
 /// BridgeBlindTransfer :
 /// Notification that a blind transfer has occurred.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeBlindTransfer {
     /// The channel performing the blind transfer
     #[serde(rename = "channel")]
     pub channel: Channel,
 
     /// The channel that is replacing transferer when the transferee(s) can not be transferred directly
-    #[serde(rename = "replace_channel")]
+    #[serde(rename = "replace_channel", skip_serializing_if = "Option::is_none")]
     pub replace_channel: Option<Channel>,
 
     /// The channel that is being transferred
-    #[serde(rename = "transferee")]
+    #[serde(rename = "transferee", skip_serializing_if = "Option::is_none")]
     pub transferee: Option<Channel>,
 
     /// The extension transferred to
@@ -141,25 +174,25 @@ pub struct BridgeBlindTransfer {
     pub is_external: bool,
 
     /// The bridge being transferred
-    #[serde(rename = "bridge")]
+    #[serde(rename = "bridge", skip_serializing_if = "Option::is_none")]
     pub bridge: Option<Bridge>,
 }
 
 /// Notification that a bridge has been created.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeCreated {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
 }
 
 /// Notification that a bridge has been destroyed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeDestroyed {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
 }
 
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeMerged {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
@@ -168,16 +201,19 @@ pub struct BridgeMerged {
 }
 
 /// Notification that the source of video in a bridge has changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct BridgeVideoSourceChanged {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
-    #[serde(rename = "old_video_source_id")]
+    #[serde(
+        rename = "old_video_source_id",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub old_video_source_id: Option<String>,
 }
 
 /// Channel changed Caller ID.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelCallerId {
     /// The integer representation of the Caller Presentation value.
     #[serde(rename = "caller_presentation")]
@@ -193,7 +229,7 @@ pub struct ChannelCallerId {
 }
 
 /// Channel changed Connected Line.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelConnectedLine {
     /// The channel whose connected line has changed.
     #[serde(rename = "channel")]
@@ -201,26 +237,28 @@ pub struct ChannelConnectedLine {
 }
 
 /// ChannelCreated: Notification that a channel has been created.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelCreated {
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// Notification that a channel has been destroyed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelDestroyed {
     #[serde(rename = "cause")]
-    pub cause: u32,
+    pub cause: HangupCause,
     /// Text representation of the cause of the hangup
     #[serde(rename = "cause_txt")]
-    /// Text representation of the cause of the hangup
     pub cause_txt: String,
+    /// Technology specific cause of the hangup, e.g. the SIP response code.
+    #[serde(rename = "tech_cause", skip_serializing_if = "Option::is_none")]
+    pub tech_cause: Option<i32>,
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 /// Channel changed location in the dialplan.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelDialplan {
     /// The channel that changed dialplan location.
     #[serde(rename = "channel")]
@@ -236,11 +274,11 @@ pub struct ChannelDialplan {
 /// ChannelDtmfReceived :
 /// DTMF received on a channel.  
 /// This event is sent when the DTMF ends. There is no notification about the start of DTMF
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelDtmfReceived {
     /// DTMF digit received (0-9, A-E, # or *)
     #[serde(rename = "digit")]
-    pub digit: String,
+    pub digit: DtmfDigit,
     /// Number of milliseconds DTMF was received
     #[serde(rename = "duration_ms")]
     pub duration_ms: u32,
@@ -250,40 +288,43 @@ pub struct ChannelDtmfReceived {
 }
 
 /// Notification that a channel has entered a bridge.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelEnteredBridge {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
-    #[serde(rename = "channel")]
+    #[serde(rename = "channel", skip_serializing_if = "Option::is_none")]
     pub channel: Option<Channel>,
 }
 
 /// A hangup was requested on the channel.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelHangupRequest {
-    /// Integer representation of the cause of the hangup.
+    /// Cause of the hangup.
     #[serde(rename = "cause")]
-    pub cause: u32,
+    pub cause: HangupCause,
     /// Whether the hangup request was a soft hangup request.
-    #[serde(rename = "soft")]
+    #[serde(rename = "soft", skip_serializing_if = "Option::is_none")]
     pub soft: Option<bool>,
+    /// Technology specific cause of the hangup, e.g. the SIP response code.
+    #[serde(rename = "tech_cause", skip_serializing_if = "Option::is_none")]
+    pub tech_cause: Option<i32>,
     /// The channel on which the hangup was requested.
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// A channel initiated a media hold.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelHold {
     #[serde(rename = "channel")]
     pub channel: Channel,
     /// The music on hold class that the initiator requested.
-    #[serde(rename = "musicclass")]
+    #[serde(rename = "musicclass", skip_serializing_if = "Option::is_none")]
     pub musicclass: Option<String>,
 }
 
 /// Notification that a channel has left a bridge.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelLeftBridge {
     #[serde(rename = "bridge")]
     pub bridge: Bridge,
@@ -291,15 +332,25 @@ pub struct ChannelLeftBridge {
     pub channel: Channel,
 }
 
+/// RTP statistics of a channel.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelRtpStatistics {
+    #[serde(rename = "channel")]
+    pub channel: Channel,
+    /// The statistics of the RTP session of the channel.
+    #[serde(rename = "rtp_statistics", skip_serializing_if = "Option::is_none")]
+    pub rtp_statistics: Option<RTPStatistics>,
+}
+
 /// Notification of a channel's state change.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelStateChange {
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// Talking is no longer detected on the channel.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelTalkingFinished {
     #[serde(rename = "channel")]
     pub channel: Channel,
@@ -308,49 +359,125 @@ pub struct ChannelTalkingFinished {
 }
 
 /// Talking was detected on the channel.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelTalkingStarted {
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// Tone was detected on the channel.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelToneDetected {
     #[serde(rename = "channel")]
     pub channel: Channel,
+    /// The tone detected (e.g. `fax`), when Asterisk reports it.
+    #[serde(rename = "tone", skip_serializing_if = "Option::is_none")]
+    pub tone: Option<String>,
+}
+
+/// A parameter of the protocol of a transfer, e.g. a SIP header.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AdditionalParam {
+    /// Name of the parameter.
+    #[serde(rename = "parameter_name")]
+    pub parameter_name: String,
+    /// Value of the parameter.
+    #[serde(rename = "parameter_value")]
+    pub parameter_value: String,
+}
+
+/// The destination of a transfer, as requested.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequiredDestination {
+    /// The call id of the channel to replace, for an attended transfer.
+    #[serde(rename = "protocol_id", skip_serializing_if = "Option::is_none")]
+    pub protocol_id: Option<String>,
+    /// Destination of a blind transfer.
+    #[serde(rename = "destination", skip_serializing_if = "Option::is_none")]
+    pub destination: Option<String>,
+    /// Parameters of the transfer request, e.g. the headers of a SIP REFER.
+    #[serde(rename = "additional_protocol_params", default)]
+    pub additional_protocol_params: Vec<AdditionalParam>,
+}
+
+/// Where a transfer sends the channel.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReferTo {
+    /// The destination requested.
+    #[serde(rename = "requested_destination")]
+    pub requested_destination: RequiredDestination,
+    /// The channel replaced by the transfer, for an attended transfer.
+    #[serde(
+        rename = "destination_channel",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub destination_channel: Option<Channel>,
+    /// The channel connected to the destination channel.
+    #[serde(rename = "connected_channel", skip_serializing_if = "Option::is_none")]
+    pub connected_channel: Option<Channel>,
+    /// The bridge of the destination channel.
+    #[serde(rename = "bridge", skip_serializing_if = "Option::is_none")]
+    pub bridge: Option<Bridge>,
+}
+
+/// Who requested a transfer.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReferredBy {
+    /// The channel that requested the transfer.
+    #[serde(rename = "source_channel")]
+    pub source_channel: Channel,
+    /// The channel connected to the source channel.
+    #[serde(rename = "connected_channel", skip_serializing_if = "Option::is_none")]
+    pub connected_channel: Option<Channel>,
+    /// The bridge of the source channel.
+    #[serde(rename = "bridge", skip_serializing_if = "Option::is_none")]
+    pub bridge: Option<Bridge>,
+}
+
+/// A transfer was requested on a channel whose transfers are handled by the application.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChannelTransfer {
+    /// State of the transfer (e.g. `channel_answered`).
+    #[serde(rename = "state", skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Where the transfer sends the channel.
+    #[serde(rename = "refer_to")]
+    pub refer_to: ReferTo,
+    /// Who requested the transfer.
+    #[serde(rename = "referred_by")]
+    pub referred_by: ReferredBy,
 }
 
 /// A channel initiated a media unhold.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelUnhold {
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// User-generated event with additional user-defined fields in the object.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelUserEvent {
     /// The name of the user event.
     #[serde(rename = "eventname")]
     pub event_name: String,
 
     ///  A channel that is signaled with the user event.
-    #[serde(rename = "channel")]
+    #[serde(rename = "channel", skip_serializing_if = "Option::is_none")]
     pub channel: Option<Channel>,
     /// A bridge that is signaled with the user event.
-    #[serde(rename = "bridge")]
+    #[serde(rename = "bridge", skip_serializing_if = "Option::is_none")]
     pub bridge: Option<Bridge>,
     /// A endpoint that is signaled with the user event.
-    #[serde(rename = "endpoint")]
+    #[serde(rename = "endpoint", skip_serializing_if = "Option::is_none")]
     pub endpoint: Option<Endpoint>,
     /// Custom Userevent data
-    #[serde(rename = "userevent")]
+    #[serde(rename = "userevent", skip_serializing_if = "Option::is_none")]
     pub user_event: Option<serde_json::Value>,
 }
 
 /// Channel variable changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ChannelVarSet {
     /// The variable that changed.
     #[serde(rename = "variable")]
@@ -360,12 +487,12 @@ pub struct ChannelVarSet {
     pub value: String,
     /// The channel on which the variable was set.
     /// If missing, the variable is a global variable.
-    #[serde(rename = "channel")]
+    #[serde(rename = "channel", skip_serializing_if = "Option::is_none")]
     pub channel: Option<Channel>,
 }
 
 /// Detailed information about a contact on an endpoint.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ContactInfo {
     /// The location of the contact.
     #[serde(rename = "uri")]
@@ -378,12 +505,12 @@ pub struct ContactInfo {
     pub aor: String,
     /// Current round trip time, in microseconds, for the contact.
     // todo: change this to duration?
-    #[serde(rename = "roundtrip_usec")]
+    #[serde(rename = "roundtrip_usec", skip_serializing_if = "Option::is_none")]
     pub roundtrip_usec: Option<String>,
 }
 
 /// The state of a contact on an endpoint has changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ContactStatusChange {
     #[serde(rename = "endpoint")]
     pub endpoint: Endpoint,
@@ -392,7 +519,7 @@ pub struct ContactStatusChange {
 }
 
 /// Notification that a device state has changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct DeviceStateChanged {
     /// Device state object
     #[serde(rename = "device_state")]
@@ -400,37 +527,37 @@ pub struct DeviceStateChanged {
 }
 
 /// Dialing state has changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Dial {
     /// The calling channel.
-    #[serde(rename = "caller")]
+    #[serde(rename = "caller", skip_serializing_if = "Option::is_none")]
     pub caller: Option<Channel>,
 
     /// The dialed channel.
-    #[serde(rename = "peer")]
+    #[serde(rename = "peer", skip_serializing_if = "Option::is_none")]
     pub peer: Option<Channel>,
     /// Forwarding target requested by the original dialed channel.
-    #[serde(rename = "forward")]
+    #[serde(rename = "forward", skip_serializing_if = "Option::is_none")]
     pub forward: Option<String>,
-    #[serde(rename = "forwarded")]
+    #[serde(rename = "forwarded", skip_serializing_if = "Option::is_none")]
     pub forwarded: Option<Channel>,
     /// The dial string for calling the peer channel.
-    #[serde(rename = "dialstring")]
+    #[serde(rename = "dialstring", skip_serializing_if = "Option::is_none")]
     pub dialstring: Option<String>,
     /// Current status of the dialing attempt to the peer.
     #[serde(rename = "dialstatus")]
-    pub dialstatus: String,
+    pub dialstatus: DialStatus,
 }
 
 /// Endpoint state changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct EndpointStateChange {
     #[serde(rename = "endpoint")]
     pub endpoint: Endpoint,
 }
 
 /// Error event sent when required params are missing.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MissingParams {
     /// A list of the missing parameters
     #[serde(rename = "params")]
@@ -438,27 +565,27 @@ pub struct MissingParams {
 }
 
 /// Detailed information about a remote peer that communicates with Asterisk.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Peer {
     /// The current state of the peer. Note that the values of the status are dependent on the underlying peer technology.
     #[serde(rename = "peer_status")]
     pub peer_status: String,
     /// An optional reason associated with the change in peer_status.
-    #[serde(rename = "cause")]
+    #[serde(rename = "cause", skip_serializing_if = "Option::is_none")]
     pub cause: Option<String>,
     /// The IP address of the peer.
-    #[serde(rename = "address")]
+    #[serde(rename = "address", skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
     /// The port of the peer.
-    #[serde(rename = "port")]
+    #[serde(rename = "port", skip_serializing_if = "Option::is_none")]
     pub port: Option<String>,
     /// The last known time the peer was contacted.
-    #[serde(rename = "time")]
+    #[serde(rename = "time", skip_serializing_if = "Option::is_none")]
     pub time: Option<String>,
 }
 
 /// The state of a peer associated with an endpoint has changed.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PeerStatusChange {
     #[serde(rename = "endpoint")]
     pub endpoint: Endpoint,
@@ -467,7 +594,7 @@ pub struct PeerStatusChange {
 }
 
 /// Event showing the continuation of a media playback operation from one media URI to the next in the list.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlaybackContinuing {
     /// Playback control object
     #[serde(rename = "playback")]
@@ -475,7 +602,7 @@ pub struct PlaybackContinuing {
 }
 
 /// Event showing the completion of a media playback operation.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlaybackFinished {
     /// Playback control object
     #[serde(rename = "playback")]
@@ -483,14 +610,14 @@ pub struct PlaybackFinished {
 }
 
 /// Event showing the start of a media playback operation.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlaybackStarted {
     #[serde(rename = "playback")]
     pub playback: Playback,
 }
 
 /// Event showing failure of a recording operation.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RecordingFailed {
     /// Recording control object
     #[serde(rename = "recording")]
@@ -498,52 +625,81 @@ pub struct RecordingFailed {
 }
 
 /// Event showing the completion of a recording operation.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RecordingFinished {
     #[serde(rename = "recording")]
     pub recording: LiveRecording,
 }
 
 /// Event showing the start of a recording operation.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RecordingStarted {
     #[serde(rename = "recording")]
     pub recording: LiveRecording,
 }
 
+/// The response to a REST request sent over the WebSocket.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RestResponse {
+    /// Id of the WebSocket transaction.
+    #[serde(rename = "transaction_id")]
+    pub transaction_id: String,
+    /// Id given to the request by the application.
+    #[serde(rename = "request_id")]
+    pub request_id: String,
+    /// HTTP status code of the response.
+    #[serde(rename = "status_code")]
+    pub status_code: u16,
+    /// HTTP reason phrase of the response.
+    #[serde(rename = "reason_phrase")]
+    pub reason_phrase: String,
+    /// URI of the request.
+    #[serde(rename = "uri")]
+    pub uri: String,
+    /// Content type of the body.
+    #[serde(rename = "content_type", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Body of the response.
+    #[serde(rename = "message_body", skip_serializing_if = "Option::is_none")]
+    pub message_body: Option<String>,
+}
+
 /// Notification that a channel has left a Stasis application.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StasisEnd {
     #[serde(rename = "channel")]
     pub channel: Channel,
 }
 
 /// Notification that a channel has entered a Stasis application.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StasisStart {
     #[serde(rename = "args")]
     pub args: Vec<String>,
     #[serde(rename = "channel")]
     pub channel: Channel,
-    #[serde(rename = "replace_channel")]
+    #[serde(rename = "replace_channel", skip_serializing_if = "Option::is_none")]
     pub replace_channel: Option<Channel>,
 }
 
 /// A text message was received from an endpoint.
-#[derive(Clone, Default, Debug, PartialEq, Deserialize)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct TextMessageReceived {
     #[serde(rename = "message")]
     pub message: TextMessage,
 
-    #[serde(rename = "endpoint")]
+    #[serde(rename = "endpoint", skip_serializing_if = "Option::is_none")]
     pub endpoint: Option<Endpoint>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type")]
+#[allow(clippy::large_enum_variant)]
 pub enum Event {
     ApplicationMoveFailed(BaseEvent<ApplicationMoveFailed>),
     ApplicationReplaced(BaseEvent<ApplicationReplaced>),
+    ApplicationRegistered(BaseEvent<ApplicationRegistered>),
+    ApplicationUnregistered(BaseEvent<ApplicationUnregistered>),
     BridgeAttendedTransfer(BaseEvent<BridgeAttendedTransfer>),
     BridgeBlindTransfer(BaseEvent<BridgeBlindTransfer>),
     BridgeCreated(BaseEvent<BridgeCreated>),
@@ -560,10 +716,12 @@ pub enum Event {
     ChannelHangupRequest(BaseEvent<ChannelHangupRequest>),
     ChannelHold(BaseEvent<ChannelHold>),
     ChannelLeftBridge(BaseEvent<ChannelLeftBridge>),
+    ChannelRtpStatistics(BaseEvent<ChannelRtpStatistics>),
     ChannelStateChange(BaseEvent<ChannelStateChange>),
     ChannelTalkingFinished(BaseEvent<ChannelTalkingFinished>),
     ChannelTalkingStarted(BaseEvent<ChannelTalkingStarted>),
     ChannelToneDetected(BaseEvent<ChannelToneDetected>),
+    ChannelTransfer(BaseEvent<ChannelTransfer>),
     ChannelUnhold(BaseEvent<ChannelUnhold>),
     #[serde(rename = "ChannelUserevent")]
     ChannelUserEvent(BaseEvent<ChannelUserEvent>),
@@ -583,6 +741,8 @@ pub enum Event {
     RecordingFailed(BaseEvent<RecordingFailed>),
     RecordingFinished(BaseEvent<RecordingFinished>),
     RecordingStarted(BaseEvent<RecordingStarted>),
+    #[serde(rename = "RESTResponse")]
+    RestResponse(BaseEvent<RestResponse>),
     StasisEnd(BaseEvent<StasisEnd>),
     StasisStart(BaseEvent<StasisStart>),
     TextMessageReceived(BaseEvent<TextMessageReceived>),
@@ -591,11 +751,69 @@ pub enum Event {
     Unknown(serde_json::Value),
 }
 
+impl Event {
+    /// When Asterisk raised the event, or `None` for unknown events.
+    pub fn timestamp(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Event::ApplicationMoveFailed(e) => Some(e.timestamp),
+            Event::ApplicationReplaced(e) => Some(e.timestamp),
+            Event::ApplicationRegistered(e) => Some(e.timestamp),
+            Event::ApplicationUnregistered(e) => Some(e.timestamp),
+            Event::BridgeAttendedTransfer(e) => Some(e.timestamp),
+            Event::BridgeBlindTransfer(e) => Some(e.timestamp),
+            Event::BridgeCreated(e) => Some(e.timestamp),
+            Event::BridgeDestroyed(e) => Some(e.timestamp),
+            Event::BridgeMerged(e) => Some(e.timestamp),
+            Event::BridgeVideoSourceChanged(e) => Some(e.timestamp),
+            Event::ChannelCallerId(e) => Some(e.timestamp),
+            Event::ChannelConnectedLine(e) => Some(e.timestamp),
+            Event::ChannelCreated(e) => Some(e.timestamp),
+            Event::ChannelDestroyed(e) => Some(e.timestamp),
+            Event::ChannelDialplan(e) => Some(e.timestamp),
+            Event::ChannelDtmfReceived(e) => Some(e.timestamp),
+            Event::ChannelEnteredBridge(e) => Some(e.timestamp),
+            Event::ChannelHangupRequest(e) => Some(e.timestamp),
+            Event::ChannelHold(e) => Some(e.timestamp),
+            Event::ChannelLeftBridge(e) => Some(e.timestamp),
+            Event::ChannelRtpStatistics(e) => Some(e.timestamp),
+            Event::ChannelStateChange(e) => Some(e.timestamp),
+            Event::ChannelTalkingFinished(e) => Some(e.timestamp),
+            Event::ChannelTalkingStarted(e) => Some(e.timestamp),
+            Event::ChannelToneDetected(e) => Some(e.timestamp),
+            Event::ChannelTransfer(e) => Some(e.timestamp),
+            Event::ChannelUnhold(e) => Some(e.timestamp),
+            Event::ChannelUserEvent(e) => Some(e.timestamp),
+            Event::ChannelVarSet(e) => Some(e.timestamp),
+            Event::ContactInfo(e) => Some(e.timestamp),
+            Event::ContactStatusChange(e) => Some(e.timestamp),
+            Event::DeviceStateChanged(e) => Some(e.timestamp),
+            Event::Dial(e) => Some(e.timestamp),
+            Event::EndpointStateChange(e) => Some(e.timestamp),
+            Event::MissingParams(e) => Some(e.timestamp),
+            Event::Peer(e) => Some(e.timestamp),
+            Event::PeerStatusChange(e) => Some(e.timestamp),
+            Event::PlaybackContinuing(e) => Some(e.timestamp),
+            Event::PlaybackFinished(e) => Some(e.timestamp),
+            Event::PlaybackStarted(e) => Some(e.timestamp),
+            Event::RecordingFailed(e) => Some(e.timestamp),
+            Event::RecordingFinished(e) => Some(e.timestamp),
+            Event::RecordingStarted(e) => Some(e.timestamp),
+            Event::RestResponse(e) => Some(e.timestamp),
+            Event::StasisEnd(e) => Some(e.timestamp),
+            Event::StasisStart(e) => Some(e.timestamp),
+            Event::TextMessageReceived(e) => Some(e.timestamp),
+            Event::Unknown(_) => None,
+        }
+    }
+}
+
 impl fmt::Display for Event {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Event::ApplicationMoveFailed(_) => write!(f, "ApplicationMoveFailed"),
             Event::ApplicationReplaced(_) => write!(f, "ApplicationReplaced"),
+            Event::ApplicationRegistered(_) => write!(f, "ApplicationRegistered"),
+            Event::ApplicationUnregistered(_) => write!(f, "ApplicationUnregistered"),
             Event::BridgeAttendedTransfer(_) => write!(f, "BridgeAttendedTransfer"),
             Event::BridgeBlindTransfer(_) => write!(f, "BridgeBlindTransfer"),
             Event::BridgeCreated(_) => write!(f, "BridgeCreated"),
@@ -612,10 +830,12 @@ impl fmt::Display for Event {
             Event::ChannelHangupRequest(_) => write!(f, "ChannelHangupRequest"),
             Event::ChannelHold(_) => write!(f, "ChannelHold"),
             Event::ChannelLeftBridge(_) => write!(f, "ChannelLeftBridge"),
+            Event::ChannelRtpStatistics(_) => write!(f, "ChannelRtpStatistics"),
             Event::ChannelStateChange(_) => write!(f, "ChannelStateChange"),
             Event::ChannelTalkingFinished(_) => write!(f, "ChannelTalkingFinished"),
             Event::ChannelTalkingStarted(_) => write!(f, "ChannelTalkingStarted"),
             Event::ChannelToneDetected(_) => write!(f, "ChannelToneDetected"),
+            Event::ChannelTransfer(_) => write!(f, "ChannelTransfer"),
             Event::ChannelUnhold(_) => write!(f, "ChannelUnhold"),
             Event::ChannelUserEvent(_) => write!(f, "ChannelUserEvent"),
             Event::ChannelVarSet(_) => write!(f, "ChannelVarSet"),
@@ -633,6 +853,7 @@ impl fmt::Display for Event {
             Event::RecordingFailed(_) => write!(f, "RecordingFailed"),
             Event::RecordingFinished(_) => write!(f, "RecordingFinished"),
             Event::RecordingStarted(_) => write!(f, "RecordingStarted"),
+            Event::RestResponse(_) => write!(f, "RestResponse"),
             Event::StasisEnd(_) => write!(f, "StasisEnd"),
             Event::StasisStart(_) => write!(f, "StasisStart"),
             Event::TextMessageReceived(_) => write!(f, "TextMessageReceived"),
@@ -641,6 +862,122 @@ impl fmt::Display for Event {
     }
 }
 
+/// Declares the kinds of events, with the `type` Asterisk gives them.
+macro_rules! event_kinds {
+    ($($name:ident => $type:literal,)*) => {
+        /// EventKind : The type of an [`Event`], without its data, e.g. to filter the events.
+        ///
+        /// Serialized as the `type` Asterisk gives the event.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+        pub enum EventKind {
+            $(
+                #[serde(rename = $type)]
+                $name,
+            )*
+        }
+
+        impl EventKind {
+            /// Every kind of event.
+            pub const ALL: &'static [EventKind] = &[$(EventKind::$name,)*];
+
+            /// The `type` Asterisk gives the event, e.g. `ChannelVarset` for `ChannelVarSet`.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(EventKind::$name => $type,)*
+                }
+            }
+
+            /// The kind of an event, by its `type` or by the name of its [`Event`] variant.
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($type | stringify!($name) => Some(EventKind::$name),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl Event {
+            /// The kind of the event, or `None` for unknown events.
+            pub fn kind(&self) -> Option<EventKind> {
+                match self {
+                    $(Event::$name(_) => Some(EventKind::$name),)*
+                    Event::Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+event_kinds! {
+    ApplicationMoveFailed => "ApplicationMoveFailed",
+    ApplicationReplaced => "ApplicationReplaced",
+    ApplicationRegistered => "ApplicationRegistered",
+    ApplicationUnregistered => "ApplicationUnregistered",
+    BridgeAttendedTransfer => "BridgeAttendedTransfer",
+    BridgeBlindTransfer => "BridgeBlindTransfer",
+    BridgeCreated => "BridgeCreated",
+    BridgeDestroyed => "BridgeDestroyed",
+    BridgeMerged => "BridgeMerged",
+    BridgeVideoSourceChanged => "BridgeVideoSourceChanged",
+    ChannelCallerId => "ChannelCallerId",
+    ChannelConnectedLine => "ChannelConnectedLine",
+    ChannelCreated => "ChannelCreated",
+    ChannelDestroyed => "ChannelDestroyed",
+    ChannelDialplan => "ChannelDialplan",
+    ChannelDtmfReceived => "ChannelDtmfReceived",
+    ChannelEnteredBridge => "ChannelEnteredBridge",
+    ChannelHangupRequest => "ChannelHangupRequest",
+    ChannelHold => "ChannelHold",
+    ChannelLeftBridge => "ChannelLeftBridge",
+    ChannelRtpStatistics => "ChannelRtpStatistics",
+    ChannelStateChange => "ChannelStateChange",
+    ChannelTalkingFinished => "ChannelTalkingFinished",
+    ChannelTalkingStarted => "ChannelTalkingStarted",
+    ChannelToneDetected => "ChannelToneDetected",
+    ChannelTransfer => "ChannelTransfer",
+    ChannelUnhold => "ChannelUnhold",
+    ChannelUserEvent => "ChannelUserevent",
+    ChannelVarSet => "ChannelVarset",
+    ContactInfo => "ContactInfo",
+    ContactStatusChange => "ContactStatusChange",
+    DeviceStateChanged => "DeviceStateChanged",
+    Dial => "Dial",
+    EndpointStateChange => "EndpointStateChange",
+    MissingParams => "MissingParams",
+    Peer => "Peer",
+    PeerStatusChange => "PeerStatusChange",
+    PlaybackContinuing => "PlaybackContinuing",
+    PlaybackFinished => "PlaybackFinished",
+    PlaybackStarted => "PlaybackStarted",
+    RecordingFailed => "RecordingFailed",
+    RecordingFinished => "RecordingFinished",
+    RecordingStarted => "RecordingStarted",
+    RestResponse => "RESTResponse",
+    StasisEnd => "StasisEnd",
+    StasisStart => "StasisStart",
+    TextMessageReceived => "TextMessageReceived",
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// State of the WebSocket connection to Asterisk.
+///
+/// Events are lost while the connection is down, so state derived from the events has to
+/// be refreshed once `Reconnected` is received.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// The connection is established.
+    Connected,
+    /// The connection was lost, reconnection attempts are running.
+    Disconnected,
+    /// The connection was established again after being lost.
+    Reconnected,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -681,4 +1018,184 @@ mod tests {
         let ari_event: Event = serde_json::from_str(e).unwrap();
         println!("{:#?}", ari_event);
     }
+
+    /// Parses the fixture of each event, which must not fall through to `Event::Unknown`, and
+    /// lists the fixtures in `FIXTURES`, by the `type` of their event.
+    macro_rules! fixtures {
+        ($($test:ident: $variant:ident => $file:literal,)*) => {
+            const FIXTURES: &[(&str, &str)] = &[
+                $(($file, include_str!(concat!("fixtures/", $file, ".json"))),)*
+            ];
+
+            mod fixtures {
+                use super::super::*;
+
+                $(
+                    #[test]
+                    fn $test() {
+                        let json = include_str!(concat!("fixtures/", $file, ".json"));
+                        let event: Event = serde_json::from_str(json).unwrap();
+                        assert!(matches!(event, Event::$variant(_)), "{:?}", event);
+                        assert!(event.timestamp().is_some());
+
+                        assert_eq!(event.kind().map(|kind| kind.as_str()), Some($file));
+                    }
+                )*
+            }
+        };
+    }
+
+    fixtures! {
+        application_move_failed: ApplicationMoveFailed => "ApplicationMoveFailed",
+        application_registered: ApplicationRegistered => "ApplicationRegistered",
+        application_replaced: ApplicationReplaced => "ApplicationReplaced",
+        application_unregistered: ApplicationUnregistered => "ApplicationUnregistered",
+        bridge_attended_transfer: BridgeAttendedTransfer => "BridgeAttendedTransfer",
+        bridge_blind_transfer: BridgeBlindTransfer => "BridgeBlindTransfer",
+        bridge_created: BridgeCreated => "BridgeCreated",
+        bridge_destroyed: BridgeDestroyed => "BridgeDestroyed",
+        bridge_merged: BridgeMerged => "BridgeMerged",
+        bridge_video_source_changed: BridgeVideoSourceChanged => "BridgeVideoSourceChanged",
+        channel_caller_id: ChannelCallerId => "ChannelCallerId",
+        channel_connected_line: ChannelConnectedLine => "ChannelConnectedLine",
+        channel_created: ChannelCreated => "ChannelCreated",
+        channel_destroyed: ChannelDestroyed => "ChannelDestroyed",
+        channel_dialplan: ChannelDialplan => "ChannelDialplan",
+        channel_dtmf_received: ChannelDtmfReceived => "ChannelDtmfReceived",
+        channel_entered_bridge: ChannelEnteredBridge => "ChannelEnteredBridge",
+        channel_hangup_request: ChannelHangupRequest => "ChannelHangupRequest",
+        channel_hold: ChannelHold => "ChannelHold",
+        channel_left_bridge: ChannelLeftBridge => "ChannelLeftBridge",
+        channel_rtp_statistics: ChannelRtpStatistics => "ChannelRtpStatistics",
+        channel_state_change: ChannelStateChange => "ChannelStateChange",
+        channel_talking_finished: ChannelTalkingFinished => "ChannelTalkingFinished",
+        channel_talking_started: ChannelTalkingStarted => "ChannelTalkingStarted",
+        channel_tone_detected: ChannelToneDetected => "ChannelToneDetected",
+        channel_transfer: ChannelTransfer => "ChannelTransfer",
+        channel_unhold: ChannelUnhold => "ChannelUnhold",
+        channel_user_event: ChannelUserEvent => "ChannelUserevent",
+        channel_var_set: ChannelVarSet => "ChannelVarset",
+        contact_info: ContactInfo => "ContactInfo",
+        contact_status_change: ContactStatusChange => "ContactStatusChange",
+        device_state_changed: DeviceStateChanged => "DeviceStateChanged",
+        dial: Dial => "Dial",
+        endpoint_state_change: EndpointStateChange => "EndpointStateChange",
+        missing_params: MissingParams => "MissingParams",
+        peer: Peer => "Peer",
+        peer_status_change: PeerStatusChange => "PeerStatusChange",
+        playback_continuing: PlaybackContinuing => "PlaybackContinuing",
+        playback_finished: PlaybackFinished => "PlaybackFinished",
+        playback_started: PlaybackStarted => "PlaybackStarted",
+        rest_response: RestResponse => "RESTResponse",
+        recording_failed: RecordingFailed => "RecordingFailed",
+        recording_finished: RecordingFinished => "RecordingFinished",
+        recording_started: RecordingStarted => "RecordingStarted",
+        stasis_end: StasisEnd => "StasisEnd",
+        stasis_start: StasisStart => "StasisStart",
+        text_message_received: TextMessageReceived => "TextMessageReceived",
+    }
+
+    /// The event of a fixture, embedded in the tests with the others in `FIXTURES`.
+    fn fixture(file: &str) -> Event {
+        let (_, json) = FIXTURES.iter().find(|(name, _)| *name == file).unwrap();
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_serialize_like_asterisk() {
+        for (file, json) in FIXTURES {
+            let original: serde_json::Value = serde_json::from_str(json).unwrap();
+            let event: Event = serde_json::from_value(original.clone()).unwrap();
+            let serialized = serde_json::to_value(&event).unwrap();
+            assert_eq!(serialized["type"], original["type"], "{file}");
+            assert_eq!(
+                serde_json::from_value::<Event>(serialized).unwrap(),
+                event,
+                "{file}"
+            );
+        }
+
+        let event = fixture("StasisStart");
+        let serialized = serde_json::to_value(&event).unwrap();
+        assert_eq!(serialized["timestamp"], "2025-01-07T21:12:31.571+0000");
+        assert_eq!(serialized["application"], "hello-world");
+        assert_eq!(serialized["channel"]["id"], "1736284349.1");
+        assert_eq!(serialized["channel"]["dialplan"]["app_name"], "Stasis");
+        assert!(serialized.get("replace_channel").is_none());
+
+        let unknown = serde_json::json!({ "type": "NotKnown", "value": 1 });
+        let event: Event = serde_json::from_value(unknown.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&event).unwrap(), unknown);
+    }
+
+    #[test]
+    fn test_parse_tech_causes() {
+        let Event::ChannelHangupRequest(e) = fixture("ChannelHangupRequest") else {
+            panic!("not a ChannelHangupRequest");
+        };
+        assert_eq!(
+            (e.data.cause, e.data.tech_cause),
+            (HangupCause::NormalClearing, Some(200))
+        );
+
+        let Event::ChannelDestroyed(e) = fixture("ChannelDestroyed") else {
+            panic!("not a ChannelDestroyed");
+        };
+        assert_eq!(
+            (e.data.cause, e.data.tech_cause),
+            (HangupCause::UserBusy, Some(486))
+        );
+        assert_eq!(e.data.cause.text(), e.data.cause_txt);
+    }
+
+    #[test]
+    fn test_parse_dial_and_dtmf() {
+        let Event::Dial(e) = fixture("Dial") else {
+            panic!("not a Dial");
+        };
+        assert_eq!(e.data.dialstatus, DialStatus::Answer);
+        assert!(e.data.dialstatus.is_final());
+
+        let Event::ChannelDtmfReceived(e) = fixture("ChannelDtmfReceived") else {
+            panic!("not a ChannelDtmfReceived");
+        };
+        assert_eq!(e.data.digit, '5');
+
+        let (_, json) = FIXTURES
+            .iter()
+            .find(|(name, _)| *name == "ChannelDtmfReceived")
+            .unwrap();
+        let json = json.replace(r#""digit": "5""#, r#""digit": "E""#);
+        let Ok(Event::ChannelDtmfReceived(e)) = serde_json::from_str(&json) else {
+            panic!("a ChannelDtmfReceived with E is not parsed");
+        };
+        assert_eq!(e.data.digit, 'E');
+    }
+
+    #[test]
+    fn test_parse_channel_transfer() {
+        let Event::ChannelTransfer(e) = fixture("ChannelTransfer") else {
+            panic!("not a ChannelTransfer");
+        };
+        let destination = &e.data.refer_to.requested_destination;
+        assert_eq!(destination.destination.as_deref(), Some("200"));
+        assert_eq!(
+            destination.additional_protocol_params[0].parameter_name,
+            "X-Reason"
+        );
+        assert_eq!(e.data.referred_by.source_channel.id, "1736284349.1");
+    }
+
+    #[test]
+    fn test_parse_rest_response() {
+        let event = fixture("RESTResponse");
+        assert_eq!(event.to_string(), "RestResponse");
+        let Event::RestResponse(e) = event else {
+            panic!("not a RESTResponse");
+        };
+        assert_eq!(
+            (e.data.status_code, e.data.request_id.as_str()),
+            (200, "req-1")
+        );
+    }
 }
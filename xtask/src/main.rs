@@ -0,0 +1,392 @@
+//! Development tasks of the workspace, run with `cargo xtask <task>`.
+//!
+//! `codegen` reads the Swagger definitions of ARI in `rest-api/api-docs`, copied from the
+//! Asterisk sources, and writes the operations they declare to `src/apis/generated`. With `--check`, it fails instead when the generated code differs
+//! from the definitions; the tests of this crate run the check, e.g. `cargo test -p xtask`.
+//!
+//! ```text
+//! cargo xtask codegen
+//! cargo xtask codegen --check
+//! cargo xtask codegen ~/src/asterisk/rest-api/api-docs
+//! ```
+
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Directory of the definitions, from the root of the workspace.
+const API_DOCS: &str = "rest-api/api-docs";
+
+/// Directory of the generated code, from the root of the workspace.
+const OUTPUT: &str = "src/apis/generated";
+
+const USAGE: &str = "usage: cargo xtask codegen [--check] [<api-docs dir>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let Some((task, args)) = args.split_first() else {
+        return Err(USAGE.into());
+    };
+    if task != "codegen" {
+        return Err(format!("unknown task {task:?}\n{USAGE}"));
+    }
+
+    let mut check = false;
+    let mut api_docs = None;
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            _ if api_docs.is_none() => api_docs = Some(PathBuf::from(arg)),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    let api_docs = api_docs.unwrap_or_else(|| workspace().join(API_DOCS));
+    let output = workspace().join(OUTPUT);
+
+    let generated = generate(&read_api_docs(&api_docs)?)?;
+    if check {
+        return self::check(&output, &generated);
+    }
+    std::fs::create_dir_all(&output)
+        .map_err(|e| format!("unable to create {}: {e}", output.display()))?;
+    for (file, contents) in &generated {
+        let path = output.join(file);
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("unable to write {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+fn workspace() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// Fails when the generated files differ from the ones in `output`, or when `output` has
+/// other files.
+fn check(output: &Path, generated: &[(String, String)]) -> Result<(), String> {
+    for (file, expected) in generated {
+        let path = output.join(file);
+        let current = std::fs::read_to_string(&path).unwrap_or_default();
+        if let Some((line, expected)) = first_difference(&current, expected) {
+            return Err(format!(
+                "{} is out of date (line {line}, expected {expected:?}); \
+                 run `cargo xtask codegen` to update it",
+                path.display()
+            ));
+        }
+    }
+
+    let entries = std::fs::read_dir(output)
+        .map_err(|e| format!("unable to read {}: {e}", output.display()))?;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let file = entry.file_name().to_string_lossy().into_owned();
+        if !generated.iter().any(|(generated, _)| *generated == file) {
+            return Err(format!(
+                "{} is not generated; remove it",
+                entry.path().display()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the API declarations of a directory, sorted by file name.
+///
+/// `resources.json`, which only lists the other files, is skipped.
+fn read_api_docs(dir: &Path) -> Result<Vec<(String, Value)>, String> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|e| format!("unable to read {}: {e}", dir.display()))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .filter(|path| path.file_stem().map_or(false, |stem| stem != "resources"))
+        .collect();
+    files.sort();
+
+    files
+        .iter()
+        .map(|path| {
+            let resource = path.file_stem().unwrap().to_string_lossy().into_owned();
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| format!("unable to read {}: {e}", path.display()))?;
+            let declaration = serde_json::from_str(&contents)
+                .map_err(|e| format!("invalid JSON in {}: {e}", path.display()))?;
+            Ok((resource, declaration))
+        })
+        .collect()
+}
+
+/// Generates the files of `src/apis/generated` from the API declarations: a module of
+/// operations by resource, and `mod.rs`.
+fn generate(declarations: &[(String, Value)]) -> Result<Vec<(String, String)>, String> {
+    let mut files = vec![];
+    let mut modules = vec![];
+    for (resource, declaration) in declarations {
+        let module = snake_case(resource);
+        files.push((
+            format!("{module}.rs"),
+            generate_resource(resource, declaration)?,
+        ));
+        modules.push(module);
+    }
+
+    let mut out = String::new();
+    out.push_str(MOD_HEADER);
+    out.push('\n');
+    for module in &modules {
+        let _ = writeln!(out, "pub mod {module};");
+    }
+    files.push(("mod.rs".to_string(), out));
+    files.sort();
+    Ok(files)
+}
+
+const MOD_HEADER: &str = "\
+//! Bindings of ARI, generated by `cargo xtask codegen` from the definitions in
+//! `rest-api/api-docs`; do not edit.
+//!
+//! Each module of a resource declares its operations, which the APIs of the client send
+//! their requests through, e.g. [`channels::ORIGINATE`].
+";
+
+fn generate_resource(resource: &str, declaration: &Value) -> Result<String, String> {
+    let mut operations = String::new();
+    for api in array(declaration, "apis") {
+        let path = string(api, "path", resource)?;
+        for operation in array(api, "operations") {
+            write_operation(&mut operations, path, operation)?;
+        }
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "//! Operations of `{resource}.json`, generated by `cargo xtask codegen`; do not edit."
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "use crate::apis::operation::{{BodyParams, Operation}};"
+    );
+    let _ = writeln!(out, "use crate::apis::transport::Method;");
+    out.push_str(&operations);
+    Ok(out)
+}
+
+/// A parameter of an operation.
+struct Param<'a> {
+    name: &'a str,
+    kind: &'a str,
+    data_type: &'a str,
+    required: bool,
+}
+
+fn write_operation(out: &mut String, path: &str, operation: &Value) -> Result<(), String> {
+    let nickname = string(operation, "nickname", path)?;
+    let method = string(operation, "httpMethod", nickname)?;
+
+    let mut params = vec![];
+    for param in array(operation, "parameters") {
+        let name = string(param, "name", nickname)?;
+        let kind = string(param, "paramType", name)?;
+        if !matches!(kind, "path" | "query" | "body") {
+            return Err(format!("{nickname}: unknown paramType {kind:?} of {name}"));
+        }
+        params.push(Param {
+            name,
+            kind,
+            data_type: string(param, "dataType", name)?,
+            required: flag(param, "required") || kind == "path",
+        });
+    }
+    let names = |kind: &str, required: bool| -> String {
+        let names: Vec<String> = params
+            .iter()
+            .filter(|param| param.kind == kind && (param.required || !required))
+            .map(|param| format!("{:?}", param.name))
+            .collect();
+        format!("&[{}]", names.join(", "))
+    };
+
+    // A body parameter of type `object` is the whole body, the others are keys of it.
+    let whole_body = params
+        .iter()
+        .find(|param| param.kind == "body" && param.data_type == "object");
+    let body_params = match whole_body {
+        Some(param) if params.iter().filter(|param| param.kind == "body").count() > 1 => {
+            return Err(format!(
+                "{nickname}: {} is not the only body parameter",
+                param.name
+            ))
+        }
+        Some(param) => format!("BodyParams::Whole({:?})", param.name),
+        None => format!("BodyParams::Keys({})", names("body", false)),
+    };
+
+    let constant = snake_case(nickname).to_uppercase();
+    let _ = writeln!(out);
+    let _ = writeln!(out, "/// `{method} {path}`");
+    let _ = writeln!(out, "pub const {constant}: Operation = Operation {{");
+    let _ = writeln!(out, "    nickname: {nickname:?},");
+    let _ = writeln!(out, "    method: Method::{method},");
+    let _ = writeln!(out, "    path: {path:?},");
+    let _ = writeln!(out, "    query: {},", names("query", false));
+    let _ = writeln!(out, "    required: {},", names("query", true));
+    let _ = writeln!(out, "    body: {body_params},");
+    let _ = writeln!(out, "}};");
+    Ok(())
+}
+
+/// `getChannelVar` to `get_channel_var`, `sendDTMF` to `send_dtmf`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            let previous = chars[i - 1];
+            let next_lower = chars
+                .get(i + 1)
+                .map_or(false, |next| next.is_ascii_lowercase());
+            if previous.is_ascii_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+fn array<'v>(value: &'v Value, key: &str) -> impl Iterator<Item = &'v Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+fn string<'v>(value: &'v Value, key: &str, context: &str) -> Result<&'v str, String> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{context}: missing {key:?}"))
+}
+
+fn flag(value: &Value, key: &str) -> bool {
+    value.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// The first line, counted from 1, where the generated file differs, with its expected
+/// content.
+fn first_difference<'a>(current: &str, expected: &'a str) -> Option<(usize, &'a str)> {
+    let mut current = current.lines();
+    for (i, line) in expected.lines().enumerate() {
+        if current.next() != Some(line) {
+            return Some((i + 1, line));
+        }
+    }
+    current.next().map(|_| (expected.lines().count() + 1, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHANNELS: &str = r#"{
+        "apiVersion": "2.0.0",
+        "swaggerVersion": "1.1",
+        "resourcePath": "/api-docs/channels.{format}",
+        "apis": [
+            {
+                "path": "/channels/{channelId}/variable",
+                "operations": [
+                    {
+                        "httpMethod": "GET",
+                        "summary": "Get the value of a channel variable or function.",
+                        "nickname": "getChannelVar",
+                        "responseClass": "Variable",
+                        "parameters": [
+                            {
+                                "name": "channelId",
+                                "paramType": "path",
+                                "required": true,
+                                "allowMultiple": false,
+                                "dataType": "string"
+                            },
+                            {
+                                "name": "variable",
+                                "paramType": "query",
+                                "required": true,
+                                "allowMultiple": false,
+                                "dataType": "string"
+                            }
+                        ]
+                    }
+                ]
+            }
+        ],
+        "models": {
+            "Variable": {
+                "id": "Variable",
+                "properties": {
+                    "value": { "required": true, "type": "string" }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_generates_operations() {
+        let declarations = vec![(
+            "channels".to_string(),
+            serde_json::from_str(CHANNELS).unwrap(),
+        )];
+        let files = generate(&declarations).unwrap();
+        let file = |name: &str| &files.iter().find(|(file, _)| file == name).unwrap().1;
+
+        assert!(file("channels.rs").contains(
+            "pub const GET_CHANNEL_VAR: Operation = Operation {\n    nickname: \"getChannelVar\",\n    method: Method::GET,\n    path: \"/channels/{channelId}/variable\",\n    query: &[\"variable\"],\n"
+        ));
+        assert!(file("channels.rs")
+            .contains("    required: &[\"variable\"],\n    body: BodyParams::Keys(&[]),\n};"));
+        assert!(file("mod.rs").ends_with("\npub mod channels;\n"));
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(snake_case("sendDTMF"), "send_dtmf");
+        assert_eq!(snake_case("absorbDTMF"), "absorb_dtmf");
+        assert_eq!(snake_case("deviceStates"), "device_states");
+    }
+
+    #[test]
+    fn test_check_reports_the_first_difference() {
+        assert_eq!(first_difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(first_difference("a\nc\n", "a\nb\n"), Some((2, "b")));
+        assert_eq!(first_difference("a\n", "a\nb\n"), Some((2, "b")));
+        assert_eq!(first_difference("a\nb\n", "a\n"), Some((2, "")));
+    }
+
+    #[test]
+    fn test_generated_code_is_up_to_date() {
+        let declarations = read_api_docs(&workspace().join(API_DOCS)).unwrap();
+        let generated = generate(&declarations).unwrap();
+        if let Err(e) = check(&workspace().join(OUTPUT), &generated) {
+            panic!("{e}");
+        }
+    }
+}